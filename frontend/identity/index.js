@@ -1,2 +1,2 @@
-function oi(t){return Uint8Array.fromBase64(t,{alphabet:"base64url",lastChunkHandling:"loose"})}function Tn(t){return t.toBase64({alphabet:"base64url",omitPadding:!0})}var xr="ts_token",A=class{#t;#n;#e=null;#r=null;constructor(n,e){this.#t=n,this.#n=e}setBody(n){return this.#r=n,this}setHeaders(n){return this.#e=n,this}async fetch(){return await Zu(this.#t,this.#n,this.#e,this.#r)}};function ai(t){Object.defineProperty(globalThis,"tokenDomain",{value:t,writable:!0,configurable:!0})}async function zn(){let t=await globalThis.window.cookieStore.get(xr);if(!t)return null;let n=t.value.split(".");if(n.length!==3)return await Dn(),null;let e=new TextDecoder,r=JSON.parse(e.decode(oi(n[1])));return{bearer:t.value,claims:r}}async function Dn(){console.info("deleting token"),await globalThis.window.cookieStore.delete(xr)}async function jr(t){if(globalThis.tokenDomain==null||globalThis.tokenDomain==null)throw new Error("`globalThis.tokenDomain` has not been set, token cannot be saved.");console.info("setting token"),await globalThis.window.cookieStore.set({domain:globalThis.tokenDomain,name:xr,value:t,sameSite:"strict",expires:Date.now()+1e3*60*60*24*30,partitioned:void 0,path:void 0})}async function Zu(t,n,e,r){let o=new Headers;if(e)for(let u of e)o.append(u[0],u[1]);r&&o.append("content-type","application/json");let a=await zn();a&&!o.has("Authorization")&&o.append("Authorization",a.bearer);let i=null;r&&(i=JSON.stringify(r));let s=await self.fetch(n,{method:t,body:i,headers:o}).catch(u=>(console.warn(u),new Response(null,{status:500})));if(s.ok){let u=s.headers.get("Authorization");return u&&await jr(u),{status:"ok",body:await s.json().catch(l=>(console.warn(l),{}))}}switch(s.status){case 400:return{status:"badRequest",problems:(await s.json().catch(c=>(console.warn(c),{problems:[]}))).problems??[]};case 401:case 403:return{status:"unauthenticated"}}return{status:"error"}}async function Kn(t){return location.href=t,await Ru()}function Ru(){let t=n=>{setTimeout(()=>t(n),400)};return new Promise(t)}var tt="http://localhost:8081",nt=["X-TS-API-Key","identity-site"];function ii(){ai("")}async function qr(t,n){let e=await Yu(t.sub);if(e.status!=="ok")return e;let r=await Fu();if(r.status!=="ok")return r;let o=await Bu(t.sub,null);if(o.status!=="ok")return o;let a={challenge:e.data,allowCredentials:o.data,hints:["security-key","hybrid","client-device"],rpId:r.data.id,userVerification:"required"},i=PublicKeyCredential.parseRequestOptionsFromJSON(a),s=await navigator.credentials.get({publicKey:i}).catch(()=>null);if(!s)return{status:"cancelled"};if(!(s instanceof PublicKeyCredential))return{status:"error"};let u=await xu(s,"consent",n);return await jr(t.bearer),u}async function Fu(){let t=await new A("GET",tt+"/.well-known/relying-party.json").setHeaders([nt]).fetch();return t.status==="ok"?{status:"ok",data:t.body}:t.status==="unauthenticated"?{status:"unauthenticated"}:{status:"error"}}async function Bu(t,n){let e="";t?e=`?identityId=${t}`:n&&(e=`?username=${n}`);let r=await new A("GET",tt+`/existing-credentials${e}`).setHeaders([nt]).fetch();return r.status==="ok"?{status:"ok",data:r.body.credentials}:r.status==="unauthenticated"?{status:"unauthenticated"}:{status:"error"}}async function Yu(t){let n=await new A("POST",tt+"/challenges").setBody({identityId:t}).setHeaders([nt]).fetch();return n.status==="ok"?{status:"ok",data:n.body.challenge}:n.status==="unauthenticated"?{status:"unauthenticated"}:{status:"error"}}async function xu(t,n,e){if(!(t.response instanceof AuthenticatorAssertionResponse))return{status:"error"};let r=await new A("POST",tt+"/tokens").setHeaders([nt]).setBody({credential:{id:t.id,authenticatorAttachment:t.authenticatorAttachment,rawId:Tn(new Uint8Array(t.rawId)),response:{authenticatorData:Tn(new Uint8Array(t.response.authenticatorData)),clientDataJSON:Tn(new Uint8Array(t.response.clientDataJSON)),signature:Tn(new Uint8Array(t.response.signature)),userHandle:t.response.userHandle?Tn(new Uint8Array(t.response.userHandle)):null}},typ:n,act:e}).fetch();if(r.status==="unauthenticated")return{status:"unauthenticated"};if(r.status!=="ok")return{status:"error"};let o=await zn();return o?{status:"ok",data:o.bearer}:{status:"error"}}function V(t,n,e,r,o){return St(n,((a,i)=>{let s=a[i];if(s===void 0)throw new TypeError(ha(i));return s})(t,n),e,r,o)}function St(t,n,e,r,o,a){let i=Gn(n,e,r);if(o&&n!==i)throw new RangeError(Lc(t,n,e,r,a));return i}function U(t){return t!==null&&/object|function/.test(typeof t)}function G(t,n=Map){let e=new n;return(r,...o)=>{if(e.has(r))return e.get(r);let a=t(r,...o);return e.set(r,a),a}}function En(t){return cn({name:t},1)}function cn(t,n){return gt(e=>({value:e,configurable:1,writable:!n}),t)}function vi(t){return gt(n=>({get:n,configurable:1}),t)}function Le(t){return{[Symbol.toStringTag]:{value:t,configurable:1}}}function Pn(t,n){let e={},r=t.length;for(let o of n)e[t[--r]]=o;return e}function gt(t,n,e){let r={};for(let o in n)r[o]=t(n[o],o,e);return r}function ae(t,n,e){let r={};for(let o=0;o<n.length;o++){let a=n[o];r[a]=t(a,o,e)}return r}function bi(t,n,e){let r={};for(let o=0;o<t.length;o++)r[n[o]]=e[t[o]];return r}function ot(t,n){let e=Object.create(null);for(let r of t)e[r]=n[r];return e}function si(t,n){for(let e of n)if(e in t)return 1;return 0}function Ii(t,n,e){for(let r of t)if(n[r]!==e[r])return 0;return 1}function Mi(t,n,e){let r={...e};for(let o=0;o<n;o++)r[t[o]]=0;return r}function I(t,...n){return(...e)=>t(...n,...e)}function ci(t){return t[0].toUpperCase()+t.substring(1)}function ie(t){return t.slice().sort()}function Fe(t,n){return String(n).padStart(t,"0")}function xt(t,n){return Math.sign(t-n)}function Gn(t,n,e){return Math.min(Math.max(t,n),e)}function Nt(t,n){return[Math.floor(t/n),Jn(t,n)]}function Jn(t,n){return(t%n+n)%n}function qt(t,n){return[$e(t,n),Xr(t,n)]}function $e(t,n){return Math.trunc(t/n)||0}function Xr(t,n){return t%n||0}function Pe(t){return Math.abs(t%1)===.5}function Ei(t,n,e){let r=0,o=0;for(let s=0;s<=n;s++){let u=t[e[s]],c=pt[s],l=C/c,[f,d]=qt(u,l);r+=d*c,o+=f}let[a,i]=qt(r,C);return[o+a,i]}function He(t,n,e){let r={};for(let o=n;o>=0;o--){let a=pt[o];r[e[o]]=$e(t,a),t=Xr(t,a)}return r}function Pi(t){if(t!==void 0)return q(t)}function Ni(t){if(t!==void 0)return yt(t)}function Qr(t){if(t!==void 0)return We(t)}function yt(t){return Oi(We(t))}function We(t){return no(Ol(t))}function Ci(t,n){if(n==null)throw new RangeError(ha(t));return n}function se(t){if(!U(t))throw new TypeError(ol);return t}function to(t,n,e=t){if(typeof n!==t)throw new TypeError(Vt(e,n));return n}function no(t,n="number"){if(!Number.isInteger(t))throw new RangeError(Xu(n,t));return t||0}function Oi(t,n="number"){if(t<=0)throw new RangeError(Qu(n,t));return t}function eo(t){if(typeof t=="symbol")throw new TypeError(rl);return String(t)}function Se(t,n){return U(t)?String(t):q(t,n)}function ro(t){if(typeof t=="string")return BigInt(t);if(typeof t!="bigint")throw new TypeError(el(t));return t}function Si(t,n="number"){if(typeof t=="bigint")throw new TypeError(nl(n));if(t=Number(t),!Number.isFinite(t))throw new RangeError(tl(n,t));return t}function L(t,n){return Math.trunc(Si(t,n))||0}function oo(t,n){return no(Si(t,n),n)}function ui(t,n){return Oi(L(t,n),n)}function ao(t,n){let[e,r]=qt(n,C),o=t+e,a=Math.sign(o);return a&&a===-Math.sign(r)&&(o-=a,r+=a*C),[o,r]}function bn(t,n,e=1){return ao(t[0]+n[0]*e,t[1]+n[1]*e)}function on(t,n){return ao(t[0],t[1]+n)}function ht(t,n){return bn(n,t,-1)}function et(t,n){return xt(t[0],n[0])||xt(t[1],n[1])}function ki(t,n,e){return et(t,n)===-1||et(t,e)===1}function io(t,n=1){let e=BigInt(C/n);return[Number(t/e),Number(t%e)*n]}function Xn(t,n=1){let e=C/n,[r,o]=qt(t,e);return[r,o*n]}function mt(t,n=1,e){let[r,o]=t,[a,i]=qt(o,n);return r*(C/n)+(a+(e?i/n:0))}function so(t,n,e=Nt){let[r,o]=t,[a,i]=e(o,n);return[r*(C/n)+a,i]}function co(t){return V(t,"isoYear",oe,re,1),t.isoYear===oe?V(t,"isoMonth",4,12,1):t.isoYear===re&&V(t,"isoMonth",1,9,1),t}function ut(t){return X({...t,...H,isoHour:12}),t}function X(t){let n=V(t,"isoYear",oe,re,1),e=n===oe?1:n===re?-1:0;return e&&wt(Y({...t,isoDay:t.isoDay+e,isoNanosecond:t.isoNanosecond-e})),t}function wt(t){if(!t||ki(t,Yl,Bl))throw new RangeError(Jt);return t}function Ut(t){return Ei(t,5,st)[1]}function ze(t){let[n,e]=Nt(t,C);return[He(e,5,st),n]}function li(t){return so(t,dt)}function $(t){return Nn(t.isoYear,t.isoMonth,t.isoDay,t.isoHour,t.isoMinute,t.isoSecond,t.isoMillisecond)}function Y(t){let n=$(t);if(n!==void 0){let[e,r]=qt(n,_);return[e,r*bt+(t.isoMicrosecond||0)*me+(t.isoNanosecond||0)]}}function uo(t,n){let[e,r]=ze(Ut(t)-n);return wt(Y({...t,isoDay:t.isoDay+r,...e}))}function Be(...t){return Nn(...t)/Xc}function Nn(...t){let[n,e]=Zi(...t),r=n.valueOf();if(!isNaN(r))return r-e*_}function Zi(t,n=1,e=1,r=0,o=0,a=0,i=0){let s=t===oe?1:t===re?-1:0,u=new Date;return u.setUTCHours(r,o,a,i),u.setUTCFullYear(t,n-1,e+s),[u,s]}function Cn(t,n){let[e,r]=on(t,n);r<0&&(r+=C,e-=1);let[o,a]=Nt(r,bt),[i,s]=Nt(a,me);return Ke(e*_+o,i,s)}function Ke(t,n=0,e=0){let r=Math.ceil(Math.max(0,Math.abs(t)-Na)/_)*Math.sign(t),o=new Date(t-r*_);return Pn(Ir,[o.getUTCFullYear(),o.getUTCMonth()+1,o.getUTCDate()+r,o.getUTCHours(),o.getUTCMinutes(),o.getUTCSeconds(),o.getUTCMilliseconds(),n,e])}function lo(t,n){if(n<-Na)throw new RangeError(Jt);let e=t.formatToParts(n),r={};for(let o of e)r[o.type]=o.value;return r}function fo(t){return[t.isoYear,t.isoMonth,t.isoDay]}function Ri(t,n){return[n,0]}function Fi(){return Yt}function Bi(t,n){switch(n){case 2:return ho(t)?29:28;case 4:case 6:case 9:case 11:return 30}return 31}function Yi(t){return ho(t)?366:365}function ho(t){return t%4==0&&(t%100!=0||t%400==0)}function xi(t){let[n,e]=Zi(t.isoYear,t.isoMonth,t.isoDay);return Jn(n.getUTCDay()-e,7)||7}function ji(t){return this.id===jn?(({isoYear:n})=>n<1?["gregory-inverse",1-n]:["gregory",n])(t):this.id===$t?ql(t):[]}function ju(t){let n=$(t);if(n<jl){let{isoYear:a}=t;return a<1?["japanese-inverse",1-a]:["japanese",a]}let e=lo(Za($t),n),{era:r,eraYear:o}=Xs(e,$t);return[r,o]}function Ve(t){return un(t),On(t,1),t}function un(t){return qi(t,1),t}function fi(t){return Ii(Ma,t,qi(t))}function qi(t,n){let{isoYear:e}=t,r=V(t,"isoMonth",1,Fi(),n);return{isoYear:e,isoMonth:r,isoDay:V(t,"isoDay",1,Bi(e,r),n)}}function On(t,n){return Pn(st,[V(t,"isoHour",0,23,n),V(t,"isoMinute",0,59,n),V(t,"isoSecond",0,59,n),V(t,"isoMillisecond",0,999,n),V(t,"isoMicrosecond",0,999,n),V(t,"isoNanosecond",0,999,n)])}function E(t){return t===void 0?0:mu(se(t))}function ce(t,n=0){t=Tt(t);let e=pu(t),r=Jl(t,n);return[mu(t),r,e]}function Sn(t,n,e,r=9,o=0,a=4){n=Tt(n);let i=hu(n,r,o),s=go(n),u=ye(n,a),c=ge(n,r,o,1);return i==null?i=Math.max(e,c):Hi(i,c),s=yo(s,c,1),t&&(u=(l=>l<4?(l+2)%4:l)(u)),[i,c,s,u]}function Je(t,n=6,e){let r=go(t=_e(t,Ae)),o=ye(t,7),a=ge(t,n);return a=Ci(Ae,a),r=yo(r,a,void 0,e),[a,r,o]}function mo(t){return Ca(Tt(t))}function Ui(t,n){return po(Tt(t),n)}function Ai(t){let n=_e(t,Hr),e=Ht(Hr,Kl,n,0);if(!e)throw new RangeError(Vt(Hr,e));return e}function po(t,n=4){let e=$i(t);return[ye(t,4),...Li(ge(t,n),e)]}function Li(t,n){return t!=null?[pt[t],t<4?9-3*t:-1]:[n===void 0?1:10**(9-n),n]}function go(t){let n=t[_n];return n===void 0?1:L(n,_n)}function yo(t,n,e,r){let o=r?C:pt[n+1];if(o){let a=pt[n];if(o%((t=St(_n,t,1,o/a-(r?0:1),1))*a))throw new RangeError(Vt(_n,t))}else t=St(_n,t,1,e?10**9:1,1);return t}function $i(t){let n=t[$r];if(n!==void 0){if(typeof n!="number"){if(eo(n)==="auto")return;throw new RangeError(Vt($r,n))}n=St($r,Math.floor(n),0,9,1)}return n}function Tt(t){return t===void 0?{}:se(t)}function _e(t,n){return typeof t=="string"?{[n]:t}:se(t)}function Ge(t){return{overflow:Ul[t]}}function wo(t,n,e=9,r=0,o){let a=n[t];if(a===void 0)return o?r:void 0;if(a=eo(a),a==="auto")return o?r:null;let i=zr[a];if(i===void 0&&(i=Zl[a]),i===void 0)throw new RangeError(Wc(t,a,zr));return St(t,i,r,e,1,ga),i}function Ht(t,n,e,r=0){let o=e[t];if(o===void 0)return r;let a=eo(o),i=n[a];if(i===void 0)throw new RangeError(Wc(t,a,n));return i}function Hi(t,n){if(n>t)throw new RangeError(Il)}function Dt(t){return{branding:Nr,epochNanoseconds:t}}function at(t,n,e){return{branding:Ft,calendar:e,timeZone:n,epochNanoseconds:t}}function it(t,n=t.calendar){return{branding:pn,calendar:n,...ot(Rl,t)}}function vt(t,n=t.calendar){return{branding:qn,calendar:n,...ot(Ea,t)}}function Qn(t,n=t.calendar){return{branding:Mr,calendar:n,...ot(Ea,t)}}function Ye(t,n=t.calendar){return{branding:Er,calendar:n,...ot(Ea,t)}}function ft(t){return{branding:Pr,...ot(uu,t)}}function F(t){return{branding:Cr,sign:Wt(t),...ot(va,t)}}function Xe(t){return so(t.epochNanoseconds,bt)[0]}function Wi(t){return((n,e=1)=>{let[r,o]=n,a=Math.floor(o/e),i=C/e;return BigInt(r)*BigInt(i)+BigInt(a)})(t.epochNanoseconds)}function zi(t){return t.epochNanoseconds}function Ki(t,n,e,r,o){let a=an(r),[i,s]=((y,w)=>{let D=w((y=_e(y,Jr))[fu]),b=Vl(y);return b=Ci(Jr,b),[b,D]})(o,t),u=Math.max(i,a);if(!s&&ne(u,s))return di(r,i);if(!s)throw new RangeError(Tr);if(!r.sign)return 0;let[c,l,f]=or(n,e,s),d=Uo(f),h=ar(f),m=Ao(f),p=h(l,c,r);In(s)||(X(c),X(p));let g=m(l,c,p,i);return ne(i,s)?di(g,i):((y,w,D,b,N,O,S)=>{let R=Wt(y),[k,en]=To(b,Ia(D,y),D,R,N,O,S),Bt=Do(w,k,en);return y[P[D]]+Bt*R})(g,d(p),i,l,c,d,h)}function di(t,n){return mt(x(t),pt[n],1)}function To(t,n,e,r,o,a,i){let s=P[e],u={...n,[s]:n[s]+r},c=i(t,o,n),l=i(t,o,u);return[a(c),a(l)]}function Do(t,n,e){let r=mt(ht(n,e));if(!r)throw new RangeError(xn);return mt(ht(n,t))/r}function Vi(t,n){let[e,r,o]=Je(n,5,1);return Dt(tr(t.epochNanoseconds,e,r,o,1))}function Ji(t,n,e){let{epochNanoseconds:r,timeZone:o,calendar:a}=n,[i,s,u]=Je(e);if(i===0&&s===1)return n;let c=t(o);if(i===6)r=((l,f,d,h)=>{let m=Q(d,f),[p,g]=l(m),y=d.epochNanoseconds,w=Lt(f,p),D=Lt(f,g);if(ki(y,w,D))throw new RangeError(xn);return os(Do(y,w,D),h)?D:w})(es,c,n,u);else{let l=c.R(r);r=Zn(c,ts(Cn(r,l),i,s,u),l,2,0,1)}return at(r,o,a)}function _i(t,n){return it(ts(t,...Je(n)),t.calendar)}function Gi(t,n){let[e,r,o]=Je(n,5);var a;return ft((a=o,vo(t,ue(e,r),a)[0]))}function Xi(t,n){let e=t(n.timeZone),r=Q(n,e),[o,a]=es(r),i=mt(ht(Lt(e,o),Lt(e,a)),br,1);if(i<=0)throw new RangeError(xn);return i}function Qi(t,n){let{timeZone:e,calendar:r}=n,o=((a,i,s)=>Lt(i,a(Q(s,i))))(rs,t(e),n);return at(o,e,r)}function ts(t,n,e,r){return ns(t,ue(n,e),r)}function ns(t,n,e){let[r,o]=vo(t,n,e);return X({...ln(t,o),...r})}function vo(t,n,e){return ze(At(Ut(t),n,e))}function xe(t){return At(t,vr,7)}function ue(t,n){return pt[t]*n}function es(t){let n=rs(t);return[n,ln(n,1)]}function rs(t){return Fl(6,t)}function qu(t,n,e){let r=Math.min(an(t),6);return Rn(nr(x(t,r),n,e),r)}function Qe(t,n,e,r,o,a,i,s,u,c){if(r===0&&o===1)return t;let l=ne(r,s)?In(s)&&r<6&&e>=6?Au:Uu:Lu,[f,d,h]=l(t,n,e,r,o,a,i,s,u,c);return h&&r!==7&&(f=((m,p,g,y,w,D,b,N)=>{let O=Wt(m);for(let S=y+1;S<=g;S++){if(S===7&&g!==7)continue;let R=Ia(S,m);R[P[S]]+=O;let k=mt(ht(b(N(w,D,R)),p));if(k&&Math.sign(k)!==O)break;m=R}return m})(f,d,e,Math.max(6,r),i,s,u,c)),f}function tr(t,n,e,r,o){if(n===6){let a=(i=>i[0]+i[1]/C)(t);return[At(a,e,r),0]}return nr(t,ue(n,e),r,o)}function nr(t,n,e,r){let[o,a]=t;r&&a<0&&(a+=C,o-=1);let[i,s]=Nt(At(a,n,e),C);return ao(o+i,s)}function At(t,n,e){return os(t/n,e)*n}function os(t,n){return Xl[n](t)}function Uu(t,n,e,r,o,a){let i=Wt(t),s=x(t),u=tr(s,r,o,a),c=ht(s,u),l=Math.sign(u[0]-s[0])===i,f=Rn(u,Math.min(e,6));return[{...t,...f},bn(n,c),l]}function Au(t,n,e,r,o,a,i,s,u,c){let l=Wt(t)||1,f=mt(x(t,5)),d=ue(r,o),h=At(f,d,a),[m,p]=To(i,{...t,...ba},6,l,s,u,c),g=h-mt(ht(m,p)),y=0;g&&Math.sign(g)!==l?n=on(m,h):(y+=l,h=At(g,d,a),n=on(p,h));let w=sr(h);return[{...t,...w,days:t.days+y},n,!!y]}function Lu(t,n,e,r,o,a,i,s,u,c){let l=Wt(t),f=P[r],d=Ia(r,t);r===7&&(t={...t,weeks:t.weeks+Math.trunc(t.days/7)});let h=$e(t[f],o)*o;d[f]=h;let[m,p]=To(i,d,r,o*l,s,u,c),g=h+Do(n,m,p)*l*o,y=At(g,o,a),w=Math.sign(y-g)===l;return d[f]=y,[d,w?p:m,w]}function bo(t,n,e,r){let[o,a,i,s]=(c=>{let l=po(c=Tt(c));return[c.timeZone,...l]})(r),u=o!==void 0;return((c,l,f,d,h,m)=>{f=nr(f,h,d,1);let p=l.R(f);return Oo(Cn(f,p),m)+(c?kn(xe(p)):"Z")})(u,n(u?t(o):gn),e.epochNanoseconds,a,i,s)}function Io(t,n,e){let[r,o,a,i,s,u]=(c=>{c=Tt(c);let l=Ca(c),f=$i(c),d=Gl(c),h=ye(c,4),m=ge(c,4);return[l,_l(c),d,h,...Li(m,f)]})(e);return((c,l,f,d,h,m,p,g,y,w)=>{d=nr(d,y,g,1);let D=c(f).R(d);return Oo(Cn(d,D),w)+kn(xe(D),p)+((b,N)=>N!==1?"["+(N===2?"!":"")+b+"]":"")(f,m)+So(l,h)})(t,n.calendar,n.timeZone,n.epochNanoseconds,r,o,a,i,s,u)}function Mo(t,n){let[e,r,o,a]=(c=>(c=Tt(c),[Ca(c),...po(c)]))(n);return i=t.calendar,s=e,u=a,Oo(ns(t,o,r),u)+So(i,s);var i,s,u}function Eo(t,n){return e=t.calendar,r=t,o=mo(n),je(r)+So(e,o);var e,r,o}function Po(t,n){return as(t.calendar,is,t,mo(n))}function No(t,n){return as(t.calendar,$u,t,mo(n))}function Co(t,n){let[e,r,o]=Ui(n);return a=o,ss(vo(t,r,e)[0],a);var a}function er(t,n){let[e,r,o]=Ui(n,3);return r>1&&fn(t={...t,...qu(t,r,e)}),((a,i)=>{let{sign:s}=a,u=s===-1?W(a):a,{hours:c,minutes:l}=u,[f,d]=so(x(u,3),dt,qt);ms(f);let h=ko(d,i),m=i>=0||!s||h;return(s<0?"-":"")+"P"+hi({Y:rn(u.years),M:rn(u.months),W:rn(u.weeks),D:rn(u.days)})+(c||l||f||m?"T"+hi({H:rn(c),M:rn(l),S:rn(f,m)+h}):"")})(t,o)}function as(t,n,e,r){let o=r>1||r===0&&t!==M;return r===1?t===M?n(e):je(e):o?je(e)+cs(t,r===2):n(e)}function hi(t){let n=[];for(let e in t){let r=t[e];r&&n.push(r,e)}return n.join("")}function Oo(t,n){return je(t)+"T"+ss(t,n)}function je(t){return is(t)+"-"+lt(t.isoDay)}function is(t){let{isoYear:n}=t;return(n<0||n>9999?us(n)+Fe(6,Math.abs(n)):Fe(4,n))+"-"+lt(t.isoMonth)}function $u(t){return lt(t.isoMonth)+"-"+lt(t.isoDay)}function ss(t,n){let e=[lt(t.isoHour),lt(t.isoMinute)];return n!==-1&&e.push(lt(t.isoSecond)+((r,o,a,i)=>ko(r*bt+o*me+a,i))(t.isoMillisecond,t.isoMicrosecond,t.isoNanosecond,n)),e.join(":")}function kn(t,n=0){if(n===1)return"";let[e,r]=Nt(Math.abs(t),br),[o,a]=Nt(r,vr),[i,s]=Nt(a,dt);return us(t)+lt(e)+":"+lt(o)+(i||s?":"+lt(i)+ko(s):"")}function So(t,n){return n!==1&&(n>1||n===0&&t!==M)?cs(t,n===2):""}function cs(t,n){return"["+(n?"!":"")+"u-ca="+t+"]"}function ko(t,n){let e=Fe(9,t);return e=n===void 0?e.replace(nf,""):e.slice(0,n),e?"."+e:""}function us(t){return t<0?"-":"+"}function rn(t,n){return t||n?t.toLocaleString("fullwide",{useGrouping:0}):""}function Hu(t,n){let{epochNanoseconds:e}=t,r=(n.R?n:n(t.timeZone)).R(e),o=Cn(e,r);return{calendar:t.calendar,...o,offsetNanoseconds:r}}function Zn(t,n,e,r=0,o=0,a,i){if(e!==void 0&&r===1&&(r===1||i))return uo(n,e);let s=t.I(n);if(e!==void 0&&r!==3){let u=((c,l,f,d)=>{let h=Y(l);d&&(f=xe(f));for(let m of c){let p=mt(ht(m,h));if(d&&(p=xe(p)),p===f)return m}})(s,n,e,a);if(u!==void 0)return u;if(r===0)throw new RangeError(yl)}return i?Y(n):le(t,n,o,s)}function le(t,n,e=0,r=t.I(n)){if(r.length===1)return r[0];if(e===1)throw new RangeError(wl);if(r.length)return r[e===3?1:0];let o=Y(n),a=((s,u)=>{let c=s.R(on(u,-C));return(l=>{if(l>C)throw new RangeError(gl);return l})(s.R(on(u,C))-c)})(t,o),i=a*(e===2?-1:1);return(r=t.I(Cn(o,i)))[e===2?0:r.length-1]}function Lt(t,n){let e=t.I(n);if(e.length)return e[0];let r=on(Y(n),-C);return t.O(r,1)}function Zo(t,n,e){return Dt(wt(bn(n.epochNanoseconds,(r=>{if(ps(r))throw new RangeError(vl);return x(r,5)})(t?W(e):e))))}function Ro(t,n,e,r,o,a=Object.create(null)){let i=n(r.timeZone),s=t(r.calendar);return{...r,...jo(i,s,r,e?W(o):o,a)}}function Fo(t,n,e,r,o=Object.create(null)){let{calendar:a}=e;return it(qo(t(a),e,n?W(r):r,o),a)}function Bo(t,n,e,r,o){let{calendar:a}=e;return vt(rr(t(a),e,n?W(r):r,o),a)}function Yo(t,n,e,r,o){let a=e.calendar,i=t(a),s=ut(te(i,e));n&&(r=ir(r)),r.sign<0&&(s=i.P(s,{...j,months:1}),s=ln(s,-1));let u=i.P(s,r,o);return Qn(te(i,u),a)}function xo(t,n,e){return ft(ls(n,t?W(e):e)[0])}function jo(t,n,e,r,o){let a=x(r,5),i=e.epochNanoseconds;if(ps(r)){let s=Q(e,t);i=bn(le(t,{...rr(n,s,{...r,...ba},o),...ot(st,s)}),a)}else i=bn(i,a),E(o);return{epochNanoseconds:wt(i)}}function qo(t,n,e,r){let[o,a]=ls(n,e);return X({...rr(t,n,{...e,...ba,days:e.days+a},r),...o})}function rr(t,n,e,r){if(e.years||e.months||e.weeks)return t.P(n,e,r);E(r);let o=e.days+x(e,5)[0];return o?ut(ln(n,o)):n}function te(t,n,e=1){return ln(n,e-t.day(n))}function ls(t,n){let[e,r]=x(n,5),[o,a]=ze(Ut(t)+r);return[o,e+a]}function ln(t,n){return n?{...t,...Ke($(t)+n*_)}:t}function or(t,n,e){let r=t(e.calendar);return In(e)?[e,r,n(e.timeZone)]:[{...e,...H},r]}function Uo(t){return t?zi:Y}function ar(t){return t?I(jo,t):qo}function Ao(t){return t?I(zu,t):Ku}function In(t){return t&&t.epochNanoseconds}function ne(t,n){return t<=6-(In(n)?1:0)}function Lo(t,n,e,r,o,a,i){let s=t(Tt(i).relativeTo),u=Math.max(an(o),an(a));if(ne(u,s))return F(fn(((p,g,y,w)=>{let D=bn(x(p),x(g),w?-1:1);if(!Number.isFinite(D[0]))throw new RangeError(Jt);return{...j,...Rn(D,y)}})(o,a,u,r)));if(!s)throw new RangeError(Tr);r&&(a=W(a));let[c,l,f]=or(n,e,s),d=ar(f),h=Ao(f),m=d(l,c,o);return F(h(l,c,d(l,m,a),u))}function fs(t,n,e,r,o){let a=an(r),[i,s,u,c,l]=((O,S,R)=>{O=_e(O,Ae);let k=hu(O),en=R(O[fu]),Bt=go(O),ku=ye(O,7),Pt=ge(O);if(k===void 0&&Pt===void 0)throw new RangeError(bl);if(Pt==null&&(Pt=0),k==null&&(k=Math.max(Pt,S)),Hi(k,Pt),Bt=yo(Bt,Pt,1),Bt>1&&Pt>5&&k!==Pt)throw new RangeError("For calendar units with roundingIncrement > 1, use largestUnit = smallestUnit");return[k,Pt,Bt,ku,en]})(o,a,t),f=Math.max(a,i);if(!l&&f<=6)return F(fn(((O,S,R,k,en)=>{let Bt=tr(x(O),R,k,en);return{...j,...Rn(Bt,S)}})(r,i,s,u,c)));if(!In(l)&&!r.sign)return r;if(!l)throw new RangeError(Tr);let[d,h,m]=or(n,e,l),p=Uo(m),g=ar(m),y=Ao(m),w=g(h,d,r);In(l)||(X(d),X(w));let D=y(h,d,w,i),b=r.sign,N=Wt(D);if(b&&N&&b!==N)throw new RangeError(xn);return D=Qe(D,p(w),i,s,u,c,h,d,p,g),F(D)}function ds(t){return t.sign===-1?ir(t):t}function ir(t){return F(W(t))}function W(t){let n={};for(let e of P)n[e]=-1*t[e]||0;return n}function hs(t){return!t.sign}function Wt(t,n=P){let e=0;for(let r of n){let o=Math.sign(t[r]);if(o){if(e&&e!==o)throw new RangeError(Dl);e=o}}return e}function fn(t){for(let n of kl)St(n,t[n],-wi,wi,1);return ms(mt(x(t),dt)),t}function ms(t){if(!Number.isSafeInteger(t))throw new RangeError(Tl)}function x(t,n=6){return Ei(t,n,P)}function Rn(t,n=6){let[e,r]=t,o=He(r,n,P);if(o[P[n]]+=e*(C/pt[n]),!Number.isFinite(o[P[n]]))throw new RangeError(Jt);return o}function sr(t,n=5){return He(t,n,P)}function ps(t){return!!Wt(t,cu)}function an(t){let n=9;for(;n>0&&!t[P[n]];n--);return n}function Wu(t,n){return[t,n]}function mi(t){let n=Math.floor(t/Ze)*Ze;return[n,n+Ze]}function gs(t){let n=zt(t=Se(t));if(!n)throw new RangeError(J(t));let e;if(n.j)e=0;else{if(!n.offset)throw new RangeError(J(t));e=dn(n.offset)}return n.timeZone&&zo(n.timeZone,1),Dt(uo(Ve(n),e))}function ys(t){let n=zt(q(t));if(!n)throw new RangeError(J(t));if(n.timeZone)return Ps(n,n.offset?dn(n.offset):void 0);if(n.j)throw new RangeError(J(t));return Cs(n)}function ws(t,n){let e=zt(q(t));if(!e||!e.timeZone)throw new RangeError(J(t));let{offset:r}=e,o=r?dn(r):void 0,[,a,i]=ce(n);return Ps(e,o,a,i)}function dn(t){let n=zo(t);if(n===void 0)throw new RangeError(J(t));return n}function Ts(t){let n=zt(q(t));if(!n||n.j)throw new RangeError(J(t));return it(Ns(n))}function cr(t,n,e){let r=zt(q(t));if(!r||r.j)throw new RangeError(J(t));return n?r.calendar===M&&(r=r.isoYear===-271821&&r.isoMonth===4?{...r,isoDay:20,...H}:{...r,isoDay:1,...H}):e&&r.calendar===M&&(r={...r,isoYear:Ot}),vt(r.C?Ns(r):Cs(r))}function Ds(t,n){let e=Ho(q(n));if(e)return $o(e),Qn(co(un(e)));let r=cr(n,1);return Qn(te(t(r.calendar),r))}function $o(t){if(t.calendar!==M)throw new RangeError(Ct(t.calendar))}function vs(t,n){let e=Wo(q(n));if(e)return $o(e),Ye(un(e));let r=cr(n,0,1),{calendar:o}=r,a=t(o),[i,s,u]=a.v(r),[c,l]=a.q(i,s),[f,d]=a.G(c,l,u);return Ye(ut(a.V(f,d,u)),o)}function bs(t){let n,e=(r=>{let o=sf.exec(r);return o?(ur(o[10]),ks(o)):void 0})(q(t));if(!e){if(e=zt(t),!e)throw new RangeError(J(t));if(!e.C)throw new RangeError(J(t));if(e.j)throw new RangeError(Ct("Z"));$o(e)}if((n=Ho(t))&&fi(n))throw new RangeError(J(t));if((n=Wo(t))&&fi(n))throw new RangeError(J(t));return ft(On(e,1))}function Is(t){let n=(e=>{let r=lf.exec(e);return r?(o=>{function a(l,f,d){let h=0,m=0;if(d&&([h,u]=Nt(u,pt[d])),l!==void 0){if(s)throw new RangeError(Ct(l));m=(p=>{let g=parseInt(p);if(!Number.isFinite(g))throw new RangeError(Ct(p));return g})(l),i=1,f&&(u=Ko(f)*(pt[d]/dt),s=1)}return h+m}let i=0,s=0,u=0,c={...Pn(P,[a(o[2]),a(o[3]),a(o[4]),a(o[5]),a(o[6],o[7],5),a(o[8],o[9],4),a(o[10],o[11],3)]),...He(u,2,P)};if(!i)throw new RangeError($c(P));return Vo(o[1])<0&&(c=W(c)),c})(r):void 0})(q(t));if(!n)throw new RangeError(J(t));return F(fn(n))}function Ms(t){let n=zt(t)||Ho(t)||Wo(t);return n?n.calendar:t}function Es(t){let n=zt(t);return n&&(n.timeZone||n.j&&gn||n.offset)||t}function Ps(t,n,e=0,r=0){let o=lr(t.timeZone),a=v(o),i;return Ve(t),i=t.C?Zn(a,t,n,e,r,!a.$,t.j):Lt(a,t),at(i,o,de(t.calendar))}function Ns(t){return Os(X(Ve(t)))}function Cs(t){return Os(ut(un(t)))}function Os(t){return{...t,calendar:de(t.calendar)}}function zt(t){let n=af.exec(t);return n?(e=>{let r=e[10],o=(r||"").toUpperCase()==="Z";return{isoYear:Ss(e),isoMonth:parseInt(e[4]),isoDay:parseInt(e[5]),...ks(e.slice(5)),...ur(e[16]),C:!!e[6],j:o,offset:o?void 0:r}})(n):void 0}function Ho(t){let n=rf.exec(t);return n?(e=>({isoYear:Ss(e),isoMonth:parseInt(e[4]),isoDay:1,...ur(e[5])}))(n):void 0}function Wo(t){let n=of.exec(t);return n?(e=>({isoYear:Ot,isoMonth:parseInt(e[1]),isoDay:parseInt(e[2]),...ur(e[3])}))(n):void 0}function zo(t,n){let e=cf.exec(t);return e?((r,o)=>{let a=r[4]||r[5];if(o&&a)throw new RangeError(Ct(a));return(i=>{if(Math.abs(i)>=C)throw new RangeError(pl);return i})((vn(r[2])*br+vn(r[3])*vr+vn(r[4])*dt+Ko(r[5]||""))*Vo(r[1]))})(e,n):void 0}function Ss(t){let n=Vo(t[1]),e=parseInt(t[2]||t[3]);if(n<0&&!e)throw new RangeError(Ct(-0));return n*e}function ks(t){let n=vn(t[3]);return{...ze(Ko(t[4]||""))[0],isoHour:vn(t[1]),isoMinute:vn(t[2]),isoSecond:n===60?59:n}}function ur(t){let n,e,r=[];if(t.replace(uf,(o,a,i)=>{let s=!!a,[u,c]=i.split("=").reverse();if(c){if(c==="u-ca")r.push(u),n||(n=s);else if(s||/[A-Z]/.test(c))throw new RangeError(Ct(o))}else{if(e)throw new RangeError(Ct(o));e=u}return""}),r.length>1&&n)throw new RangeError(Ct(t));return{timeZone:e,calendar:r[0]||M}}function Ko(t){return parseInt(t.padEnd(9,"0"))}function Fn(t){return new RegExp(`^${t}$`,"i")}function Vo(t){return t&&t!=="+"?-1:1}function vn(t){return t===void 0?0:parseInt(t)}function Zs(t){return lr(q(t))}function lr(t){let n=Jo(t);return typeof n=="number"?kn(n):n?(e=>{if(hf.test(e))throw new RangeError(pa(e));if(df.test(e))throw new RangeError(ml);return e.toLowerCase().split("/").map((r,o)=>(r.length<=3||/\d/.test(r))&&!/etc|yap/.test(r)?r.toUpperCase():r.replace(/baja|dumont|[a-z]+/g,(a,i)=>a.length<=2&&!o||a==="in"||a==="chat"?a.toUpperCase():a.length>2||!i?ci(a).replace(/island|noronha|murdo|rivadavia|urville/,ci):a)).join("/")})(t):gn}function pi(t){let n=Jo(t);return typeof n=="number"?n:n?n.resolvedOptions().timeZone:gn}function Jo(t){let n=zo(t=t.toUpperCase(),1);return n!==void 0?n:t!==gn?ff(t):void 0}function _o(t,n){return et(t.epochNanoseconds,n.epochNanoseconds)}function Go(t,n){return et(t.epochNanoseconds,n.epochNanoseconds)}function Rs(t,n,e,r,o,a){let i=t(Tt(a).relativeTo),s=Math.max(an(r),an(o));if(Ii(P,r,o))return 0;if(ne(s,i))return et(x(r),x(o));if(!i)throw new RangeError(Tr);let[u,c,l]=or(n,e,i),f=Uo(l),d=ar(l);return et(f(d(c,u,r)),f(d(c,u,o)))}function Xo(t,n){return hn(t,n)||fr(t,n)}function hn(t,n){return xt($(t),$(n))}function fr(t,n){return xt(Ut(t),Ut(n))}function Fs(t,n){return!_o(t,n)}function Bs(t,n){return!Go(t,n)&&!!As(t.timeZone,n.timeZone)&&t.calendar===n.calendar}function Ys(t,n){return!Xo(t,n)&&t.calendar===n.calendar}function xs(t,n){return!hn(t,n)&&t.calendar===n.calendar}function js(t,n){return!hn(t,n)&&t.calendar===n.calendar}function qs(t,n){return!hn(t,n)&&t.calendar===n.calendar}function Us(t,n){return!fr(t,n)}function As(t,n){if(t===n)return 1;try{return pi(t)===pi(n)}catch{}}function Qo(t,n,e,r){let o=Sn(t,r,3,5),a=dr(n.epochNanoseconds,e.epochNanoseconds,...o);return F(t?W(a):a)}function ta(t,n,e,r,o,a){let i=mr(r.calendar,o.calendar),[s,u,c,l]=Sn(e,a,5),f=r.epochNanoseconds,d=o.epochNanoseconds,h=et(d,f),m;if(h)if(s<6)m=dr(f,d,s,u,c,l);else{let p=n(((y,w)=>{if(!As(y,w))throw new RangeError(Jc);return y})(r.timeZone,o.timeZone)),g=t(i);m=$s(g,p,r,o,h,s,a),m=Qe(m,d,s,u,c,l,g,r,zi,I(jo,p))}else m=j;return F(e?W(m):m)}function na(t,n,e,r,o){let a=mr(e.calendar,r.calendar),[i,s,u,c]=Sn(n,o,6),l=Y(e),f=Y(r),d=et(f,l),h;if(d)if(i<=6)h=dr(l,f,i,s,u,c);else{let m=t(a);h=Hs(m,e,r,d,i,o),h=Qe(h,f,i,s,u,c,m,e,Y,qo)}else h=j;return F(n?W(h):h)}function ea(t,n,e,r,o){let a=mr(e.calendar,r.calendar);return Ls(n,()=>t(a),e,r,...Sn(n,o,6,9,6))}function ra(t,n,e,r,o){let a=mr(e.calendar,r.calendar),i=Sn(n,o,9,9,8),s=t(a),u=te(s,e),c=te(s,r);return u.isoYear===c.isoYear&&u.isoMonth===c.isoMonth&&u.isoDay===c.isoDay?F(j):Ls(n,()=>s,ut(u),ut(c),...i,8)}function Ls(t,n,e,r,o,a,i,s,u=6){let c=Y(e),l=Y(r);if(c===void 0||l===void 0)throw new RangeError(Jt);let f;if(et(l,c))if(o===6)f=dr(c,l,o,a,i,s);else{let d=n();f=d.N(e,r,o),a===u&&i===1||(f=Qe(f,l,o,a,i,s,d,e,Y,rr))}else f=j;return F(t?W(f):f)}function oa(t,n,e,r){let[o,a,i,s]=Sn(t,r,5,5),u=At(aa(n,e),ue(a,i),s),c={...j,...sr(u,o)};return F(t?W(c):c)}function zu(t,n,e,r,o,a){let i=et(r.epochNanoseconds,e.epochNanoseconds);return i?o<6?Ws(e.epochNanoseconds,r.epochNanoseconds,o):$s(n,t,e,r,i,o,a):j}function Ku(t,n,e,r,o){let a=Y(n),i=Y(e),s=et(i,a);return s?r<=6?Ws(a,i,r):Hs(t,n,e,s,r,o):j}function $s(t,n,e,r,o,a,i){let[s,u,c]=((d,h,m,p)=>{function g(){return S={...ln(D,N++*-p),...w},R=le(d,S),et(b,R)===-p}let y=Q(h,d),w=ot(st,y),D=Q(m,d),b=m.epochNanoseconds,N=0,O=aa(y,D),S,R;if(Math.sign(O)===-p&&N++,g()&&(p===-1||g()))throw new RangeError(xn);let k=mt(ht(R,b));return[y,S,k]})(n,e,r,o);var l,f;return{...a===6?(l=s,f=u,{...j,days:zs(l,f)}):t.N(s,u,a,i),...sr(c)}}function Hs(t,n,e,r,o,a){let[i,s,u]=((c,l,f)=>{let d=l,h=aa(c,l);return Math.sign(h)===-f&&(d=ln(l,-f),h+=C*f),[c,d,h]})(n,e,r);return{...t.N(i,s,o,a),...sr(u)}}function dr(t,n,e,r,o,a){return{...j,...Rn(tr(ht(t,n),r,o,a),e)}}function Ws(t,n,e){return{...j,...Rn(ht(t,n),e)}}function zs(t,n){return hr($(t),$(n))}function hr(t,n){return Math.trunc((n-t)/_)}function aa(t,n){return Ut(n)-Ut(t)}function mr(t,n){if(t!==n)throw new RangeError(Vc);return t}function Ks(t){return this.m(t)[0]}function Vs(t){return this.m(t)[1]}function ia(t){let[n]=this.v(t);return hr(this.p(n),$(t))+1}function sa(t){let n=mf.exec(t);if(!n)throw new RangeError(dl(t));return[parseInt(n[1]),!!n[2]]}function fe(t,n){return"M"+lt(t)+(n?"L":"")}function qe(t,n,e){return t+(n||e&&t>=e?1:0)}function ca(t,n){return t-(n&&t>=n?1:0)}function Js(t,n){return(n+t)*(Math.sign(n)||1)||0}function Wr(t){return au[Gs(t)]}function _s(t){return Cl[Gs(t)]}function Gs(t){return sn(t.id||M)}function Vu(t){function n(o){return((a,i)=>({...Xs(a,i),o:a.month,day:parseInt(a.day)}))(lo(e,o),r)}let e=Za(t),r=sn(t);return{id:t,h:Ju(n),l:_u(n)}}function Ju(t){return G(n=>{let e=$(n);return t(e)},WeakMap)}function _u(t){let n=t(0).year-xl;return G(e=>{let r,o=Nn(e-n),a=0,i=[],s=[];do o+=400*_;while((r=t(o)).year<=e);do if(o+=(1-r.day)*_,r.year===e&&(i.push(o),s.push(r.o)),o-=_,++a>100||o<-Na)throw new RangeError(xn);while((r=t(o)).year>=e);return{i:i.reverse(),u:Gc(s.reverse())}})}function Xs(t,n){let e,r,o=Qs(t);if(t.era){let a=au[n],i=iu[n]||{};a!==void 0&&(e=n==="islamic"?"ah":t.era.normalize("NFD").toLowerCase().replace(/[^a-z0-9]/g,""),e==="bc"||e==="b"?e="bce":e==="ad"||e==="a"?e="ce":e==="beforeroc"&&(e="broc"),e=i[e]||e,r=o,o=Js(r,a[e]||0))}return{era:e,eraYear:r,year:o}}function Qs(t){return parseInt(t.relatedYear||t.year)}function Ue(t){let{year:n,o:e,day:r}=this.h(t),{u:o}=this.l(n);return[n,o[e]+1,r]}function ee(t,n=1,e=1){return this.l(t).i[n-1]+(e-1)*_}function tc(t,n){let e=ke.call(this,t);return[ca(n,e),e===n]}function ke(t){let n=yi(this,t),e=yi(this,t-1),r=n.length;if(r>e.length){let o=_s(this);if(o<0)return-o;for(let a=0;a<r;a++)if(n[a]!==e[a])return a+1}}function Ne(t){return hr(ee.call(this,t),ee.call(this,t+1))}function gi(t,n){let{i:e}=this.l(t),r=n+1,o=e;return r>e.length&&(r=1,o=this.l(t+1).i),hr(e[n-1],o[r-1])}function Ce(t){return this.l(t).i.length}function nc(t){let n=this.h(t);return[n.era,n.eraYear]}function yi(t,n){return Object.keys(t.l(n).u)}function Bn(t){return de(q(t))}function de(t){if((t=t.toLowerCase())!==M&&t!==jn){let n=Za(t).resolvedOptions().calendar;if(sn(t)!==sn(n))throw new RangeError(ma(t));return n}return t}function sn(t){return t==="islamicc"&&(t="islamic"),t.split("-")[0]}function ec(t,n){return e=>e===M?t:e===jn||e===$t?Object.assign(Object.create(t),{id:e}):Object.assign(Object.create(n),pf(e))}function rc(t,n,e,r){let o=Kt(e,r,Zt,[],nu);if(o.timeZone!==void 0){let a=e.F(o),i=he(o),s=t(o.timeZone);return{epochNanoseconds:Zn(n(s),{...a,...i},o.offset!==void 0?dn(o.offset):void 0),timeZone:s}}return{...e.F(o),...H}}function oc(t,n,e,r,o,a){let i=Kt(e,o,Zt,Qc,nu),s=t(i.timeZone),[u,c,l]=ce(a),f=e.F(i,Ge(u)),d=he(i,u);return at(Zn(n(s),{...f,...d},i.offset!==void 0?dn(i.offset):void 0,c,l),s,r)}function ac(t,n,e){let r=Kt(t,n,Zt,[],It),o=E(e);return it(X({...t.F(r,Ge(o)),...he(r,o)}))}function ic(t,n,e,r=[]){let o=Kt(t,n,Zt,r);return t.F(o,e)}function sc(t,n,e,r){let o=Kt(t,n,Da,r);return t.K(o,e)}function cc(t,n,e,r){let o=Kt(t,e,Zt,pe);return n&&o.month!==void 0&&o.monthCode===void 0&&o.year===void 0&&(o.year=Ot),t._(o,r)}function uc(t,n){return ft(he(rt(t,Kr,[],1),E(n)))}function lc(t){let n=rt(t,va);return F(fn({...j,...n}))}function Kt(t,n,e,r=[],o=[]){return rt(n,[...t.fields(e),...o].sort(),r)}function rt(t,n,e,r=!e){let o={},a,i=0;for(let s of n){if(s===a)throw new RangeError(il(s));if(s==="constructor"||s==="__proto__")throw new RangeError(al(s));let u=t[s];if(u!==void 0)i=1,Ti[s]&&(u=Ti[s](u,s)),o[s]=u;else if(e){if(e.includes(s))throw new TypeError(ha(s));o[s]=ou[s]}a=s}if(r&&!i)throw new TypeError($c(n));return o}function he(t,n){return On(Ra({...ou,...t}),n)}function fc(t,n,e,r,o){let{calendar:a,timeZone:i}=e,s=t(a),u=n(i),c=[...s.fields(Zt),...tu].sort(),l=(y=>{let w=Q(y,v),D=kn(w.offsetNanoseconds),b=Sr(y.calendar),[N,O,S]=b.v(w),[R,k]=b.q(N,O),en=fe(R,k);return{...If(w),year:N,monthCode:en,day:S,offset:D}})(e),f=rt(r,c),d=s.k(l,f),h={...l,...f},[m,p,g]=ce(o,2);return at(Zn(u,{...s.F(d,Ge(m)),...On(Ra(h),m)},dn(h.offset),p,g),i,a)}function dc(t,n,e,r){let o=t(n.calendar),a=[...o.fields(Zt),...It].sort(),i={...Tc(s=n),hour:s.isoHour,minute:s.isoMinute,second:s.isoSecond,millisecond:s.isoMillisecond,microsecond:s.isoMicrosecond,nanosecond:s.isoNanosecond};var s;let u=rt(e,a),c=E(r),l=o.k(i,u),f={...i,...u};return it(X({...o.F(l,Ge(c)),...On(Ra(f),c)}))}function hc(t,n,e,r){let o=t(n.calendar),a=o.fields(Zt).sort(),i=Tc(n),s=rt(e,a),u=o.k(i,s);return o.F(u,r)}function mc(t,n,e,r){let o=t(n.calendar),a=o.fields(Da).sort(),i=(c=>{let l=Sr(c.calendar),[f,d]=l.v(c),[h,m]=l.q(f,d);return{year:f,monthCode:fe(h,m)}})(n),s=rt(e,a),u=o.k(i,s);return o.K(u,r)}function pc(t,n,e,r){let o=t(n.calendar),a=o.fields(Zt).sort(),i=(c=>{let l=Sr(c.calendar),[f,d,h]=l.v(c),[m,p]=l.q(f,d);return{monthCode:fe(m,p),day:h}})(n),s=rt(e,a),u=o.k(i,s);return o._(u,r)}function gc(t,n,e){return ft(((r,o,a)=>he({...ot(Kr,r),...rt(o,Kr)},E(a)))(t,n,e))}function yc(t,n){return F((e=t,r=n,fn({...e,...rt(r,va)})));var e,r}function wc(t,n,e,r,o){n=ot(e=t.fields(e),n),r=rt(r,o=t.fields(o),[]);let a=t.k(n,r);return a=rt(a,[...e,...o].sort(),[]),t.F(a)}function Ur(t,n){let e=Wr(t),r=iu[t.id||""]||{},{era:o,eraYear:a,year:i}=n;if(o!==void 0||a!==void 0){if(o===void 0||a===void 0)throw new TypeError(cl);if(!e)throw new RangeError(sl);let s=e[r[o]||o];if(s===void 0)throw new RangeError(ll(o));let u=Js(a,s);if(i!==void 0&&i!==u)throw new RangeError(ul);i=u}else if(i===void 0)throw new TypeError(fl(e));return i}function Oe(t,n,e,r){let{month:o,monthCode:a}=n;if(a!==void 0){let i=((s,u,c,l)=>{let f=s.L(c),[d,h]=sa(u),m=qe(d,h,f);if(h){let p=_s(s);if(p===void 0)throw new RangeError(Vn);if(p>0){if(m>p)throw new RangeError(Vn);if(f===void 0){if(l===1)throw new RangeError(Vn);m--}}else{if(m!==-p)throw new RangeError(Vn);if(f===void 0&&l===1)throw new RangeError(Vn)}}return m})(t,a,e,r);if(o!==void 0&&o!==i)throw new RangeError(hl);o=i,r=1}else if(o===void 0)throw new TypeError(Kc);return St("month",o,1,t.B(e),r)}function Ar(t,n,e,r,o){return V(n,"day",1,t.U(r,e),o)}function Lr(t,n,e,r){let o=0,a=[];for(let i of e)n[i]!==void 0?o=1:a.push(i);if(Object.assign(t,n),o)for(let i of r||a)delete t[i]}function Tc(t){let n=Sr(t.calendar),[e,r,o]=n.v(t),[a,i]=n.q(e,r);return{year:e,monthCode:fe(a,i),day:o}}function Dc(t){return Dt(wt(io(ro(t))))}function vc(t,n,e,r,o=M){return at(wt(io(ro(e))),n(r),t(o))}function bc(t,n,e,r,o=0,a=0,i=0,s=0,u=0,c=0,l=M){return it(X(Ve(gt(L,Pn(Ir,[n,e,r,o,a,i,s,u,c])))),t(l))}function Ic(t,n,e,r,o=M){return vt(ut(un(gt(L,{isoYear:n,isoMonth:e,isoDay:r}))),t(o))}function Mc(t,n,e,r=M,o=1){let a=L(n),i=L(e),s=t(r);return Qn(co(un({isoYear:a,isoMonth:i,isoDay:L(o)})),s)}function Ec(t,n,e,r=M,o=Ot){let a=L(n),i=L(e),s=t(r);return Ye(ut(un({isoYear:L(o),isoMonth:a,isoDay:i})),s)}function Pc(t=0,n=0,e=0,r=0,o=0,a=0){return ft(On(gt(L,Pn(st,[t,n,e,r,o,a])),1))}function Nc(t=0,n=0,e=0,r=0,o=0,a=0,i=0,s=0,u=0,c=0){return F(fn(gt(oo,Pn(P,[t,n,e,r,o,a,i,s,u,c]))))}function Cc(t,n,e=M){return at(t.epochNanoseconds,n,e)}function Oc(t){return Dt(t.epochNanoseconds)}function ua(t,n){return it(Q(n,t))}function la(t,n){return vt(Q(n,t))}function fa(t,n){return ft(Q(n,t))}function Sc(t,n,e,r){let o=((a,i,s,u)=>{let c=(l=>pu(Tt(l)))(u);return le(a(i),s,c)})(t,e,n,r);return at(wt(o),e,n.calendar)}function kc(t,n,e,r,o){let a=t(o.timeZone),i=o.plainTime,s=i!==void 0?n(i):void 0,u=e(a),c;return c=s?le(u,{...r,...s}):Lt(u,{...r,...H}),at(c,a,r.calendar)}function Zc(t,n=H){return it(X({...t,...n}))}function Rc(t,n,e){return((r,o)=>{let a=Kt(r,o,eu);return r.K(a,void 0)})(t(n.calendar),e)}function Fc(t,n,e){return((r,o)=>{let a=Kt(r,o,ru);return r._(a)})(t(n.calendar),e)}function Bc(t,n,e,r){return((o,a,i)=>wc(o,a,eu,se(i),pe))(t(n.calendar),e,r)}function Yc(t,n,e,r){return((o,a,i)=>wc(o,a,ru,se(i),ya))(t(n.calendar),e,r)}function xc(t){return Dt(wt(Xn(oo(t),bt)))}function jc(t){return Dt(wt(io(ro(t))))}function mn(t,n,e){let r=new Set(e);return(o,a)=>{let i=e&&si(o,e);if(!si(o=((s,u)=>{let c={};for(let l in u)s.has(l)||(c[l]=u[l]);return c})(r,o),t)){if(a&&i)throw new TypeError("Invalid formatting options");o={...n,...o}}return e&&(o.timeZone=gn,["full","long"].includes(o.J)&&(o.J="medium")),o}}function kt(t,n=da,e=0){let[r,,,o]=t;return(a,i=Lf,...s)=>{let u=n(o&&o(...s),a,i,r,e),c=u.resolvedOptions();return[u,...Gu(t,c,s)]}}function da(t,n,e,r,o){if(e=r(e,o),t){if(e.timeZone!==void 0)throw new TypeError(Ml);e.timeZone=t}return new Rt(n,e)}function Gu(t,n,e){let[,r,o]=t;return e.map(a=>(a.calendar&&((i,s,u)=>{if((u||i!==M)&&i!==s)throw new RangeError(Vc)})(a.calendar,n.calendar,o),r(a,n)))}function qc(t,n,e){let r=n.timeZone,o=t(r),a={...Q(n,o),...e||H},i;return i=e?Zn(o,a,a.offsetNanoseconds,2):Lt(o,a),at(i,r,n.calendar)}function Uc(t,n=H){return it(X({...t,...n}))}function pr(t,n){return{...t,calendar:n}}function Ac(t,n){return{...t,timeZone:n}}function gr(t){let n=yr();return Cn(n,t.R(n))}function yr(){return Xn(Date.now(),bt)}function Yn(){return Di||(Di=new Rt().resolvedOptions().timeZone)}var Xu=(t,n)=>`Non-integer ${t}: ${n}`,Qu=(t,n)=>`Non-positive ${t}: ${n}`,tl=(t,n)=>`Non-finite ${t}: ${n}`,nl=t=>`Cannot convert bigint to ${t}`,el=t=>`Invalid bigint: ${t}`,rl="Cannot convert Symbol to string",ol="Invalid object",Lc=(t,n,e,r,o)=>o?Lc(t,o[n],o[e],o[r]):Vt(t,n)+`; must be between ${e}-${r}`,Vt=(t,n)=>`Invalid ${t}: ${n}`,ha=t=>`Missing ${t}`,al=t=>`Invalid field ${t}`,il=t=>`Duplicate field ${t}`,$c=t=>"No valid fields: "+t.join(),Hc="Invalid bag",Wc=(t,n,e)=>Vt(t,n)+"; must be "+Object.keys(e).join(),zc="Cannot use valueOf",wr="Invalid calling context",sl="Forbidden era/eraYear",cl="Mismatching era/eraYear",ul="Mismatching year/eraYear",ll=t=>`Invalid era: ${t}`,fl=t=>"Missing year"+(t?"/era/eraYear":""),dl=t=>`Invalid monthCode: ${t}`,hl="Mismatching month/monthCode",Kc="Missing month/monthCode",Vn="Invalid leap month",xn="Invalid protocol results",ma=t=>Vt("Calendar",t),Vc="Mismatching Calendars",pa=t=>Vt("TimeZone",t),Jc="Mismatching TimeZones",ml="Forbidden ICU TimeZone",pl="Out-of-bounds offset",gl="Out-of-bounds TimeZone gap",yl="Invalid TimeZone offset",wl="Ambiguous offset",Jt="Out-of-bounds date",Tl="Out-of-bounds duration",Dl="Cannot mix duration signs",Tr="Missing relativeTo",vl="Cannot use large units",bl="Required smallestUnit or largestUnit",Il="smallestUnit > largestUnit",J=t=>`Cannot parse: ${t}`,Ct=t=>`Invalid substring: ${t}`,_c=t=>`Cannot format ${t}`,Dr="Mismatching types for formatting",Ml="Cannot specify TimeZone",Gc=I(ae,(t,n)=>n),Mn=I(ae,(t,n,e)=>e),lt=I(Fe,2),zr={nanosecond:0,microsecond:1,millisecond:2,second:3,minute:4,hour:5,day:6,week:7,month:8,year:9},ga=Object.keys(zr),_=864e5,Xc=1e3,me=1e3,bt=1e6,dt=1e9,vr=6e10,br=36e11,C=864e11,pt=[1,me,bt,dt,vr,br,C],It=ga.slice(0,6),Kr=ie(It),El=["offset"],Qc=["timeZone"],tu=It.concat(El),nu=tu.concat(Qc),Vr=["era","eraYear"],Pl=Vr.concat(["year"]),ya=["year"],wa=["monthCode"],Ta=["month"].concat(wa),pe=["day"],Da=Ta.concat(ya),eu=wa.concat(ya),Zt=pe.concat(Da),Nl=pe.concat(Ta),ru=pe.concat(wa),ou=Mn(It,0),M="iso8601",jn="gregory",$t="japanese",au={[jn]:{"gregory-inverse":-1,gregory:0},[$t]:{"japanese-inverse":-1,japanese:0,meiji:1867,taisho:1911,showa:1925,heisei:1988,reiwa:2018},ethiopic:{ethioaa:0,ethiopic:5500},coptic:{"coptic-inverse":-1,coptic:0},roc:{"roc-inverse":-1,roc:0},buddhist:{be:0},islamic:{ah:0},indian:{saka:0},persian:{ap:0}},iu={[jn]:{bce:"gregory-inverse",ce:"gregory"},[$t]:{bce:"japanese-inverse",ce:"japanese"},ethiopic:{era0:"ethioaa",era1:"ethiopic"},coptic:{era0:"coptic-inverse",era1:"coptic"},roc:{broc:"roc-inverse",minguo:"roc"}},Cl={chinese:13,dangi:13,hebrew:-6},q=I(to,"string"),su=I(to,"boolean"),Ol=I(to,"number"),P=ga.map(t=>t+"s"),va=ie(P),Sl=P.slice(0,6),cu=P.slice(6),kl=cu.slice(1),Zl=Gc(P),j=Mn(P,0),ba=Mn(Sl,0),Ia=I(Mi,P),st=["isoNanosecond","isoMicrosecond","isoMillisecond","isoSecond","isoMinute","isoHour"],Ma=["isoDay","isoMonth","isoYear"],Ir=st.concat(Ma),Ea=ie(Ma),uu=ie(st),Rl=ie(Ir),H=Mn(uu,0),Fl=I(Mi,Ir),Pa=1e8,Na=Pa*_,Bl=[Pa,0],Yl=[-Pa,0],re=275760,oe=-271821,Rt=Intl.DateTimeFormat,lu="en-GB",xl=1970,Ot=1972,Yt=12,jl=Nn(1868,9,8),ql=G(ju,WeakMap),Ae="smallestUnit",Jr="unit",_n="roundingIncrement",$r="fractionalSecondDigits",fu="relativeTo",Hr="direction",du={constrain:0,reject:1},Ul=Object.keys(du),Al={compatible:0,reject:1,earlier:2,later:3},Ll={reject:0,use:1,prefer:2,ignore:3},$l={auto:0,never:1,critical:2,always:3},Hl={auto:0,never:1,critical:2},Wl={auto:0,never:1},zl={floor:0,halfFloor:1,ceil:2,halfCeil:3,trunc:4,halfTrunc:5,expand:6,halfExpand:7,halfEven:8},Kl={previous:-1,next:1},ge=I(wo,Ae),hu=I(wo,"largestUnit"),Vl=I(wo,Jr),mu=I(Ht,"overflow",du),pu=I(Ht,"disambiguation",Al),Jl=I(Ht,"offset",Ll),Ca=I(Ht,"calendarName",$l),_l=I(Ht,"timeZoneName",Hl),Gl=I(Ht,"offset",Wl),ye=I(Ht,"roundingMode",zl),Mr="PlainYearMonth",Er="PlainMonthDay",qn="PlainDate",pn="PlainDateTime",Pr="PlainTime",Ft="ZonedDateTime",Nr="Instant",Cr="Duration",Xl=[Math.floor,t=>Pe(t)?Math.floor(t):Math.round(t),Math.ceil,t=>Pe(t)?Math.ceil(t):Math.round(t),Math.trunc,t=>Pe(t)?Math.trunc(t)||0:Math.round(t),t=>t<0?Math.floor(t):Math.ceil(t),t=>Math.sign(t)*Math.round(Math.abs(t))||0,t=>Pe(t)?(t=Math.trunc(t)||0)+t%2:Math.round(t)],gn="UTC",Ze=5184e3,Ql=Be(1847),tf=Be(new Date().getUTCFullYear()+10),nf=/0+$/,Q=G(Hu,WeakMap),wi=2**32-1,v=G(t=>{let n=Jo(t);return typeof n=="object"?new Gr(n):new _r(n||0)}),_r=class{constructor(n){this.$=n}R(){return this.$}I(n){return(e=>{let r=Y({...e,...H});if(!r||Math.abs(r[0])>1e8)throw new RangeError(Jt)})(n),[uo(n,this.$)]}O(){}},Gr=class{constructor(n){this.nn=(e=>{function r(c){let l=Gn(c,s,u),[f,d]=mi(l),h=a(f),m=a(d);return h===m?h:o(i(f,d),h,m,c)}function o(c,l,f,d){let h,m;for(;(d===void 0||(h=d<c[0]?l:d>=c[1]?f:void 0)===void 0)&&(m=c[1]-c[0]);){let p=c[0]+Math.floor(m/2);e(p)===f?c[1]=p:c[0]=p+1}return h}let a=G(e),i=G(Wu),s=Ql,u=tf;return{tn(c){let l=r(c-86400),f=r(c+86400),d=c-l,h=c-f;if(l===f)return[d];let m=r(d);return m===r(h)?[c-m]:l>f?[d,h]:[]},rn:r,O(c,l){let f=Gn(c,s,u),[d,h]=mi(f),m=Ze*l,p=l<0?()=>h>s||(s=f,0):()=>d<u||(u=f,0);for(;p();){let g=a(d),y=a(h);if(g!==y){let w=i(d,h);o(w,g,y);let D=w[0];if((xt(D,c)||1)===l)return D}d+=m,h+=m}}}})((e=>r=>{let o=lo(e,r*Xc);return Be(Qs(o),parseInt(o.month),parseInt(o.day),parseInt(o.hour),parseInt(o.minute),parseInt(o.second))-r})(n))}R(n){return this.nn.rn((e=>li(e)[0])(n))*dt}I(n){let[e,r]=[Be((o=n).isoYear,o.isoMonth,o.isoDay,o.isoHour,o.isoMinute,o.isoSecond),o.isoMillisecond*bt+o.isoMicrosecond*me+o.isoNanosecond];var o;return this.nn.tn(e).map(a=>wt(on(Xn(a,dt),r)))}O(n,e){let[r,o]=li(n),a=this.nn.O(r+(e>0||o?1:0),e);if(a!==void 0)return Xn(a,dt)}},Oa="([+-])",Re="(?:[.,](\\d{1,9}))?",gu=`(?:(?:${Oa}(\\d{6}))|(\\d{4}))-?(\\d{2})`,Sa="(\\d{2})(?::?(\\d{2})(?::?(\\d{2})"+Re+")?)?",ka=Oa+Sa,ef=gu+"-?(\\d{2})(?:[T ]"+Sa+"(Z|"+ka+")?)?",yu="\\[(!?)([^\\]]*)\\]",Or=`((?:${yu}){0,9})`,rf=Fn(gu+Or),of=Fn("(?:--)?(\\d{2})-?(\\d{2})"+Or),af=Fn(ef+Or),sf=Fn("T?"+Sa+"(?:"+ka+")?"+Or),cf=Fn(ka),uf=new RegExp(yu,"g"),lf=Fn(`${Oa}?P(\\d+Y)?(\\d+M)?(\\d+W)?(\\d+D)?(?:T(?:(\\d+)${Re}H)?(?:(\\d+)${Re}M)?(?:(\\d+)${Re}S)?)?`),ff=G(t=>new Rt(lu,{timeZone:t,era:"short",year:"numeric",month:"numeric",day:"numeric",hour:"numeric",minute:"numeric",second:"numeric"})),df=/^(AC|AE|AG|AR|AS|BE|BS|CA|CN|CS|CT|EA|EC|IE|IS|JS|MI|NE|NS|PL|PN|PR|PS|SS|VS)T$/,hf=/[^\w\/:+-]+/,mf=/^M(\d{2})(L?)$/,pf=G(Vu),Za=G(t=>new Rt(lu,{calendar:t,timeZone:gn,era:"short",year:"numeric",month:"short",day:"numeric"})),wu={P(t,n,e){let r=E(e),o,{years:a,months:i,weeks:s,days:u}=n;if(u+=x(n,5)[0],a||i)o=((c,l,f,d,h)=>{let[m,p,g]=c.v(l);if(f){let[y,w]=c.q(m,p);m+=f,p=qe(y,w,c.L(m)),p=St("month",p,1,c.B(m),h)}return d&&([m,p]=c.un(m,p,d)),g=St("day",g,1,c.U(m,p),h),c.p(m,p,g)})(this,t,a,i,r);else{if(!s&&!u)return t;o=$(t)}if(o===void 0)throw new RangeError(Jt);return o+=(7*s+u)*_,ut(Ke(o))},N(t,n,e){if(e<=7){let u=0,c=zs({...t,...H},{...n,...H});return e===7&&([u,c]=qt(c,7)),{...j,weeks:u,days:c}}let r=this.v(t),o=this.v(n),[a,i,s]=((u,c,l,f,d,h,m)=>{let p=d-c,g=h-l,y=m-f;if(p||g){let w=Math.sign(p||g),D=u.U(d,h),b=0;if(Math.sign(y)===-w){let N=D;[d,h]=u.un(d,h,-w),p=d-c,g=h-l,D=u.U(d,h),b=w<0?-N:D}if(y=m-Math.min(f,D)+b,p){let[N,O]=u.q(c,l),[S,R]=u.q(d,h);if(g=S-N||Number(R)-Number(O),Math.sign(g)===-w){let k=w<0&&-u.B(d);p=(d-=w)-c,g=h-qe(N,O,u.L(d))+(k||u.B(d))}}}return[p,g,y]})(this,...r,...o);return e===8&&(i+=this.cn(a,r[0]),a=0),{...j,years:a,months:i,days:s}},F(t,n){let e=E(n),r=Ur(this,t),o=Oe(this,t,r,e),a=Ar(this,t,o,r,e);return vt(ut(this.V(r,o,a)),this.id||M)},K(t,n){let e=E(n),r=Ur(this,t),o=Oe(this,t,r,e);return Qn(co(this.V(r,o,1)),this.id||M)},_(t,n){let e=E(n),r,o,a,i=t.eraYear!==void 0||t.year!==void 0?Ur(this,t):void 0,s=!this.id;if(i===void 0&&s&&(i=Ot),i!==void 0){let f=Oe(this,t,i,e);r=Ar(this,t,f,i,e);let d=this.L(i);o=ca(f,d),a=f===d}else{if(t.monthCode===void 0)throw new TypeError(Kc);if([o,a]=sa(t.monthCode),this.id&&this.id!==jn&&this.id!==$t)if(this.id&&sn(this.id)==="coptic"&&e===0){let f=a||o!==13?30:6;r=t.day,r=Gn(r,1,f)}else if(this.id&&sn(this.id)==="chinese"&&e===0){let f=!a||o!==1&&o!==9&&o!==10&&o!==11&&o!==12?30:29;r=t.day,r=Gn(r,1,f)}else r=t.day;else r=Ar(this,t,Oe(this,t,Ot,e),Ot,e)}let u=this.G(o,a,r);if(!u)throw new RangeError("Cannot guess year");let[c,l]=u;return Ye(ut(this.V(c,l,r)),this.id||M)},fields(t){return Wr(this)&&t.includes("year")?[...t,...Vr]:t},k(t,n){let e=Object.assign(Object.create(null),t);return Lr(e,n,Ta),Wr(this)&&(Lr(e,n,Pl),this.id===$t&&Lr(e,n,Nl,Vr)),e},inLeapYear(t){let[n]=this.v(t);return this.sn(n)},monthsInYear(t){let[n]=this.v(t);return this.B(n)},daysInMonth(t){let[n,e]=this.v(t);return this.U(n,e)},daysInYear(t){let[n]=this.v(t);return this.fn(n)},dayOfYear:ia,era(t){return this.hn(t)[0]},eraYear(t){return this.hn(t)[1]},monthCode(t){let[n,e]=this.v(t),[r,o]=this.q(n,e);return fe(r,o)},dayOfWeek:xi,daysInWeek(){return 7}},gf={v:fo,hn:ji,q:Ri},yf={dayOfYear:ia,v:fo,p:Nn},wf=Object.assign({},yf,{weekOfYear:Ks,yearOfWeek:Vs,m(t){function n(h){return(7-h<r?7:0)-h}function e(h){let m=Yi(d+h),p=h||1,g=n(Jn(u+m*p,7));return l=(m+(g-c)*p)/7}let r=this.id?1:4,o=xi(t),a=this.dayOfYear(t),i=Jn(o-1,7),s=a-1,u=Jn(i-s,7),c=n(u),l,f=Math.floor((s-c)/7)+1,d=t.isoYear;return f?f>e(0)&&(f=1,d++):(f=e(-1),d--),[f,d,l]}}),Tf=Object.assign({},wu,wf,{v:fo,hn:ji,q:Ri,G(t,n){if(!n)return[Ot,t]},sn:ho,L(){},B:Fi,cn:t=>t*Yt,U:Bi,fn:Yi,V:(t,n,e)=>({isoYear:t,isoMonth:n,isoDay:e}),p:Nn,un:(t,n,e)=>(t+=$e(e,Yt),(n+=Xr(e,Yt))<1?(t--,n+=Yt):n>Yt&&(t++,n-=Yt),[t,n]),year(t){return t.isoYear},month(t){return t.isoMonth},day:t=>t.isoDay}),Df={v:Ue,hn:nc,q:tc},vf={dayOfYear:ia,v:Ue,p:ee,weekOfYear:Ks,yearOfWeek:Vs,m(){return[]}},bf=Object.assign({},wu,vf,{v:Ue,hn:nc,q:tc,G(t,n,e){let r=this.id&&sn(this.id)==="chinese"?((c,l,f)=>{if(l)switch(c){case 1:return 1651;case 2:return f<30?1947:1765;case 3:return f<30?1966:1955;case 4:return f<30?1963:1944;case 5:return f<30?1971:1952;case 6:return f<30?1960:1941;case 7:return f<30?1968:1938;case 8:return f<30?1957:1718;case 9:return 1832;case 10:return 1870;case 11:return 1814;case 12:return 1890}return 1972})(t,n,e):Ot,[o,a,i]=Ue.call(this,{isoYear:r,isoMonth:Yt,isoDay:31}),s=ke.call(this,o),u=a===s;(xt(t,ca(a,s))||xt(Number(n),Number(u))||xt(e,i))===1&&o--;for(let c=0;c<100;c++){let l=o-c,f=ke.call(this,l),d=qe(t,n,f);if(n===(d===f)&&e<=gi.call(this,l,d))return[l,d]}},sn(t){let n=Ne.call(this,t);return n>Ne.call(this,t-1)&&n>Ne.call(this,t+1)},L:ke,B:Ce,cn(t,n){let e=n+t,r=Math.sign(t),o=r<0?-1:0,a=0;for(let i=n;i!==e;i+=r)a+=Ce.call(this,i+o);return a},U:gi,fn:Ne,V(t,n,e){return Ke(ee.call(this,t,n,e))},p:ee,un(t,n,e){if(e){if(n+=e,!Number.isSafeInteger(n))throw new RangeError(Jt);if(e<0)for(;n<1;)n+=Ce.call(this,--t);else{let r;for(;n>(r=Ce.call(this,t));)n-=r,t++}}return[t,n]},year(t){return this.h(t).year},month(t){let{year:n,o:e}=this.h(t),{u:r}=this.l(n);return r[e]+1},day(t){return this.h(t).day}}),Sr=ec(gf,Df),T=ec(Tf,bf),Ti={era:Se,eraYear:L,year:L,month:ui,monthCode(t){let n=Se(t);return sa(n),n},day:ui,...Mn(It,L),...Mn(P,oo),offset(t){let n=Se(t);return dn(n),n}},Ra=I(bi,It,st),If=I(bi,st,It),jt="numeric",we=["timeZoneName"],Tu={month:jt,day:jt},Fa={year:jt,month:jt},Ba=Object.assign({},Fa,{day:jt}),Ya={hour:jt,minute:jt,second:jt},xa=Object.assign({},Ba,Ya),Mf=Object.assign({},xa,{timeZoneName:"short"}),Ef=Object.keys(Fa),Pf=Object.keys(Tu),Nf=Object.keys(Ba),Cf=Object.keys(Ya),ja=["dateStyle"],Of=Ef.concat(ja),Sf=Pf.concat(ja),qa=Nf.concat(ja,["weekday"]),Te=Cf.concat(["dayPeriod","timeStyle","fractionalSecondDigits"]),Ua=qa.concat(Te),kf=we.concat(Te),Zf=we.concat(qa),Rf=we.concat(["day","weekday"],Te),Ff=we.concat(["year","weekday"],Te),Bf=mn(Ua,xa),Yf=mn(Ua,Mf),xf=mn(Ua,xa,we),jf=mn(qa,Ba,kf),qf=mn(Te,Ya,Zf),Uf=mn(Of,Fa,Rf),Af=mn(Sf,Tu,Ff),Lf={},Du=new Rt(void 0,{calendar:M}).resolvedOptions().calendar===M,Aa=[Bf,Xe],vu=[Yf,Xe,0,(t,n)=>{let e=t.timeZone;if(n&&n.timeZone!==e)throw new RangeError(Jc);return e}],La=[xf,$],$a=[jf,$],Ha=[qf,t=>Ut(t)/bt],Wa=[Uf,$,Du],za=[Af,$,Du],Di;function Qt(t,n,e,r,o){function a(...u){if(!(this instanceof a))throw new TypeError(wr);Mu(this,n(...u))}function i(u,c){return Object.defineProperties(function(...l){return u.call(this,s(this),...l)},En(c))}function s(u){let c=K(u);if(!c||c.branding!==t)throw new TypeError(wr);return c}return Object.defineProperties(a.prototype,{...vi(gt(i,e)),...cn(gt(i,r)),...Le("Temporal."+t)}),Object.defineProperties(a,{...cn(o),...En(t)}),[a,u=>{let c=Object.create(a.prototype);return Mu(c,u),c},s]}function Wn(t){if(K(t)||t.calendar!==void 0||t.timeZone!==void 0)throw new TypeError(Hc);return t}function be(t){return Eu(t)||M}function Eu(t){let{calendar:n}=t;if(n!==void 0)return Rr(n)}function Rr(t){if(U(t)){let{calendar:n}=K(t)||{};if(!n)throw new TypeError(ma(t));return n}return(n=>de(Ms(q(n))))(t)}function Ja(t){let n={};for(let e in t)n[e]=r=>{let{calendar:o}=r;return T(o)[e](r)};return n}function tn(){throw new TypeError(zc)}function ct(t){if(U(t)){let{timeZone:n}=K(t)||{};if(!n)throw new TypeError(pa(t));return n}return(n=>lr(Es(q(n))))(t)}function B(t){if(U(t)){let n=K(t);return n&&n.branding===Cr?n:lc(t)}return Is(t)}function De(t){if(t!==void 0){if(U(t)){let n=K(t)||{};switch(n.branding){case Ft:case qn:return n;case pn:return vt(n)}let e=be(t);return{...rc(ct,v,T(e),t),calendar:e}}return ys(t)}}function _t(t,n){if(U(t)){let r=K(t)||{};switch(r.branding){case Pr:return E(n),r;case pn:return E(n),ft(r);case Ft:return E(n),fa(v,r)}return uc(t,n)}let e=bs(t);return E(n),e}function _a(t){return t===void 0?void 0:_t(t)}function Un(t,n){if(U(t)){let r=K(t)||{};switch(r.branding){case pn:return E(n),r;case qn:return E(n),it({...r,...H});case Ft:return E(n),ua(v,r)}return ac(T(be(t)),t,n)}let e=Ts(t);return E(n),e}function bu(t,n){if(U(t)){let r=K(t);if(r&&r.branding===Er)return E(n),r;let o=Eu(t);return cc(T(o||M),!o,t,n)}let e=vs(T,t);return E(n),e}function An(t,n){if(U(t)){let r=K(t);return r&&r.branding===Mr?(E(n),r):sc(T(be(t)),t,n)}let e=Ds(T,t);return E(n),e}function Ln(t,n){if(U(t)){let r=K(t)||{};switch(r.branding){case qn:return E(n),r;case pn:return E(n),vt(r);case Ft:return E(n),la(v,r)}return ic(T(be(t)),t,n)}let e=cr(t);return E(n),e}function $n(t,n){if(U(t)){let e=K(t);if(e&&e.branding===Ft)return ce(n),e;let r=be(t);return oc(ct,v,T(r),r,t,n)}return ws(t,n)}function Iu(t){return gt(n=>e=>n(Ka(e)),t)}function Ka(t){return Q(t,v)}function Hn(t){if(U(t)){let n=K(t);if(n)switch(n.branding){case Nr:return n;case Ft:return Dt(n.epochNanoseconds)}}return gs(t)}function $f(){function t(a,i){return new n(a,i)}function n(a,i=Object.create(null)){Zr.set(this,((s,u)=>{let c=new Rt(s,u),l=c.resolvedOptions(),f=l.locale,d=ot(Object.keys(u),l),h=G(zf),m=(p,...g)=>{if(p){if(g.length!==2)throw new TypeError(Dr);for(let b of g)if(b===void 0)throw new TypeError(Dr)}p||g[0]!==void 0||(g=[]);let y=g.map(b=>K(b)||Number(b)),w,D=0;for(let b of y){let N=typeof b=="object"?b.branding:void 0;if(D++&&N!==w)throw new TypeError(Dr);w=N}return w?h(w)(f,d,...y):[c,...y]};return m.X=c,m})(a,i))}let e=Rt.prototype,r=Object.getOwnPropertyDescriptors(e),o=Object.getOwnPropertyDescriptors(Rt);for(let a in r){let i=r[a],s=a.startsWith("format")&&Hf(a);typeof i.value=="function"?i.value=a==="constructor"?t:s||Wf(a):s&&(i.get=function(){if(!Zr.has(this))throw new TypeError(wr);return(...u)=>s.apply(this,u)},Object.defineProperties(i.get,En(`get ${a}`)))}return o.prototype.value=n.prototype=Object.create({},r),Object.defineProperties(t,o),t}function Hf(t){return Object.defineProperties(function(...n){let e=Zr.get(this),[r,...o]=e(t.includes("Range"),...n);return r[t](...o)},En(t))}function Wf(t){return Object.defineProperties(function(...n){return Zr.get(this).X[t](...n)},En(t))}function zf(t){let n=Xf[t];if(!n)throw new TypeError(_c(t));return kt(n,G(da),1)}var kr=new WeakMap,K=kr.get.bind(kr),Mu=kr.set.bind(kr),Pu={era:Pi,eraYear:Qr,year:We,month:yt,daysInMonth:yt,daysInYear:yt,inLeapYear:su,monthsInYear:yt},Ga={monthCode:q},Nu={day:yt},Kf={dayOfWeek:yt,dayOfYear:yt,weekOfYear:Ni,yearOfWeek:Qr,daysInWeek:yt},Xa=Ja(Object.assign({},Pu,Ga,Nu,Kf)),Vf=Ja({...Pu,...Ga}),Jf=Ja({...Ga,...Nu}),Ie={calendarId:t=>t.calendar},_f=ae(t=>n=>n[t],P.concat("sign")),Qa=ae((t,n)=>e=>e[st[n]],It),Cu={epochMilliseconds:Xe,epochNanoseconds:Wi},[Gf,Z,Sd]=Qt(Cr,Nc,{..._f,blank:hs},{with:(t,n)=>Z(yc(t,n)),negated:t=>Z(ir(t)),abs:t=>Z(ds(t)),add:(t,n,e)=>Z(Lo(De,T,v,0,t,B(n),e)),subtract:(t,n,e)=>Z(Lo(De,T,v,1,t,B(n),e)),round:(t,n)=>Z(fs(De,T,v,t,n)),total:(t,n)=>Ki(De,T,v,t,n),toLocaleString(t,n,e){return Intl.DurationFormat?new Intl.DurationFormat(n,e).format(this):er(t)},toString:er,toJSON:t=>er(t),valueOf:tn},{from:t=>Z(B(t)),compare:(t,n,e)=>Rs(De,T,v,B(t),B(n),e)}),Xf={Instant:Aa,PlainDateTime:La,PlainDate:$a,PlainTime:Ha,PlainYearMonth:Wa,PlainMonthDay:za},Qf=kt(Aa),td=kt(vu),nd=kt(La),ed=kt($a),rd=kt(Ha),od=kt(Wa),ad=kt(za),[id,Xt]=Qt(Pr,Pc,Qa,{with(t,n,e){return Xt(gc(this,Wn(n),e))},add:(t,n)=>Xt(xo(0,t,B(n))),subtract:(t,n)=>Xt(xo(1,t,B(n))),until:(t,n,e)=>Z(oa(0,t,_t(n),e)),since:(t,n,e)=>Z(oa(1,t,_t(n),e)),round:(t,n)=>Xt(Gi(t,n)),equals:(t,n)=>Us(t,_t(n)),toLocaleString(t,n,e){let[r,o]=rd(n,e,t);return r.format(o)},toString:Co,toJSON:t=>Co(t),valueOf:tn},{from:(t,n)=>Xt(_t(t,n)),compare:(t,n)=>fr(_t(t),_t(n))}),[sd,Mt]=Qt(pn,I(bc,Bn),{...Ie,...Xa,...Qa},{with:(t,n,e)=>Mt(dc(T,t,Wn(n),e)),withCalendar:(t,n)=>Mt(pr(t,Rr(n))),withPlainTime:(t,n)=>Mt(Uc(t,_a(n))),add:(t,n,e)=>Mt(Fo(T,0,t,B(n),e)),subtract:(t,n,e)=>Mt(Fo(T,1,t,B(n),e)),until:(t,n,e)=>Z(na(T,0,t,Un(n),e)),since:(t,n,e)=>Z(na(T,1,t,Un(n),e)),round:(t,n)=>Mt(_i(t,n)),equals:(t,n)=>Ys(t,Un(n)),toZonedDateTime:(t,n,e)=>z(Sc(v,t,ct(n),e)),toPlainDate:t=>Et(vt(t)),toPlainTime:t=>Xt(ft(t)),toLocaleString(t,n,e){let[r,o]=nd(n,e,t);return r.format(o)},toString:Mo,toJSON:t=>Mo(t),valueOf:tn},{from:(t,n)=>Mt(Un(t,n)),compare:(t,n)=>Xo(Un(t),Un(n))}),[cd,Va,kd]=Qt(Er,I(Ec,Bn),{...Ie,...Jf},{with:(t,n,e)=>Va(pc(T,t,Wn(n),e)),equals:(t,n)=>qs(t,bu(n)),toPlainDate(t,n){return Et(Yc(T,t,this,n))},toLocaleString(t,n,e){let[r,o]=ad(n,e,t);return r.format(o)},toString:No,toJSON:t=>No(t),valueOf:tn},{from:(t,n)=>Va(bu(t,n))}),[ud,ve,Zd]=Qt(Mr,I(Mc,Bn),{...Ie,...Vf},{with:(t,n,e)=>ve(mc(T,t,Wn(n),e)),add:(t,n,e)=>ve(Yo(T,0,t,B(n),e)),subtract:(t,n,e)=>ve(Yo(T,1,t,B(n),e)),until:(t,n,e)=>Z(ra(T,0,t,An(n),e)),since:(t,n,e)=>Z(ra(T,1,t,An(n),e)),equals:(t,n)=>js(t,An(n)),toPlainDate(t,n){return Et(Bc(T,t,this,n))},toLocaleString(t,n,e){let[r,o]=od(n,e,t);return r.format(o)},toString:Po,toJSON:t=>Po(t),valueOf:tn},{from:(t,n)=>ve(An(t,n)),compare:(t,n)=>hn(An(t),An(n))}),[ld,Et,Rd]=Qt(qn,I(Ic,Bn),{...Ie,...Xa},{with:(t,n,e)=>Et(hc(T,t,Wn(n),e)),withCalendar:(t,n)=>Et(pr(t,Rr(n))),add:(t,n,e)=>Et(Bo(T,0,t,B(n),e)),subtract:(t,n,e)=>Et(Bo(T,1,t,B(n),e)),until:(t,n,e)=>Z(ea(T,0,t,Ln(n),e)),since:(t,n,e)=>Z(ea(T,1,t,Ln(n),e)),equals:(t,n)=>xs(t,Ln(n)),toZonedDateTime(t,n){let e=U(n)?n:{timeZone:n};return z(kc(ct,_t,v,t,e))},toPlainDateTime:(t,n)=>Mt(Zc(t,_a(n))),toPlainYearMonth(t){return ve(Rc(T,t,this))},toPlainMonthDay(t){return Va(Fc(T,t,this))},toLocaleString(t,n,e){let[r,o]=ed(n,e,t);return r.format(o)},toString:Eo,toJSON:t=>Eo(t),valueOf:tn},{from:(t,n)=>Et(Ln(t,n)),compare:(t,n)=>hn(Ln(t),Ln(n))}),[fd,z]=Qt(Ft,I(vc,Bn,Zs),{...Cu,...Ie,...Iu(Xa),...Iu(Qa),offset:t=>kn(Ka(t).offsetNanoseconds),offsetNanoseconds:t=>Ka(t).offsetNanoseconds,timeZoneId:t=>t.timeZone,hoursInDay:t=>Xi(v,t)},{with:(t,n,e)=>z(fc(T,v,t,Wn(n),e)),withCalendar:(t,n)=>z(pr(t,Rr(n))),withTimeZone:(t,n)=>z(Ac(t,ct(n))),withPlainTime:(t,n)=>z(qc(v,t,_a(n))),add:(t,n,e)=>z(Ro(T,v,0,t,B(n),e)),subtract:(t,n,e)=>z(Ro(T,v,1,t,B(n),e)),until:(t,n,e)=>Z(F(ta(T,v,0,t,$n(n),e))),since:(t,n,e)=>Z(F(ta(T,v,1,t,$n(n),e))),round:(t,n)=>z(Ji(v,t,n)),startOfDay:t=>z(Qi(v,t)),equals:(t,n)=>Bs(t,$n(n)),toInstant:t=>Gt(Oc(t)),toPlainDateTime:t=>Mt(ua(v,t)),toPlainDate:t=>Et(la(v,t)),toPlainTime:t=>Xt(fa(v,t)),toLocaleString(t,n,e={}){let[r,o]=td(n,e,t);return r.format(o)},toString:(t,n)=>Io(v,t,n),toJSON:t=>Io(v,t),valueOf:tn,getTimeZoneTransition(t,n){let{timeZone:e,epochNanoseconds:r}=t,o=Ai(n),a=v(e).O(r,o);return a?z({...t,epochNanoseconds:a}):null}},{from:(t,n)=>z($n(t,n)),compare:(t,n)=>Go($n(t),$n(n))}),[dd,Gt,Fd]=Qt(Nr,Dc,Cu,{add:(t,n)=>Gt(Zo(0,t,B(n))),subtract:(t,n)=>Gt(Zo(1,t,B(n))),until:(t,n,e)=>Z(Qo(0,t,Hn(n),e)),since:(t,n,e)=>Z(Qo(1,t,Hn(n),e)),round:(t,n)=>Gt(Vi(t,n)),equals:(t,n)=>Fs(t,Hn(n)),toZonedDateTimeISO:(t,n)=>z(Cc(t,ct(n))),toLocaleString(t,n,e){let[r,o]=Qf(n,e,t);return r.format(o)},toString:(t,n)=>bo(ct,v,t,n),toJSON:t=>bo(ct,v,t),valueOf:tn},{from:t=>Gt(Hn(t)),fromEpochMilliseconds:t=>Gt(xc(t)),fromEpochNanoseconds:t=>Gt(jc(t)),compare:(t,n)=>_o(Hn(t),Hn(n))}),hd=Object.defineProperties({},{...Le("Temporal.Now"),...cn({timeZoneId:()=>Yn(),instant:()=>Gt(Dt(yr())),zonedDateTimeISO:(t=Yn())=>z(at(yr(),ct(t),M)),plainDateTimeISO:(t=Yn())=>Mt(it(gr(v(ct(t))),M)),plainDateISO:(t=Yn())=>Et(vt(gr(v(ct(t))),M)),plainTimeISO:(t=Yn())=>Xt(ft(gr(v(ct(t)))))})}),ti=Object.defineProperties({},{...Le("Temporal"),...cn({PlainYearMonth:ud,PlainMonthDay:cd,PlainDate:ld,PlainTime:id,PlainDateTime:sd,ZonedDateTime:fd,Instant:dd,Duration:Gf,Now:hd})}),md=$f(),Zr=new WeakMap,ni=Object.defineProperties(Object.create(Intl),cn({DateTimeFormat:md}));function Fr(t){let n=Me().timeZone;return ti.Instant.from(t).toZonedDateTimeISO(n)}function Me(){return ni.DateTimeFormat().resolvedOptions()}var yn=class{element;contents;action;constructor(n,e){this.element=Ou(`${n}/error`,HTMLElement),this.contents=Ou(`${n}/error/content`,HTMLElement),this.action=e}clearError(){this.element.classList.add("collapse"),this.element.ariaHidden="true",this.contents.textContent=""}addError(n){if(this.contents.textContent===""){this.element.classList.remove("collapse"),this.element.ariaHidden="false",this.contents.textContent=`Could not ${this.action}: ${n}`;return}this.contents.textContent+=`, ${n}`}panic(){this.element.classList.remove("collapse"),this.element.ariaHidden="false",this.contents.textContent=`Something went wrong while trying to ${this.action}. Try again later.`}};function Ou(t,n){let e=document.getElementById(t);if(!e||!(e instanceof n))throw`element '${t}' does not exist`;return e}async function Br(){let t=await zn();return t?{bearer:t.bearer,act:t.claims.act??null,exp:t.claims.exp,sub:t.claims.sub,typ:t.claims.typ,tid:t.claims.tid}:null}async function nn(t){await Br()&&(await new A("POST",tt+"/revoked-tokens").setHeaders([nt]).fetch(),alert("Your session has expired")),await Dn();let e=t?`/login?redirect=${encodeURI(location.href)}`:"/login";return await Kn(e)}ii();var wn=await Br();if(!wn)throw await Kn("/login"),new Error;wn.typ==="provisioning"&&await Kn("/add-passkey");document.getElementById("logout").addEventListener("mouseup",async()=>{await Dn(),await nn(!1)});var ei=new yn("/deleteIdentity","delete your identity");document.getElementById("deleteIdentity").addEventListener("mouseup",async()=>{let t=await qr(wn,`DELETE /identities/${wn.sub}`);if(t.status==="cancelled"){ei.addError("the consent prompt was cancelled");return}else if(t.status==="unauthenticated"){await nn(!1);return}else if(t.status!=="ok"){ei.panic();return}let n=await new A("DELETE",tt+`/identities/${wn.sub}`).setHeaders([nt,["Authorization",t.data]]).fetch();if(n.status==="badRequest"&&n.problems.length!=0){for(let e of n.problems)e.detail&&Su.addError(e.detail);return}else if(n.status!=="ok"){ei.panic();return}await Dn(),await nn(!1)});var Ee=await new A("GET",tt+`/identities/${wn.sub}`).setHeaders([nt]).fetch(),pd=new yn("/identity","fetch your identity details");if(Ee.status==="ok"){let t=Fr(Ee.body.created).toPlainDate().toLocaleString(Me().locale,{dateStyle:"long"});document.getElementById("username").textContent=Ee.body.username,document.getElementById("displayName").textContent=Ee.body.displayName,document.getElementById("created").textContent=t}else Ee.status==="unauthenticated"?await nn(!1):pd.panic();var ri=await new A("GET",tt+`/public-keys?identityId=${wn.sub}`).setHeaders([nt]).fetch(),Su=new yn("/passkeys","fetch your passkey details"),Yr=new yn("/deletePasskey","delete your passkey");if(ri.status==="ok"){let t=document.getElementById("passkeys");for(let n of ri.body.publicKeys)gd(n,t)}else ri.status==="unauthenticated"?await nn(!1):Su.panic();function gd(t,n){let e=document.createElement("button");e.setHTMLUnsafe('<svg aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 512 512"><path d="M296,64H216a7.91,7.91,0,0,0-8,8V96h96V72A7.91,7.91,0,0,0,296,64Z" style="fill: none" /><path d="M432,96H336V72a40,40,0,0,0-40-40H216a40,40,0,0,0-40,40V96H80a16,16,0,0,0,0,32H97L116,432.92c1.42,26.85,22,47.08,48,47.08H348c26.13,0,46.3-19.78,48-47L415,128h17a16,16,0,0,0,0-32ZM192.57,416H192a16,16,0,0,1-16-15.43l-8-224a16,16,0,1,1,32-1.14l8,224A16,16,0,0,1,192.57,416ZM272,400a16,16,0,0,1-32,0V176a16,16,0,0,1,32,0ZM304,96H208V72a7.91,7.91,0,0,1,8-8h80a7.91,7.91,0,0,1,8,8Zm32,304.57A16,16,0,0,1,320,416h-.58A16,16,0,0,1,304,399.43l8-224a16,16,0,1,1,32,1.14Z" /></svg>'),e.addEventListener("mouseup",()=>{yd(t.rawId)}),e.className="icon-button circle ghost red",e.ariaLabel=`delete '${t.displayName}' passkey`;let r=document.createElement("h3");r.innerText=t.displayName;let o=document.createElement("div");o.appendChild(r),o.appendChild(e);let a=document.createElement("small"),i=Fr(t.created).toPlainDate().toLocaleString(Me().locale,{dateStyle:"long"});a.innerText=`Created: ${i}`;let s=document.createElement("small"),u=t.lastUsed?Fr(t.lastUsed).toPlainDate().toLocaleString(Me().locale,{dateStyle:"long"}):"Never";s.innerText=`Last Used: ${u}`;let c=document.createElement("div");c.appendChild(a),c.appendChild(s);let l=document.createElement("div");l.className="passkey",l.appendChild(o),l.appendChild(c),n.prepend(l)}async function yd(t){let n=await Br();if(!n){await nn(!1);return}let e=await qr(n,`DELETE /public-keys/${t}`);if(e.status==="cancelled"){Yr.addError("the prompt was cancelled");return}else if(e.status==="unauthenticated"){await nn(!1);return}else if(e.status!=="ok"){Yr.panic();return}let r=await new A("DELETE",tt+`/public-keys/${t}`).setHeaders([nt,["Authorization",e.data]]).fetch();if(r.status==="badRequest"&&r.problems.length!=0){for(let o of r.problems)o.detail&&Yr.addError(o.detail);return}else if(r.status!=="ok"){Yr.panic();return}location.reload()}
+function oi(t){return Uint8Array.fromBase64(t,{alphabet:"base64url",lastChunkHandling:"loose"})}function Tn(t){return t.toBase64({alphabet:"base64url",omitPadding:!0})}var xr="ts_token",A=class{#t;#n;#e=null;#r=null;constructor(n,e){this.#t=n,this.#n=e}setBody(n){return this.#r=n,this}setHeaders(n){return this.#e=n,this}async fetch(){return await Zu(this.#t,this.#n,this.#e,this.#r)}};function ai(t){Object.defineProperty(globalThis,"tokenDomain",{value:t,writable:!0,configurable:!0})}async function zn(){let t=await globalThis.window.cookieStore.get(xr);if(!t)return null;let n=t.value.split(".");if(n.length!==3)return await Dn(),null;let e=new TextDecoder,r=JSON.parse(e.decode(oi(n[1])));return{bearer:t.value,claims:r}}async function Dn(){console.info("deleting token"),await globalThis.window.cookieStore.delete(xr)}async function jr(t){if(globalThis.tokenDomain==null||globalThis.tokenDomain==null)throw new Error("`globalThis.tokenDomain` has not been set, token cannot be saved.");console.info("setting token"),await globalThis.window.cookieStore.set({domain:globalThis.tokenDomain,name:xr,value:t,sameSite:"strict",expires:Date.now()+1e3*60*60*24*30,partitioned:void 0,path:void 0})}async function Zu(t,n,e,r){let o=new Headers;if(e)for(let u of e)o.append(u[0],u[1]);r&&o.append("content-type","application/json");let a=await zn();a&&!o.has("Authorization")&&o.append("Authorization",a.bearer);let i=null;r&&(i=JSON.stringify(r));let s=await self.fetch(n,{method:t,body:i,headers:o}).catch(u=>(console.warn(u),new Response(null,{status:500})));if(s.ok){let u=s.headers.get("Authorization");return u&&await jr(u),{status:"ok",body:await s.json().catch(l=>(console.warn(l),{}))}}switch(s.status){case 400:return{status:"badRequest",problems:(await s.json().catch(c=>(console.warn(c),{problems:[]}))).problems??[]};case 401:case 403:return{status:"unauthenticated"}}return{status:"error"}}async function Kn(t){return location.href=t,await Ru()}function Ru(){let t=n=>{setTimeout(()=>t(n),400)};return new Promise(t)}var tt="http://localhost:8081",nt=["X-TS-API-Key","identity-site"];function ii(){ai("")}async function qr(t,n){let e=await Yu(t.sub,{purpose:"consent",act:n});if(e.status!=="ok")return e;let r=await Fu();if(r.status!=="ok")return r;let o=await Bu(t.sub,null);if(o.status!=="ok")return o;let a={challenge:e.data,allowCredentials:o.data,hints:["security-key","hybrid","client-device"],rpId:r.data.id,userVerification:"required"},i=PublicKeyCredential.parseRequestOptionsFromJSON(a),s=await navigator.credentials.get({publicKey:i}).catch(()=>null);if(!s)return{status:"cancelled"};if(!(s instanceof PublicKeyCredential))return{status:"error"};let u=await xu(s,"consent",n);return await jr(t.bearer),u}async function Fu(){let t=await new A("GET",tt+"/.well-known/relying-party.json").setHeaders([nt]).fetch();return t.status==="ok"?{status:"ok",data:t.body}:t.status==="unauthenticated"?{status:"unauthenticated"}:{status:"error"}}async function Bu(t,n){let e="";t?e=`?identityId=${t}`:n&&(e=`?username=${n}`);let r=await new A("GET",tt+`/existing-credentials${e}`).setHeaders([nt]).fetch();return r.status==="ok"?{status:"ok",data:r.body.credentials}:r.status==="unauthenticated"?{status:"unauthenticated"}:{status:"error"}}async function Yu(t,c){let n=await new A("POST",tt+"/challenges").setBody({identityId:t,...c}).setHeaders([nt]).fetch();return n.status==="ok"?{status:"ok",data:n.body.challenge}:n.status==="unauthenticated"?{status:"unauthenticated"}:{status:"error"}}async function xu(t,n,e){if(!(t.response instanceof AuthenticatorAssertionResponse))return{status:"error"};let r=await new A("POST",tt+"/tokens").setHeaders([nt]).setBody({credential:{id:t.id,authenticatorAttachment:t.authenticatorAttachment,rawId:Tn(new Uint8Array(t.rawId)),response:{authenticatorData:Tn(new Uint8Array(t.response.authenticatorData)),clientDataJSON:Tn(new Uint8Array(t.response.clientDataJSON)),signature:Tn(new Uint8Array(t.response.signature)),userHandle:t.response.userHandle?Tn(new Uint8Array(t.response.userHandle)):null}},typ:n,act:e}).fetch();if(r.status==="unauthenticated")return{status:"unauthenticated"};if(r.status!=="ok")return{status:"error"};let o=await zn();return o?{status:"ok",data:o.bearer}:{status:"error"}}function V(t,n,e,r,o){return St(n,((a,i)=>{let s=a[i];if(s===void 0)throw new TypeError(ha(i));return s})(t,n),e,r,o)}function St(t,n,e,r,o,a){let i=Gn(n,e,r);if(o&&n!==i)throw new RangeError(Lc(t,n,e,r,a));return i}function U(t){return t!==null&&/object|function/.test(typeof t)}function G(t,n=Map){let e=new n;return(r,...o)=>{if(e.has(r))return e.get(r);let a=t(r,...o);return e.set(r,a),a}}function En(t){return cn({name:t},1)}function cn(t,n){return gt(e=>({value:e,configurable:1,writable:!n}),t)}function vi(t){return gt(n=>({get:n,configurable:1}),t)}function Le(t){return{[Symbol.toStringTag]:{value:t,configurable:1}}}function Pn(t,n){let e={},r=t.length;for(let o of n)e[t[--r]]=o;return e}function gt(t,n,e){let r={};for(let o in n)r[o]=t(n[o],o,e);return r}function ae(t,n,e){let r={};for(let o=0;o<n.length;o++){let a=n[o];r[a]=t(a,o,e)}return r}function bi(t,n,e){let r={};for(let o=0;o<t.length;o++)r[n[o]]=e[t[o]];return r}function ot(t,n){let e=Object.create(null);for(let r of t)e[r]=n[r];return e}function si(t,n){for(let e of n)if(e in t)return 1;return 0}function Ii(t,n,e){for(let r of t)if(n[r]!==e[r])return 0;return 1}function Mi(t,n,e){let r={...e};for(let o=0;o<n;o++)r[t[o]]=0;return r}function I(t,...n){return(...e)=>t(...n,...e)}function ci(t){return t[0].toUpperCase()+t.substring(1)}function ie(t){return t.slice().sort()}function Fe(t,n){return String(n).padStart(t,"0")}function xt(t,n){return Math.sign(t-n)}function Gn(t,n,e){return Math.min(Math.max(t,n),e)}function Nt(t,n){return[Math.floor(t/n),Jn(t,n)]}function Jn(t,n){return(t%n+n)%n}function qt(t,n){return[$e(t,n),Xr(t,n)]}function $e(t,n){return Math.trunc(t/n)||0}function Xr(t,n){return t%n||0}function Pe(t){return Math.abs(t%1)===.5}function Ei(t,n,e){let r=0,o=0;for(let s=0;s<=n;s++){let u=t[e[s]],c=pt[s],l=C/c,[f,d]=qt(u,l);r+=d*c,o+=f}let[a,i]=qt(r,C);return[o+a,i]}function He(t,n,e){let r={};for(let o=n;o>=0;o--){let a=pt[o];r[e[o]]=$e(t,a),t=Xr(t,a)}return r}function Pi(t){if(t!==void 0)return q(t)}function Ni(t){if(t!==void 0)return yt(t)}function Qr(t){if(t!==void 0)return We(t)}function yt(t){return Oi(We(t))}function We(t){return no(Ol(t))}function Ci(t,n){if(n==null)throw new RangeError(ha(t));return n}function se(t){if(!U(t))throw new TypeError(ol);return t}function to(t,n,e=t){if(typeof n!==t)throw new TypeError(Vt(e,n));return n}function no(t,n="number"){if(!Number.isInteger(t))throw new RangeError(Xu(n,t));return t||0}function Oi(t,n="number"){if(t<=0)throw new RangeError(Qu(n,t));return t}function eo(t){if(typeof t=="symbol")throw new TypeError(rl);return String(t)}function Se(t,n){return U(t)?String(t):q(t,n)}function ro(t){if(typeof t=="string")return BigInt(t);if(typeof t!="bigint")throw new TypeError(el(t));return t}function Si(t,n="number"){if(typeof t=="bigint")throw new TypeError(nl(n));if(t=Number(t),!Number.isFinite(t))throw new RangeError(tl(n,t));return t}function L(t,n){return Math.trunc(Si(t,n))||0}function oo(t,n){return no(Si(t,n),n)}function ui(t,n){return Oi(L(t,n),n)}function ao(t,n){let[e,r]=qt(n,C),o=t+e,a=Math.sign(o);return a&&a===-Math.sign(r)&&(o-=a,r+=a*C),[o,r]}function bn(t,n,e=1){return ao(t[0]+n[0]*e,t[1]+n[1]*e)}function on(t,n){return ao(t[0],t[1]+n)}function ht(t,n){return bn(n,t,-1)}function et(t,n){return xt(t[0],n[0])||xt(t[1],n[1])}function ki(t,n,e){return et(t,n)===-1||et(t,e)===1}function io(t,n=1){let e=BigInt(C/n);return[Number(t/e),Number(t%e)*n]}function Xn(t,n=1){let e=C/n,[r,o]=qt(t,e);return[r,o*n]}function mt(t,n=1,e){let[r,o]=t,[a,i]=qt(o,n);return r*(C/n)+(a+(e?i/n:0))}function so(t,n,e=Nt){let[r,o]=t,[a,i]=e(o,n);return[r*(C/n)+a,i]}function co(t){return V(t,"isoYear",oe,re,1),t.isoYear===oe?V(t,"isoMonth",4,12,1):t.isoYear===re&&V(t,"isoMonth",1,9,1),t}function ut(t){return X({...t,...H,isoHour:12}),t}function X(t){let n=V(t,"isoYear",oe,re,1),e=n===oe?1:n===re?-1:0;return e&&wt(Y({...t,isoDay:t.isoDay+e,isoNanosecond:t.isoNanosecond-e})),t}function wt(t){if(!t||ki(t,Yl,Bl))throw new RangeError(Jt);return t}function Ut(t){return Ei(t,5,st)[1]}function ze(t){let[n,e]=Nt(t,C);return[He(e,5,st),n]}function li(t){return so(t,dt)}function $(t){return Nn(t.isoYear,t.isoMonth,t.isoDay,t.isoHour,t.isoMinute,t.isoSecond,t.isoMillisecond)}function Y(t){let n=$(t);if(n!==void 0){let[e,r]=qt(n,_);return[e,r*bt+(t.isoMicrosecond||0)*me+(t.isoNanosecond||0)]}}function uo(t,n){let[e,r]=ze(Ut(t)-n);return wt(Y({...t,isoDay:t.isoDay+r,...e}))}function Be(...t){return Nn(...t)/Xc}function Nn(...t){let[n,e]=Zi(...t),r=n.valueOf();if(!isNaN(r))return r-e*_}function Zi(t,n=1,e=1,r=0,o=0,a=0,i=0){let s=t===oe?1:t===re?-1:0,u=new Date;return u.setUTCHours(r,o,a,i),u.setUTCFullYear(t,n-1,e+s),[u,s]}function Cn(t,n){let[e,r]=on(t,n);r<0&&(r+=C,e-=1);let[o,a]=Nt(r,bt),[i,s]=Nt(a,me);return Ke(e*_+o,i,s)}function Ke(t,n=0,e=0){let r=Math.ceil(Math.max(0,Math.abs(t)-Na)/_)*Math.sign(t),o=new Date(t-r*_);return Pn(Ir,[o.getUTCFullYear(),o.getUTCMonth()+1,o.getUTCDate()+r,o.getUTCHours(),o.getUTCMinutes(),o.getUTCSeconds(),o.getUTCMilliseconds(),n,e])}function lo(t,n){if(n<-Na)throw new RangeError(Jt);let e=t.formatToParts(n),r={};for(let o of e)r[o.type]=o.value;return r}function fo(t){return[t.isoYear,t.isoMonth,t.isoDay]}function Ri(t,n){return[n,0]}function Fi(){return Yt}function Bi(t,n){switch(n){case 2:return ho(t)?29:28;case 4:case 6:case 9:case 11:return 30}return 31}function Yi(t){return ho(t)?366:365}function ho(t){return t%4==0&&(t%100!=0||t%400==0)}function xi(t){let[n,e]=Zi(t.isoYear,t.isoMonth,t.isoDay);return Jn(n.getUTCDay()-e,7)||7}function ji(t){return this.id===jn?(({isoYear:n})=>n<1?["gregory-inverse",1-n]:["gregory",n])(t):this.id===$t?ql(t):[]}function ju(t){let n=$(t);if(n<jl){let{isoYear:a}=t;return a<1?["japanese-inverse",1-a]:["japanese",a]}let e=lo(Za($t),n),{era:r,eraYear:o}=Xs(e,$t);return[r,o]}function Ve(t){return un(t),On(t,1),t}function un(t){return qi(t,1),t}function fi(t){return Ii(Ma,t,qi(t))}function qi(t,n){let{isoYear:e}=t,r=V(t,"isoMonth",1,Fi(),n);return{isoYear:e,isoMonth:r,isoDay:V(t,"isoDay",1,Bi(e,r),n)}}function On(t,n){return Pn(st,[V(t,"isoHour",0,23,n),V(t,"isoMinute",0,59,n),V(t,"isoSecond",0,59,n),V(t,"isoMillisecond",0,999,n),V(t,"isoMicrosecond",0,999,n),V(t,"isoNanosecond",0,999,n)])}function E(t){return t===void 0?0:mu(se(t))}function ce(t,n=0){t=Tt(t);let e=pu(t),r=Jl(t,n);return[mu(t),r,e]}function Sn(t,n,e,r=9,o=0,a=4){n=Tt(n);let i=hu(n,r,o),s=go(n),u=ye(n,a),c=ge(n,r,o,1);return i==null?i=Math.max(e,c):Hi(i,c),s=yo(s,c,1),t&&(u=(l=>l<4?(l+2)%4:l)(u)),[i,c,s,u]}function Je(t,n=6,e){let r=go(t=_e(t,Ae)),o=ye(t,7),a=ge(t,n);return a=Ci(Ae,a),r=yo(r,a,void 0,e),[a,r,o]}function mo(t){return Ca(Tt(t))}function Ui(t,n){return po(Tt(t),n)}function Ai(t){let n=_e(t,Hr),e=Ht(Hr,Kl,n,0);if(!e)throw new RangeError(Vt(Hr,e));return e}function po(t,n=4){let e=$i(t);return[ye(t,4),...Li(ge(t,n),e)]}function Li(t,n){return t!=null?[pt[t],t<4?9-3*t:-1]:[n===void 0?1:10**(9-n),n]}function go(t){let n=t[_n];return n===void 0?1:L(n,_n)}function yo(t,n,e,r){let o=r?C:pt[n+1];if(o){let a=pt[n];if(o%((t=St(_n,t,1,o/a-(r?0:1),1))*a))throw new RangeError(Vt(_n,t))}else t=St(_n,t,1,e?10**9:1,1);return t}function $i(t){let n=t[$r];if(n!==void 0){if(typeof n!="number"){if(eo(n)==="auto")return;throw new RangeError(Vt($r,n))}n=St($r,Math.floor(n),0,9,1)}return n}function Tt(t){return t===void 0?{}:se(t)}function _e(t,n){return typeof t=="string"?{[n]:t}:se(t)}function Ge(t){return{overflow:Ul[t]}}function wo(t,n,e=9,r=0,o){let a=n[t];if(a===void 0)return o?r:void 0;if(a=eo(a),a==="auto")return o?r:null;let i=zr[a];if(i===void 0&&(i=Zl[a]),i===void 0)throw new RangeError(Wc(t,a,zr));return St(t,i,r,e,1,ga),i}function Ht(t,n,e,r=0){let o=e[t];if(o===void 0)return r;let a=eo(o),i=n[a];if(i===void 0)throw new RangeError(Wc(t,a,n));return i}function Hi(t,n){if(n>t)throw new RangeError(Il)}function Dt(t){return{branding:Nr,epochNanoseconds:t}}function at(t,n,e){return{branding:Ft,calendar:e,timeZone:n,epochNanoseconds:t}}function it(t,n=t.calendar){return{branding:pn,calendar:n,...ot(Rl,t)}}function vt(t,n=t.calendar){return{branding:qn,calendar:n,...ot(Ea,t)}}function Qn(t,n=t.calendar){return{branding:Mr,calendar:n,...ot(Ea,t)}}function Ye(t,n=t.calendar){return{branding:Er,calendar:n,...ot(Ea,t)}}function ft(t){return{branding:Pr,...ot(uu,t)}}function F(t){return{branding:Cr,sign:Wt(t),...ot(va,t)}}function Xe(t){return so(t.epochNanoseconds,bt)[0]}function Wi(t){return((n,e=1)=>{let[r,o]=n,a=Math.floor(o/e),i=C/e;return BigInt(r)*BigInt(i)+BigInt(a)})(t.epochNanoseconds)}function zi(t){return t.epochNanoseconds}function Ki(t,n,e,r,o){let a=an(r),[i,s]=((y,w)=>{let D=w((y=_e(y,Jr))[fu]),b=Vl(y);return b=Ci(Jr,b),[b,D]})(o,t),u=Math.max(i,a);if(!s&&ne(u,s))return di(r,i);if(!s)throw new RangeError(Tr);if(!r.sign)return 0;let[c,l,f]=or(n,e,s),d=Uo(f),h=ar(f),m=Ao(f),p=h(l,c,r);In(s)||(X(c),X(p));let g=m(l,c,p,i);return ne(i,s)?di(g,i):((y,w,D,b,N,O,S)=>{let R=Wt(y),[k,en]=To(b,Ia(D,y),D,R,N,O,S),Bt=Do(w,k,en);return y[P[D]]+Bt*R})(g,d(p),i,l,c,d,h)}function di(t,n){return mt(x(t),pt[n],1)}function To(t,n,e,r,o,a,i){let s=P[e],u={...n,[s]:n[s]+r},c=i(t,o,n),l=i(t,o,u);return[a(c),a(l)]}function Do(t,n,e){let r=mt(ht(n,e));if(!r)throw new RangeError(xn);return mt(ht(n,t))/r}function Vi(t,n){let[e,r,o]=Je(n,5,1);return Dt(tr(t.epochNanoseconds,e,r,o,1))}function Ji(t,n,e){let{epochNanoseconds:r,timeZone:o,calendar:a}=n,[i,s,u]=Je(e);if(i===0&&s===1)return n;let c=t(o);if(i===6)r=((l,f,d,h)=>{let m=Q(d,f),[p,g]=l(m),y=d.epochNanoseconds,w=Lt(f,p),D=Lt(f,g);if(ki(y,w,D))throw new RangeError(xn);return os(Do(y,w,D),h)?D:w})(es,c,n,u);else{let l=c.R(r);r=Zn(c,ts(Cn(r,l),i,s,u),l,2,0,1)}return at(r,o,a)}function _i(t,n){return it(ts(t,...Je(n)),t.calendar)}function Gi(t,n){let[e,r,o]=Je(n,5);var a;return ft((a=o,vo(t,ue(e,r),a)[0]))}function Xi(t,n){let e=t(n.timeZone),r=Q(n,e),[o,a]=es(r),i=mt(ht(Lt(e,o),Lt(e,a)),br,1);if(i<=0)throw new RangeError(xn);return i}function Qi(t,n){let{timeZone:e,calendar:r}=n,o=((a,i,s)=>Lt(i,a(Q(s,i))))(rs,t(e),n);return at(o,e,r)}function ts(t,n,e,r){return ns(t,ue(n,e),r)}function ns(t,n,e){let[r,o]=vo(t,n,e);return X({...ln(t,o),...r})}function vo(t,n,e){return ze(At(Ut(t),n,e))}function xe(t){return At(t,vr,7)}function ue(t,n){return pt[t]*n}function es(t){let n=rs(t);return[n,ln(n,1)]}function rs(t){return Fl(6,t)}function qu(t,n,e){let r=Math.min(an(t),6);return Rn(nr(x(t,r),n,e),r)}function Qe(t,n,e,r,o,a,i,s,u,c){if(r===0&&o===1)return t;let l=ne(r,s)?In(s)&&r<6&&e>=6?Au:Uu:Lu,[f,d,h]=l(t,n,e,r,o,a,i,s,u,c);return h&&r!==7&&(f=((m,p,g,y,w,D,b,N)=>{let O=Wt(m);for(let S=y+1;S<=g;S++){if(S===7&&g!==7)continue;let R=Ia(S,m);R[P[S]]+=O;let k=mt(ht(b(N(w,D,R)),p));if(k&&Math.sign(k)!==O)break;m=R}return m})(f,d,e,Math.max(6,r),i,s,u,c)),f}function tr(t,n,e,r,o){if(n===6){let a=(i=>i[0]+i[1]/C)(t);return[At(a,e,r),0]}return nr(t,ue(n,e),r,o)}function nr(t,n,e,r){let[o,a]=t;r&&a<0&&(a+=C,o-=1);let[i,s]=Nt(At(a,n,e),C);return ao(o+i,s)}function At(t,n,e){return os(t/n,e)*n}function os(t,n){return Xl[n](t)}function Uu(t,n,e,r,o,a){let i=Wt(t),s=x(t),u=tr(s,r,o,a),c=ht(s,u),l=Math.sign(u[0]-s[0])===i,f=Rn(u,Math.min(e,6));return[{...t,...f},bn(n,c),l]}function Au(t,n,e,r,o,a,i,s,u,c){let l=Wt(t)||1,f=mt(x(t,5)),d=ue(r,o),h=At(f,d,a),[m,p]=To(i,{...t,...ba},6,l,s,u,c),g=h-mt(ht(m,p)),y=0;g&&Math.sign(g)!==l?n=on(m,h):(y+=l,h=At(g,d,a),n=on(p,h));let w=sr(h);return[{...t,...w,days:t.days+y},n,!!y]}function Lu(t,n,e,r,o,a,i,s,u,c){let l=Wt(t),f=P[r],d=Ia(r,t);r===7&&(t={...t,weeks:t.weeks+Math.trunc(t.days/7)});let h=$e(t[f],o)*o;d[f]=h;let[m,p]=To(i,d,r,o*l,s,u,c),g=h+Do(n,m,p)*l*o,y=At(g,o,a),w=Math.sign(y-g)===l;return d[f]=y,[d,w?p:m,w]}function bo(t,n,e,r){let[o,a,i,s]=(c=>{let l=po(c=Tt(c));return[c.timeZone,...l]})(r),u=o!==void 0;return((c,l,f,d,h,m)=>{f=nr(f,h,d,1);let p=l.R(f);return Oo(Cn(f,p),m)+(c?kn(xe(p)):"Z")})(u,n(u?t(o):gn),e.epochNanoseconds,a,i,s)}function Io(t,n,e){let[r,o,a,i,s,u]=(c=>{c=Tt(c);let l=Ca(c),f=$i(c),d=Gl(c),h=ye(c,4),m=ge(c,4);return[l,_l(c),d,h,...Li(m,f)]})(e);return((c,l,f,d,h,m,p,g,y,w)=>{d=nr(d,y,g,1);let D=c(f).R(d);return Oo(Cn(d,D),w)+kn(xe(D),p)+((b,N)=>N!==1?"["+(N===2?"!":"")+b+"]":"")(f,m)+So(l,h)})(t,n.calendar,n.timeZone,n.epochNanoseconds,r,o,a,i,s,u)}function Mo(t,n){let[e,r,o,a]=(c=>(c=Tt(c),[Ca(c),...po(c)]))(n);return i=t.calendar,s=e,u=a,Oo(ns(t,o,r),u)+So(i,s);var i,s,u}function Eo(t,n){return e=t.calendar,r=t,o=mo(n),je(r)+So(e,o);var e,r,o}function Po(t,n){return as(t.calendar,is,t,mo(n))}function No(t,n){return as(t.calendar,$u,t,mo(n))}function Co(t,n){let[e,r,o]=Ui(n);return a=o,ss(vo(t,r,e)[0],a);var a}function er(t,n){let[e,r,o]=Ui(n,3);return r>1&&fn(t={...t,...qu(t,r,e)}),((a,i)=>{let{sign:s}=a,u=s===-1?W(a):a,{hours:c,minutes:l}=u,[f,d]=so(x(u,3),dt,qt);ms(f);let h=ko(d,i),m=i>=0||!s||h;return(s<0?"-":"")+"P"+hi({Y:rn(u.years),M:rn(u.months),W:rn(u.weeks),D:rn(u.days)})+(c||l||f||m?"T"+hi({H:rn(c),M:rn(l),S:rn(f,m)+h}):"")})(t,o)}function as(t,n,e,r){let o=r>1||r===0&&t!==M;return r===1?t===M?n(e):je(e):o?je(e)+cs(t,r===2):n(e)}function hi(t){let n=[];for(let e in t){let r=t[e];r&&n.push(r,e)}return n.join("")}function Oo(t,n){return je(t)+"T"+ss(t,n)}function je(t){return is(t)+"-"+lt(t.isoDay)}function is(t){let{isoYear:n}=t;return(n<0||n>9999?us(n)+Fe(6,Math.abs(n)):Fe(4,n))+"-"+lt(t.isoMonth)}function $u(t){return lt(t.isoMonth)+"-"+lt(t.isoDay)}function ss(t,n){let e=[lt(t.isoHour),lt(t.isoMinute)];return n!==-1&&e.push(lt(t.isoSecond)+((r,o,a,i)=>ko(r*bt+o*me+a,i))(t.isoMillisecond,t.isoMicrosecond,t.isoNanosecond,n)),e.join(":")}function kn(t,n=0){if(n===1)return"";let[e,r]=Nt(Math.abs(t),br),[o,a]=Nt(r,vr),[i,s]=Nt(a,dt);return us(t)+lt(e)+":"+lt(o)+(i||s?":"+lt(i)+ko(s):"")}function So(t,n){return n!==1&&(n>1||n===0&&t!==M)?cs(t,n===2):""}function cs(t,n){return"["+(n?"!":"")+"u-ca="+t+"]"}function ko(t,n){let e=Fe(9,t);return e=n===void 0?e.replace(nf,""):e.slice(0,n),e?"."+e:""}function us(t){return t<0?"-":"+"}function rn(t,n){return t||n?t.toLocaleString("fullwide",{useGrouping:0}):""}function Hu(t,n){let{epochNanoseconds:e}=t,r=(n.R?n:n(t.timeZone)).R(e),o=Cn(e,r);return{calendar:t.calendar,...o,offsetNanoseconds:r}}function Zn(t,n,e,r=0,o=0,a,i){if(e!==void 0&&r===1&&(r===1||i))return uo(n,e);let s=t.I(n);if(e!==void 0&&r!==3){let u=((c,l,f,d)=>{let h=Y(l);d&&(f=xe(f));for(let m of c){let p=mt(ht(m,h));if(d&&(p=xe(p)),p===f)return m}})(s,n,e,a);if(u!==void 0)return u;if(r===0)throw new RangeError(yl)}return i?Y(n):le(t,n,o,s)}function le(t,n,e=0,r=t.I(n)){if(r.length===1)return r[0];if(e===1)throw new RangeError(wl);if(r.length)return r[e===3?1:0];let o=Y(n),a=((s,u)=>{let c=s.R(on(u,-C));return(l=>{if(l>C)throw new RangeError(gl);return l})(s.R(on(u,C))-c)})(t,o),i=a*(e===2?-1:1);return(r=t.I(Cn(o,i)))[e===2?0:r.length-1]}function Lt(t,n){let e=t.I(n);if(e.length)return e[0];let r=on(Y(n),-C);return t.O(r,1)}function Zo(t,n,e){return Dt(wt(bn(n.epochNanoseconds,(r=>{if(ps(r))throw new RangeError(vl);return x(r,5)})(t?W(e):e))))}function Ro(t,n,e,r,o,a=Object.create(null)){let i=n(r.timeZone),s=t(r.calendar);return{...r,...jo(i,s,r,e?W(o):o,a)}}function Fo(t,n,e,r,o=Object.create(null)){let{calendar:a}=e;return it(qo(t(a),e,n?W(r):r,o),a)}function Bo(t,n,e,r,o){let{calendar:a}=e;return vt(rr(t(a),e,n?W(r):r,o),a)}function Yo(t,n,e,r,o){let a=e.calendar,i=t(a),s=ut(te(i,e));n&&(r=ir(r)),r.sign<0&&(s=i.P(s,{...j,months:1}),s=ln(s,-1));let u=i.P(s,r,o);return Qn(te(i,u),a)}function xo(t,n,e){return ft(ls(n,t?W(e):e)[0])}function jo(t,n,e,r,o){let a=x(r,5),i=e.epochNanoseconds;if(ps(r)){let s=Q(e,t);i=bn(le(t,{...rr(n,s,{...r,...ba},o),...ot(st,s)}),a)}else i=bn(i,a),E(o);return{epochNanoseconds:wt(i)}}function qo(t,n,e,r){let[o,a]=ls(n,e);return X({...rr(t,n,{...e,...ba,days:e.days+a},r),...o})}function rr(t,n,e,r){if(e.years||e.months||e.weeks)return t.P(n,e,r);E(r);let o=e.days+x(e,5)[0];return o?ut(ln(n,o)):n}function te(t,n,e=1){return ln(n,e-t.day(n))}function ls(t,n){let[e,r]=x(n,5),[o,a]=ze(Ut(t)+r);return[o,e+a]}function ln(t,n){return n?{...t,...Ke($(t)+n*_)}:t}function or(t,n,e){let r=t(e.calendar);return In(e)?[e,r,n(e.timeZone)]:[{...e,...H},r]}function Uo(t){return t?zi:Y}function ar(t){return t?I(jo,t):qo}function Ao(t){return t?I(zu,t):Ku}function In(t){return t&&t.epochNanoseconds}function ne(t,n){return t<=6-(In(n)?1:0)}function Lo(t,n,e,r,o,a,i){let s=t(Tt(i).relativeTo),u=Math.max(an(o),an(a));if(ne(u,s))return F(fn(((p,g,y,w)=>{let D=bn(x(p),x(g),w?-1:1);if(!Number.isFinite(D[0]))throw new RangeError(Jt);return{...j,...Rn(D,y)}})(o,a,u,r)));if(!s)throw new RangeError(Tr);r&&(a=W(a));let[c,l,f]=or(n,e,s),d=ar(f),h=Ao(f),m=d(l,c,o);return F(h(l,c,d(l,m,a),u))}function fs(t,n,e,r,o){let a=an(r),[i,s,u,c,l]=((O,S,R)=>{O=_e(O,Ae);let k=hu(O),en=R(O[fu]),Bt=go(O),ku=ye(O,7),Pt=ge(O);if(k===void 0&&Pt===void 0)throw new RangeError(bl);if(Pt==null&&(Pt=0),k==null&&(k=Math.max(Pt,S)),Hi(k,Pt),Bt=yo(Bt,Pt,1),Bt>1&&Pt>5&&k!==Pt)throw new RangeError("For calendar units with roundingIncrement > 1, use largestUnit = smallestUnit");return[k,Pt,Bt,ku,en]})(o,a,t),f=Math.max(a,i);if(!l&&f<=6)return F(fn(((O,S,R,k,en)=>{let Bt=tr(x(O),R,k,en);return{...j,...Rn(Bt,S)}})(r,i,s,u,c)));if(!In(l)&&!r.sign)return r;if(!l)throw new RangeError(Tr);let[d,h,m]=or(n,e,l),p=Uo(m),g=ar(m),y=Ao(m),w=g(h,d,r);In(l)||(X(d),X(w));let D=y(h,d,w,i),b=r.sign,N=Wt(D);if(b&&N&&b!==N)throw new RangeError(xn);return D=Qe(D,p(w),i,s,u,c,h,d,p,g),F(D)}function ds(t){return t.sign===-1?ir(t):t}function ir(t){return F(W(t))}function W(t){let n={};for(let e of P)n[e]=-1*t[e]||0;return n}function hs(t){return!t.sign}function Wt(t,n=P){let e=0;for(let r of n){let o=Math.sign(t[r]);if(o){if(e&&e!==o)throw new RangeError(Dl);e=o}}return e}function fn(t){for(let n of kl)St(n,t[n],-wi,wi,1);return ms(mt(x(t),dt)),t}function ms(t){if(!Number.isSafeInteger(t))throw new RangeError(Tl)}function x(t,n=6){return Ei(t,n,P)}function Rn(t,n=6){let[e,r]=t,o=He(r,n,P);if(o[P[n]]+=e*(C/pt[n]),!Number.isFinite(o[P[n]]))throw new RangeError(Jt);return o}function sr(t,n=5){return He(t,n,P)}function ps(t){return!!Wt(t,cu)}function an(t){let n=9;for(;n>0&&!t[P[n]];n--);return n}function Wu(t,n){return[t,n]}function mi(t){let n=Math.floor(t/Ze)*Ze;return[n,n+Ze]}function gs(t){let n=zt(t=Se(t));if(!n)throw new RangeError(J(t));let e;if(n.j)e=0;else{if(!n.offset)throw new RangeError(J(t));e=dn(n.offset)}return n.timeZone&&zo(n.timeZone,1),Dt(uo(Ve(n),e))}function ys(t){let n=zt(q(t));if(!n)throw new RangeError(J(t));if(n.timeZone)return Ps(n,n.offset?dn(n.offset):void 0);if(n.j)throw new RangeError(J(t));return Cs(n)}function ws(t,n){let e=zt(q(t));if(!e||!e.timeZone)throw new RangeError(J(t));let{offset:r}=e,o=r?dn(r):void 0,[,a,i]=ce(n);return Ps(e,o,a,i)}function dn(t){let n=zo(t);if(n===void 0)throw new RangeError(J(t));return n}function Ts(t){let n=zt(q(t));if(!n||n.j)throw new RangeError(J(t));return it(Ns(n))}function cr(t,n,e){let r=zt(q(t));if(!r||r.j)throw new RangeError(J(t));return n?r.calendar===M&&(r=r.isoYear===-271821&&r.isoMonth===4?{...r,isoDay:20,...H}:{...r,isoDay:1,...H}):e&&r.calendar===M&&(r={...r,isoYear:Ot}),vt(r.C?Ns(r):Cs(r))}function Ds(t,n){let e=Ho(q(n));if(e)return $o(e),Qn(co(un(e)));let r=cr(n,1);return Qn(te(t(r.calendar),r))}function $o(t){if(t.calendar!==M)throw new RangeError(Ct(t.calendar))}function vs(t,n){let e=Wo(q(n));if(e)return $o(e),Ye(un(e));let r=cr(n,0,1),{calendar:o}=r,a=t(o),[i,s,u]=a.v(r),[c,l]=a.q(i,s),[f,d]=a.G(c,l,u);return Ye(ut(a.V(f,d,u)),o)}function bs(t){let n,e=(r=>{let o=sf.exec(r);return o?(ur(o[10]),ks(o)):void 0})(q(t));if(!e){if(e=zt(t),!e)throw new RangeError(J(t));if(!e.C)throw new RangeError(J(t));if(e.j)throw new RangeError(Ct("Z"));$o(e)}if((n=Ho(t))&&fi(n))throw new RangeError(J(t));if((n=Wo(t))&&fi(n))throw new RangeError(J(t));return ft(On(e,1))}function Is(t){let n=(e=>{let r=lf.exec(e);return r?(o=>{function a(l,f,d){let h=0,m=0;if(d&&([h,u]=Nt(u,pt[d])),l!==void 0){if(s)throw new RangeError(Ct(l));m=(p=>{let g=parseInt(p);if(!Number.isFinite(g))throw new RangeError(Ct(p));return g})(l),i=1,f&&(u=Ko(f)*(pt[d]/dt),s=1)}return h+m}let i=0,s=0,u=0,c={...Pn(P,[a(o[2]),a(o[3]),a(o[4]),a(o[5]),a(o[6],o[7],5),a(o[8],o[9],4),a(o[10],o[11],3)]),...He(u,2,P)};if(!i)throw new RangeError($c(P));return Vo(o[1])<0&&(c=W(c)),c})(r):void 0})(q(t));if(!n)throw new RangeError(J(t));return F(fn(n))}function Ms(t){let n=zt(t)||Ho(t)||Wo(t);return n?n.calendar:t}function Es(t){let n=zt(t);return n&&(n.timeZone||n.j&&gn||n.offset)||t}function Ps(t,n,e=0,r=0){let o=lr(t.timeZone),a=v(o),i;return Ve(t),i=t.C?Zn(a,t,n,e,r,!a.$,t.j):Lt(a,t),at(i,o,de(t.calendar))}function Ns(t){return Os(X(Ve(t)))}function Cs(t){return Os(ut(un(t)))}function Os(t){return{...t,calendar:de(t.calendar)}}function zt(t){let n=af.exec(t);return n?(e=>{let r=e[10],o=(r||"").toUpperCase()==="Z";return{isoYear:Ss(e),isoMonth:parseInt(e[4]),isoDay:parseInt(e[5]),...ks(e.slice(5)),...ur(e[16]),C:!!e[6],j:o,offset:o?void 0:r}})(n):void 0}function Ho(t){let n=rf.exec(t);return n?(e=>({isoYear:Ss(e),isoMonth:parseInt(e[4]),isoDay:1,...ur(e[5])}))(n):void 0}function Wo(t){let n=of.exec(t);return n?(e=>({isoYear:Ot,isoMonth:parseInt(e[1]),isoDay:parseInt(e[2]),...ur(e[3])}))(n):void 0}function zo(t,n){let e=cf.exec(t);return e?((r,o)=>{let a=r[4]||r[5];if(o&&a)throw new RangeError(Ct(a));return(i=>{if(Math.abs(i)>=C)throw new RangeError(pl);return i})((vn(r[2])*br+vn(r[3])*vr+vn(r[4])*dt+Ko(r[5]||""))*Vo(r[1]))})(e,n):void 0}function Ss(t){let n=Vo(t[1]),e=parseInt(t[2]||t[3]);if(n<0&&!e)throw new RangeError(Ct(-0));return n*e}function ks(t){let n=vn(t[3]);return{...ze(Ko(t[4]||""))[0],isoHour:vn(t[1]),isoMinute:vn(t[2]),isoSecond:n===60?59:n}}function ur(t){let n,e,r=[];if(t.replace(uf,(o,a,i)=>{let s=!!a,[u,c]=i.split("=").reverse();if(c){if(c==="u-ca")r.push(u),n||(n=s);else if(s||/[A-Z]/.test(c))throw new RangeError(Ct(o))}else{if(e)throw new RangeError(Ct(o));e=u}return""}),r.length>1&&n)throw new RangeError(Ct(t));return{timeZone:e,calendar:r[0]||M}}function Ko(t){return parseInt(t.padEnd(9,"0"))}function Fn(t){return new RegExp(`^${t}$`,"i")}function Vo(t){return t&&t!=="+"?-1:1}function vn(t){return t===void 0?0:parseInt(t)}function Zs(t){return lr(q(t))}function lr(t){let n=Jo(t);return typeof n=="number"?kn(n):n?(e=>{if(hf.test(e))throw new RangeError(pa(e));if(df.test(e))throw new RangeError(ml);return e.toLowerCase().split("/").map((r,o)=>(r.length<=3||/\d/.test(r))&&!/etc|yap/.test(r)?r.toUpperCase():r.replace(/baja|dumont|[a-z]+/g,(a,i)=>a.length<=2&&!o||a==="in"||a==="chat"?a.toUpperCase():a.length>2||!i?ci(a).replace(/island|noronha|murdo|rivadavia|urville/,ci):a)).join("/")})(t):gn}function pi(t){let n=Jo(t);return typeof n=="number"?n:n?n.resolvedOptions().timeZone:gn}function Jo(t){let n=zo(t=t.toUpperCase(),1);return n!==void 0?n:t!==gn?ff(t):void 0}function _o(t,n){return et(t.epochNanoseconds,n.epochNanoseconds)}function Go(t,n){return et(t.epochNanoseconds,n.epochNanoseconds)}function Rs(t,n,e,r,o,a){let i=t(Tt(a).relativeTo),s=Math.max(an(r),an(o));if(Ii(P,r,o))return 0;if(ne(s,i))return et(x(r),x(o));if(!i)throw new RangeError(Tr);let[u,c,l]=or(n,e,i),f=Uo(l),d=ar(l);return et(f(d(c,u,r)),f(d(c,u,o)))}function Xo(t,n){return hn(t,n)||fr(t,n)}function hn(t,n){return xt($(t),$(n))}function fr(t,n){return xt(Ut(t),Ut(n))}function Fs(t,n){return!_o(t,n)}function Bs(t,n){return!Go(t,n)&&!!As(t.timeZone,n.timeZone)&&t.calendar===n.calendar}function Ys(t,n){return!Xo(t,n)&&t.calendar===n.calendar}function xs(t,n){return!hn(t,n)&&t.calendar===n.calendar}function js(t,n){return!hn(t,n)&&t.calendar===n.calendar}function qs(t,n){return!hn(t,n)&&t.calendar===n.calendar}function Us(t,n){return!fr(t,n)}function As(t,n){if(t===n)return 1;try{return pi(t)===pi(n)}catch{}}function Qo(t,n,e,r){let o=Sn(t,r,3,5),a=dr(n.epochNanoseconds,e.epochNanoseconds,...o);return F(t?W(a):a)}function ta(t,n,e,r,o,a){let i=mr(r.calendar,o.calendar),[s,u,c,l]=Sn(e,a,5),f=r.epochNanoseconds,d=o.epochNanoseconds,h=et(d,f),m;if(h)if(s<6)m=dr(f,d,s,u,c,l);else{let p=n(((y,w)=>{if(!As(y,w))throw new RangeError(Jc);return y})(r.timeZone,o.timeZone)),g=t(i);m=$s(g,p,r,o,h,s,a),m=Qe(m,d,s,u,c,l,g,r,zi,I(jo,p))}else m=j;return F(e?W(m):m)}function na(t,n,e,r,o){let a=mr(e.calendar,r.calendar),[i,s,u,c]=Sn(n,o,6),l=Y(e),f=Y(r),d=et(f,l),h;if(d)if(i<=6)h=dr(l,f,i,s,u,c);else{let m=t(a);h=Hs(m,e,r,d,i,o),h=Qe(h,f,i,s,u,c,m,e,Y,qo)}else h=j;return F(n?W(h):h)}function ea(t,n,e,r,o){let a=mr(e.calendar,r.calendar);return Ls(n,()=>t(a),e,r,...Sn(n,o,6,9,6))}function ra(t,n,e,r,o){let a=mr(e.calendar,r.calendar),i=Sn(n,o,9,9,8),s=t(a),u=te(s,e),c=te(s,r);return u.isoYear===c.isoYear&&u.isoMonth===c.isoMonth&&u.isoDay===c.isoDay?F(j):Ls(n,()=>s,ut(u),ut(c),...i,8)}function Ls(t,n,e,r,o,a,i,s,u=6){let c=Y(e),l=Y(r);if(c===void 0||l===void 0)throw new RangeError(Jt);let f;if(et(l,c))if(o===6)f=dr(c,l,o,a,i,s);else{let d=n();f=d.N(e,r,o),a===u&&i===1||(f=Qe(f,l,o,a,i,s,d,e,Y,rr))}else f=j;return F(t?W(f):f)}function oa(t,n,e,r){let[o,a,i,s]=Sn(t,r,5,5),u=At(aa(n,e),ue(a,i),s),c={...j,...sr(u,o)};return F(t?W(c):c)}function zu(t,n,e,r,o,a){let i=et(r.epochNanoseconds,e.epochNanoseconds);return i?o<6?Ws(e.epochNanoseconds,r.epochNanoseconds,o):$s(n,t,e,r,i,o,a):j}function Ku(t,n,e,r,o){let a=Y(n),i=Y(e),s=et(i,a);return s?r<=6?Ws(a,i,r):Hs(t,n,e,s,r,o):j}function $s(t,n,e,r,o,a,i){let[s,u,c]=((d,h,m,p)=>{function g(){return S={...ln(D,N++*-p),...w},R=le(d,S),et(b,R)===-p}let y=Q(h,d),w=ot(st,y),D=Q(m,d),b=m.epochNanoseconds,N=0,O=aa(y,D),S,R;if(Math.sign(O)===-p&&N++,g()&&(p===-1||g()))throw new RangeError(xn);let k=mt(ht(R,b));return[y,S,k]})(n,e,r,o);var l,f;return{...a===6?(l=s,f=u,{...j,days:zs(l,f)}):t.N(s,u,a,i),...sr(c)}}function Hs(t,n,e,r,o,a){let[i,s,u]=((c,l,f)=>{let d=l,h=aa(c,l);return Math.sign(h)===-f&&(d=ln(l,-f),h+=C*f),[c,d,h]})(n,e,r);return{...t.N(i,s,o,a),...sr(u)}}function dr(t,n,e,r,o,a){return{...j,...Rn(tr(ht(t,n),r,o,a),e)}}function Ws(t,n,e){return{...j,...Rn(ht(t,n),e)}}function zs(t,n){return hr($(t),$(n))}function hr(t,n){return Math.trunc((n-t)/_)}function aa(t,n){return Ut(n)-Ut(t)}function mr(t,n){if(t!==n)throw new RangeError(Vc);return t}function Ks(t){return this.m(t)[0]}function Vs(t){return this.m(t)[1]}function ia(t){let[n]=this.v(t);return hr(this.p(n),$(t))+1}function sa(t){let n=mf.exec(t);if(!n)throw new RangeError(dl(t));return[parseInt(n[1]),!!n[2]]}function fe(t,n){return"M"+lt(t)+(n?"L":"")}function qe(t,n,e){return t+(n||e&&t>=e?1:0)}function ca(t,n){return t-(n&&t>=n?1:0)}function Js(t,n){return(n+t)*(Math.sign(n)||1)||0}function Wr(t){return au[Gs(t)]}function _s(t){return Cl[Gs(t)]}function Gs(t){return sn(t.id||M)}function Vu(t){function n(o){return((a,i)=>({...Xs(a,i),o:a.month,day:parseInt(a.day)}))(lo(e,o),r)}let e=Za(t),r=sn(t);return{id:t,h:Ju(n),l:_u(n)}}function Ju(t){return G(n=>{let e=$(n);return t(e)},WeakMap)}function _u(t){let n=t(0).year-xl;return G(e=>{let r,o=Nn(e-n),a=0,i=[],s=[];do o+=400*_;while((r=t(o)).year<=e);do if(o+=(1-r.day)*_,r.year===e&&(i.push(o),s.push(r.o)),o-=_,++a>100||o<-Na)throw new RangeError(xn);while((r=t(o)).year>=e);return{i:i.reverse(),u:Gc(s.reverse())}})}function Xs(t,n){let e,r,o=Qs(t);if(t.era){let a=au[n],i=iu[n]||{};a!==void 0&&(e=n==="islamic"?"ah":t.era.normalize("NFD").toLowerCase().replace(/[^a-z0-9]/g,""),e==="bc"||e==="b"?e="bce":e==="ad"||e==="a"?e="ce":e==="beforeroc"&&(e="broc"),e=i[e]||e,r=o,o=Js(r,a[e]||0))}return{era:e,eraYear:r,year:o}}function Qs(t){return parseInt(t.relatedYear||t.year)}function Ue(t){let{year:n,o:e,day:r}=this.h(t),{u:o}=this.l(n);return[n,o[e]+1,r]}function ee(t,n=1,e=1){return this.l(t).i[n-1]+(e-1)*_}function tc(t,n){let e=ke.call(this,t);return[ca(n,e),e===n]}function ke(t){let n=yi(this,t),e=yi(this,t-1),r=n.length;if(r>e.length){let o=_s(this);if(o<0)return-o;for(let a=0;a<r;a++)if(n[a]!==e[a])return a+1}}function Ne(t){return hr(ee.call(this,t),ee.call(this,t+1))}function gi(t,n){let{i:e}=this.l(t),r=n+1,o=e;return r>e.length&&(r=1,o=this.l(t+1).i),hr(e[n-1],o[r-1])}function Ce(t){return this.l(t).i.length}function nc(t){let n=this.h(t);return[n.era,n.eraYear]}function yi(t,n){return Object.keys(t.l(n).u)}function Bn(t){return de(q(t))}function de(t){if((t=t.toLowerCase())!==M&&t!==jn){let n=Za(t).resolvedOptions().calendar;if(sn(t)!==sn(n))throw new RangeError(ma(t));return n}return t}function sn(t){return t==="islamicc"&&(t="islamic"),t.split("-")[0]}function ec(t,n){return e=>e===M?t:e===jn||e===$t?Object.assign(Object.create(t),{id:e}):Object.assign(Object.create(n),pf(e))}function rc(t,n,e,r){let o=Kt(e,r,Zt,[],nu);if(o.timeZone!==void 0){let a=e.F(o),i=he(o),s=t(o.timeZone);return{epochNanoseconds:Zn(n(s),{...a,...i},o.offset!==void 0?dn(o.offset):void 0),timeZone:s}}return{...e.F(o),...H}}function oc(t,n,e,r,o,a){let i=Kt(e,o,Zt,Qc,nu),s=t(i.timeZone),[u,c,l]=ce(a),f=e.F(i,Ge(u)),d=he(i,u);return at(Zn(n(s),{...f,...d},i.offset!==void 0?dn(i.offset):void 0,c,l),s,r)}function ac(t,n,e){let r=Kt(t,n,Zt,[],It),o=E(e);return it(X({...t.F(r,Ge(o)),...he(r,o)}))}function ic(t,n,e,r=[]){let o=Kt(t,n,Zt,r);return t.F(o,e)}function sc(t,n,e,r){let o=Kt(t,n,Da,r);return t.K(o,e)}function cc(t,n,e,r){let o=Kt(t,e,Zt,pe);return n&&o.month!==void 0&&o.monthCode===void 0&&o.year===void 0&&(o.year=Ot),t._(o,r)}function uc(t,n){return ft(he(rt(t,Kr,[],1),E(n)))}function lc(t){let n=rt(t,va);return F(fn({...j,...n}))}function Kt(t,n,e,r=[],o=[]){return rt(n,[...t.fields(e),...o].sort(),r)}function rt(t,n,e,r=!e){let o={},a,i=0;for(let s of n){if(s===a)throw new RangeError(il(s));if(s==="constructor"||s==="__proto__")throw new RangeError(al(s));let u=t[s];if(u!==void 0)i=1,Ti[s]&&(u=Ti[s](u,s)),o[s]=u;else if(e){if(e.includes(s))throw new TypeError(ha(s));o[s]=ou[s]}a=s}if(r&&!i)throw new TypeError($c(n));return o}function he(t,n){return On(Ra({...ou,...t}),n)}function fc(t,n,e,r,o){let{calendar:a,timeZone:i}=e,s=t(a),u=n(i),c=[...s.fields(Zt),...tu].sort(),l=(y=>{let w=Q(y,v),D=kn(w.offsetNanoseconds),b=Sr(y.calendar),[N,O,S]=b.v(w),[R,k]=b.q(N,O),en=fe(R,k);return{...If(w),year:N,monthCode:en,day:S,offset:D}})(e),f=rt(r,c),d=s.k(l,f),h={...l,...f},[m,p,g]=ce(o,2);return at(Zn(u,{...s.F(d,Ge(m)),...On(Ra(h),m)},dn(h.offset),p,g),i,a)}function dc(t,n,e,r){let o=t(n.calendar),a=[...o.fields(Zt),...It].sort(),i={...Tc(s=n),hour:s.isoHour,minute:s.isoMinute,second:s.isoSecond,millisecond:s.isoMillisecond,microsecond:s.isoMicrosecond,nanosecond:s.isoNanosecond};var s;let u=rt(e,a),c=E(r),l=o.k(i,u),f={...i,...u};return it(X({...o.F(l,Ge(c)),...On(Ra(f),c)}))}function hc(t,n,e,r){let o=t(n.calendar),a=o.fields(Zt).sort(),i=Tc(n),s=rt(e,a),u=o.k(i,s);return o.F(u,r)}function mc(t,n,e,r){let o=t(n.calendar),a=o.fields(Da).sort(),i=(c=>{let l=Sr(c.calendar),[f,d]=l.v(c),[h,m]=l.q(f,d);return{year:f,monthCode:fe(h,m)}})(n),s=rt(e,a),u=o.k(i,s);return o.K(u,r)}function pc(t,n,e,r){let o=t(n.calendar),a=o.fields(Zt).sort(),i=(c=>{let l=Sr(c.calendar),[f,d,h]=l.v(c),[m,p]=l.q(f,d);return{monthCode:fe(m,p),day:h}})(n),s=rt(e,a),u=o.k(i,s);return o._(u,r)}function gc(t,n,e){return ft(((r,o,a)=>he({...ot(Kr,r),...rt(o,Kr)},E(a)))(t,n,e))}function yc(t,n){return F((e=t,r=n,fn({...e,...rt(r,va)})));var e,r}function wc(t,n,e,r,o){n=ot(e=t.fields(e),n),r=rt(r,o=t.fields(o),[]);let a=t.k(n,r);return a=rt(a,[...e,...o].sort(),[]),t.F(a)}function Ur(t,n){let e=Wr(t),r=iu[t.id||""]||{},{era:o,eraYear:a,year:i}=n;if(o!==void 0||a!==void 0){if(o===void 0||a===void 0)throw new TypeError(cl);if(!e)throw new RangeError(sl);let s=e[r[o]||o];if(s===void 0)throw new RangeError(ll(o));let u=Js(a,s);if(i!==void 0&&i!==u)throw new RangeError(ul);i=u}else if(i===void 0)throw new TypeError(fl(e));return i}function Oe(t,n,e,r){let{month:o,monthCode:a}=n;if(a!==void 0){let i=((s,u,c,l)=>{let f=s.L(c),[d,h]=sa(u),m=qe(d,h,f);if(h){let p=_s(s);if(p===void 0)throw new RangeError(Vn);if(p>0){if(m>p)throw new RangeError(Vn);if(f===void 0){if(l===1)throw new RangeError(Vn);m--}}else{if(m!==-p)throw new RangeError(Vn);if(f===void 0&&l===1)throw new RangeError(Vn)}}return m})(t,a,e,r);if(o!==void 0&&o!==i)throw new RangeError(hl);o=i,r=1}else if(o===void 0)throw new TypeError(Kc);return St("month",o,1,t.B(e),r)}function Ar(t,n,e,r,o){return V(n,"day",1,t.U(r,e),o)}function Lr(t,n,e,r){let o=0,a=[];for(let i of e)n[i]!==void 0?o=1:a.push(i);if(Object.assign(t,n),o)for(let i of r||a)delete t[i]}function Tc(t){let n=Sr(t.calendar),[e,r,o]=n.v(t),[a,i]=n.q(e,r);return{year:e,monthCode:fe(a,i),day:o}}function Dc(t){return Dt(wt(io(ro(t))))}function vc(t,n,e,r,o=M){return at(wt(io(ro(e))),n(r),t(o))}function bc(t,n,e,r,o=0,a=0,i=0,s=0,u=0,c=0,l=M){return it(X(Ve(gt(L,Pn(Ir,[n,e,r,o,a,i,s,u,c])))),t(l))}function Ic(t,n,e,r,o=M){return vt(ut(un(gt(L,{isoYear:n,isoMonth:e,isoDay:r}))),t(o))}function Mc(t,n,e,r=M,o=1){let a=L(n),i=L(e),s=t(r);return Qn(co(un({isoYear:a,isoMonth:i,isoDay:L(o)})),s)}function Ec(t,n,e,r=M,o=Ot){let a=L(n),i=L(e),s=t(r);return Ye(ut(un({isoYear:L(o),isoMonth:a,isoDay:i})),s)}function Pc(t=0,n=0,e=0,r=0,o=0,a=0){return ft(On(gt(L,Pn(st,[t,n,e,r,o,a])),1))}function Nc(t=0,n=0,e=0,r=0,o=0,a=0,i=0,s=0,u=0,c=0){return F(fn(gt(oo,Pn(P,[t,n,e,r,o,a,i,s,u,c]))))}function Cc(t,n,e=M){return at(t.epochNanoseconds,n,e)}function Oc(t){return Dt(t.epochNanoseconds)}function ua(t,n){return it(Q(n,t))}function la(t,n){return vt(Q(n,t))}function fa(t,n){return ft(Q(n,t))}function Sc(t,n,e,r){let o=((a,i,s,u)=>{let c=(l=>pu(Tt(l)))(u);return le(a(i),s,c)})(t,e,n,r);return at(wt(o),e,n.calendar)}function kc(t,n,e,r,o){let a=t(o.timeZone),i=o.plainTime,s=i!==void 0?n(i):void 0,u=e(a),c;return c=s?le(u,{...r,...s}):Lt(u,{...r,...H}),at(c,a,r.calendar)}function Zc(t,n=H){return it(X({...t,...n}))}function Rc(t,n,e){return((r,o)=>{let a=Kt(r,o,eu);return r.K(a,void 0)})(t(n.calendar),e)}function Fc(t,n,e){return((r,o)=>{let a=Kt(r,o,ru);return r._(a)})(t(n.calendar),e)}function Bc(t,n,e,r){return((o,a,i)=>wc(o,a,eu,se(i),pe))(t(n.calendar),e,r)}function Yc(t,n,e,r){return((o,a,i)=>wc(o,a,ru,se(i),ya))(t(n.calendar),e,r)}function xc(t){return Dt(wt(Xn(oo(t),bt)))}function jc(t){return Dt(wt(io(ro(t))))}function mn(t,n,e){let r=new Set(e);return(o,a)=>{let i=e&&si(o,e);if(!si(o=((s,u)=>{let c={};for(let l in u)s.has(l)||(c[l]=u[l]);return c})(r,o),t)){if(a&&i)throw new TypeError("Invalid formatting options");o={...n,...o}}return e&&(o.timeZone=gn,["full","long"].includes(o.J)&&(o.J="medium")),o}}function kt(t,n=da,e=0){let[r,,,o]=t;return(a,i=Lf,...s)=>{let u=n(o&&o(...s),a,i,r,e),c=u.resolvedOptions();return[u,...Gu(t,c,s)]}}function da(t,n,e,r,o){if(e=r(e,o),t){if(e.timeZone!==void 0)throw new TypeError(Ml);e.timeZone=t}return new Rt(n,e)}function Gu(t,n,e){let[,r,o]=t;return e.map(a=>(a.calendar&&((i,s,u)=>{if((u||i!==M)&&i!==s)throw new RangeError(Vc)})(a.calendar,n.calendar,o),r(a,n)))}function qc(t,n,e){let r=n.timeZone,o=t(r),a={...Q(n,o),...e||H},i;return i=e?Zn(o,a,a.offsetNanoseconds,2):Lt(o,a),at(i,r,n.calendar)}function Uc(t,n=H){return it(X({...t,...n}))}function pr(t,n){return{...t,calendar:n}}function Ac(t,n){return{...t,timeZone:n}}function gr(t){let n=yr();return Cn(n,t.R(n))}function yr(){return Xn(Date.now(),bt)}function Yn(){return Di||(Di=new Rt().resolvedOptions().timeZone)}var Xu=(t,n)=>`Non-integer ${t}: ${n}`,Qu=(t,n)=>`Non-positive ${t}: ${n}`,tl=(t,n)=>`Non-finite ${t}: ${n}`,nl=t=>`Cannot convert bigint to ${t}`,el=t=>`Invalid bigint: ${t}`,rl="Cannot convert Symbol to string",ol="Invalid object",Lc=(t,n,e,r,o)=>o?Lc(t,o[n],o[e],o[r]):Vt(t,n)+`; must be between ${e}-${r}`,Vt=(t,n)=>`Invalid ${t}: ${n}`,ha=t=>`Missing ${t}`,al=t=>`Invalid field ${t}`,il=t=>`Duplicate field ${t}`,$c=t=>"No valid fields: "+t.join(),Hc="Invalid bag",Wc=(t,n,e)=>Vt(t,n)+"; must be "+Object.keys(e).join(),zc="Cannot use valueOf",wr="Invalid calling context",sl="Forbidden era/eraYear",cl="Mismatching era/eraYear",ul="Mismatching year/eraYear",ll=t=>`Invalid era: ${t}`,fl=t=>"Missing year"+(t?"/era/eraYear":""),dl=t=>`Invalid monthCode: ${t}`,hl="Mismatching month/monthCode",Kc="Missing month/monthCode",Vn="Invalid leap month",xn="Invalid protocol results",ma=t=>Vt("Calendar",t),Vc="Mismatching Calendars",pa=t=>Vt("TimeZone",t),Jc="Mismatching TimeZones",ml="Forbidden ICU TimeZone",pl="Out-of-bounds offset",gl="Out-of-bounds TimeZone gap",yl="Invalid TimeZone offset",wl="Ambiguous offset",Jt="Out-of-bounds date",Tl="Out-of-bounds duration",Dl="Cannot mix duration signs",Tr="Missing relativeTo",vl="Cannot use large units",bl="Required smallestUnit or largestUnit",Il="smallestUnit > largestUnit",J=t=>`Cannot parse: ${t}`,Ct=t=>`Invalid substring: ${t}`,_c=t=>`Cannot format ${t}`,Dr="Mismatching types for formatting",Ml="Cannot specify TimeZone",Gc=I(ae,(t,n)=>n),Mn=I(ae,(t,n,e)=>e),lt=I(Fe,2),zr={nanosecond:0,microsecond:1,millisecond:2,second:3,minute:4,hour:5,day:6,week:7,month:8,year:9},ga=Object.keys(zr),_=864e5,Xc=1e3,me=1e3,bt=1e6,dt=1e9,vr=6e10,br=36e11,C=864e11,pt=[1,me,bt,dt,vr,br,C],It=ga.slice(0,6),Kr=ie(It),El=["offset"],Qc=["timeZone"],tu=It.concat(El),nu=tu.concat(Qc),Vr=["era","eraYear"],Pl=Vr.concat(["year"]),ya=["year"],wa=["monthCode"],Ta=["month"].concat(wa),pe=["day"],Da=Ta.concat(ya),eu=wa.concat(ya),Zt=pe.concat(Da),Nl=pe.concat(Ta),ru=pe.concat(wa),ou=Mn(It,0),M="iso8601",jn="gregory",$t="japanese",au={[jn]:{"gregory-inverse":-1,gregory:0},[$t]:{"japanese-inverse":-1,japanese:0,meiji:1867,taisho:1911,showa:1925,heisei:1988,reiwa:2018},ethiopic:{ethioaa:0,ethiopic:5500},coptic:{"coptic-inverse":-1,coptic:0},roc:{"roc-inverse":-1,roc:0},buddhist:{be:0},islamic:{ah:0},indian:{saka:0},persian:{ap:0}},iu={[jn]:{bce:"gregory-inverse",ce:"gregory"},[$t]:{bce:"japanese-inverse",ce:"japanese"},ethiopic:{era0:"ethioaa",era1:"ethiopic"},coptic:{era0:"coptic-inverse",era1:"coptic"},roc:{broc:"roc-inverse",minguo:"roc"}},Cl={chinese:13,dangi:13,hebrew:-6},q=I(to,"string"),su=I(to,"boolean"),Ol=I(to,"number"),P=ga.map(t=>t+"s"),va=ie(P),Sl=P.slice(0,6),cu=P.slice(6),kl=cu.slice(1),Zl=Gc(P),j=Mn(P,0),ba=Mn(Sl,0),Ia=I(Mi,P),st=["isoNanosecond","isoMicrosecond","isoMillisecond","isoSecond","isoMinute","isoHour"],Ma=["isoDay","isoMonth","isoYear"],Ir=st.concat(Ma),Ea=ie(Ma),uu=ie(st),Rl=ie(Ir),H=Mn(uu,0),Fl=I(Mi,Ir),Pa=1e8,Na=Pa*_,Bl=[Pa,0],Yl=[-Pa,0],re=275760,oe=-271821,Rt=Intl.DateTimeFormat,lu="en-GB",xl=1970,Ot=1972,Yt=12,jl=Nn(1868,9,8),ql=G(ju,WeakMap),Ae="smallestUnit",Jr="unit",_n="roundingIncrement",$r="fractionalSecondDigits",fu="relativeTo",Hr="direction",du={constrain:0,reject:1},Ul=Object.keys(du),Al={compatible:0,reject:1,earlier:2,later:3},Ll={reject:0,use:1,prefer:2,ignore:3},$l={auto:0,never:1,critical:2,always:3},Hl={auto:0,never:1,critical:2},Wl={auto:0,never:1},zl={floor:0,halfFloor:1,ceil:2,halfCeil:3,trunc:4,halfTrunc:5,expand:6,halfExpand:7,halfEven:8},Kl={previous:-1,next:1},ge=I(wo,Ae),hu=I(wo,"largestUnit"),Vl=I(wo,Jr),mu=I(Ht,"overflow",du),pu=I(Ht,"disambiguation",Al),Jl=I(Ht,"offset",Ll),Ca=I(Ht,"calendarName",$l),_l=I(Ht,"timeZoneName",Hl),Gl=I(Ht,"offset",Wl),ye=I(Ht,"roundingMode",zl),Mr="PlainYearMonth",Er="PlainMonthDay",qn="PlainDate",pn="PlainDateTime",Pr="PlainTime",Ft="ZonedDateTime",Nr="Instant",Cr="Duration",Xl=[Math.floor,t=>Pe(t)?Math.floor(t):Math.round(t),Math.ceil,t=>Pe(t)?Math.ceil(t):Math.round(t),Math.trunc,t=>Pe(t)?Math.trunc(t)||0:Math.round(t),t=>t<0?Math.floor(t):Math.ceil(t),t=>Math.sign(t)*Math.round(Math.abs(t))||0,t=>Pe(t)?(t=Math.trunc(t)||0)+t%2:Math.round(t)],gn="UTC",Ze=5184e3,Ql=Be(1847),tf=Be(new Date().getUTCFullYear()+10),nf=/0+$/,Q=G(Hu,WeakMap),wi=2**32-1,v=G(t=>{let n=Jo(t);return typeof n=="object"?new Gr(n):new _r(n||0)}),_r=class{constructor(n){this.$=n}R(){return this.$}I(n){return(e=>{let r=Y({...e,...H});if(!r||Math.abs(r[0])>1e8)throw new RangeError(Jt)})(n),[uo(n,this.$)]}O(){}},Gr=class{constructor(n){this.nn=(e=>{function r(c){let l=Gn(c,s,u),[f,d]=mi(l),h=a(f),m=a(d);return h===m?h:o(i(f,d),h,m,c)}function o(c,l,f,d){let h,m;for(;(d===void 0||(h=d<c[0]?l:d>=c[1]?f:void 0)===void 0)&&(m=c[1]-c[0]);){let p=c[0]+Math.floor(m/2);e(p)===f?c[1]=p:c[0]=p+1}return h}let a=G(e),i=G(Wu),s=Ql,u=tf;return{tn(c){let l=r(c-86400),f=r(c+86400),d=c-l,h=c-f;if(l===f)return[d];let m=r(d);return m===r(h)?[c-m]:l>f?[d,h]:[]},rn:r,O(c,l){let f=Gn(c,s,u),[d,h]=mi(f),m=Ze*l,p=l<0?()=>h>s||(s=f,0):()=>d<u||(u=f,0);for(;p();){let g=a(d),y=a(h);if(g!==y){let w=i(d,h);o(w,g,y);let D=w[0];if((xt(D,c)||1)===l)return D}d+=m,h+=m}}}})((e=>r=>{let o=lo(e,r*Xc);return Be(Qs(o),parseInt(o.month),parseInt(o.day),parseInt(o.hour),parseInt(o.minute),parseInt(o.second))-r})(n))}R(n){return this.nn.rn((e=>li(e)[0])(n))*dt}I(n){let[e,r]=[Be((o=n).isoYear,o.isoMonth,o.isoDay,o.isoHour,o.isoMinute,o.isoSecond),o.isoMillisecond*bt+o.isoMicrosecond*me+o.isoNanosecond];var o;return this.nn.tn(e).map(a=>wt(on(Xn(a,dt),r)))}O(n,e){let[r,o]=li(n),a=this.nn.O(r+(e>0||o?1:0),e);if(a!==void 0)return Xn(a,dt)}},Oa="([+-])",Re="(?:[.,](\\d{1,9}))?",gu=`(?:(?:${Oa}(\\d{6}))|(\\d{4}))-?(\\d{2})`,Sa="(\\d{2})(?::?(\\d{2})(?::?(\\d{2})"+Re+")?)?",ka=Oa+Sa,ef=gu+"-?(\\d{2})(?:[T ]"+Sa+"(Z|"+ka+")?)?",yu="\\[(!?)([^\\]]*)\\]",Or=`((?:${yu}){0,9})`,rf=Fn(gu+Or),of=Fn("(?:--)?(\\d{2})-?(\\d{2})"+Or),af=Fn(ef+Or),sf=Fn("T?"+Sa+"(?:"+ka+")?"+Or),cf=Fn(ka),uf=new RegExp(yu,"g"),lf=Fn(`${Oa}?P(\\d+Y)?(\\d+M)?(\\d+W)?(\\d+D)?(?:T(?:(\\d+)${Re}H)?(?:(\\d+)${Re}M)?(?:(\\d+)${Re}S)?)?`),ff=G(t=>new Rt(lu,{timeZone:t,era:"short",year:"numeric",month:"numeric",day:"numeric",hour:"numeric",minute:"numeric",second:"numeric"})),df=/^(AC|AE|AG|AR|AS|BE|BS|CA|CN|CS|CT|EA|EC|IE|IS|JS|MI|NE|NS|PL|PN|PR|PS|SS|VS)T$/,hf=/[^\w\/:+-]+/,mf=/^M(\d{2})(L?)$/,pf=G(Vu),Za=G(t=>new Rt(lu,{calendar:t,timeZone:gn,era:"short",year:"numeric",month:"short",day:"numeric"})),wu={P(t,n,e){let r=E(e),o,{years:a,months:i,weeks:s,days:u}=n;if(u+=x(n,5)[0],a||i)o=((c,l,f,d,h)=>{let[m,p,g]=c.v(l);if(f){let[y,w]=c.q(m,p);m+=f,p=qe(y,w,c.L(m)),p=St("month",p,1,c.B(m),h)}return d&&([m,p]=c.un(m,p,d)),g=St("day",g,1,c.U(m,p),h),c.p(m,p,g)})(this,t,a,i,r);else{if(!s&&!u)return t;o=$(t)}if(o===void 0)throw new RangeError(Jt);return o+=(7*s+u)*_,ut(Ke(o))},N(t,n,e){if(e<=7){let u=0,c=zs({...t,...H},{...n,...H});return e===7&&([u,c]=qt(c,7)),{...j,weeks:u,days:c}}let r=this.v(t),o=this.v(n),[a,i,s]=((u,c,l,f,d,h,m)=>{let p=d-c,g=h-l,y=m-f;if(p||g){let w=Math.sign(p||g),D=u.U(d,h),b=0;if(Math.sign(y)===-w){let N=D;[d,h]=u.un(d,h,-w),p=d-c,g=h-l,D=u.U(d,h),b=w<0?-N:D}if(y=m-Math.min(f,D)+b,p){let[N,O]=u.q(c,l),[S,R]=u.q(d,h);if(g=S-N||Number(R)-Number(O),Math.sign(g)===-w){let k=w<0&&-u.B(d);p=(d-=w)-c,g=h-qe(N,O,u.L(d))+(k||u.B(d))}}}return[p,g,y]})(this,...r,...o);return e===8&&(i+=this.cn(a,r[0]),a=0),{...j,years:a,months:i,days:s}},F(t,n){let e=E(n),r=Ur(this,t),o=Oe(this,t,r,e),a=Ar(this,t,o,r,e);return vt(ut(this.V(r,o,a)),this.id||M)},K(t,n){let e=E(n),r=Ur(this,t),o=Oe(this,t,r,e);return Qn(co(this.V(r,o,1)),this.id||M)},_(t,n){let e=E(n),r,o,a,i=t.eraYear!==void 0||t.year!==void 0?Ur(this,t):void 0,s=!this.id;if(i===void 0&&s&&(i=Ot),i!==void 0){let f=Oe(this,t,i,e);r=Ar(this,t,f,i,e);let d=this.L(i);o=ca(f,d),a=f===d}else{if(t.monthCode===void 0)throw new TypeError(Kc);if([o,a]=sa(t.monthCode),this.id&&this.id!==jn&&this.id!==$t)if(this.id&&sn(this.id)==="coptic"&&e===0){let f=a||o!==13?30:6;r=t.day,r=Gn(r,1,f)}else if(this.id&&sn(this.id)==="chinese"&&e===0){let f=!a||o!==1&&o!==9&&o!==10&&o!==11&&o!==12?30:29;r=t.day,r=Gn(r,1,f)}else r=t.day;else r=Ar(this,t,Oe(this,t,Ot,e),Ot,e)}let u=this.G(o,a,r);if(!u)throw new RangeError("Cannot guess year");let[c,l]=u;return Ye(ut(this.V(c,l,r)),this.id||M)},fields(t){return Wr(this)&&t.includes("year")?[...t,...Vr]:t},k(t,n){let e=Object.assign(Object.create(null),t);return Lr(e,n,Ta),Wr(this)&&(Lr(e,n,Pl),this.id===$t&&Lr(e,n,Nl,Vr)),e},inLeapYear(t){let[n]=this.v(t);return this.sn(n)},monthsInYear(t){let[n]=this.v(t);return this.B(n)},daysInMonth(t){let[n,e]=this.v(t);return this.U(n,e)},daysInYear(t){let[n]=this.v(t);return this.fn(n)},dayOfYear:ia,era(t){return this.hn(t)[0]},eraYear(t){return this.hn(t)[1]},monthCode(t){let[n,e]=this.v(t),[r,o]=this.q(n,e);return fe(r,o)},dayOfWeek:xi,daysInWeek(){return 7}},gf={v:fo,hn:ji,q:Ri},yf={dayOfYear:ia,v:fo,p:Nn},wf=Object.assign({},yf,{weekOfYear:Ks,yearOfWeek:Vs,m(t){function n(h){return(7-h<r?7:0)-h}function e(h){let m=Yi(d+h),p=h||1,g=n(Jn(u+m*p,7));return l=(m+(g-c)*p)/7}let r=this.id?1:4,o=xi(t),a=this.dayOfYear(t),i=Jn(o-1,7),s=a-1,u=Jn(i-s,7),c=n(u),l,f=Math.floor((s-c)/7)+1,d=t.isoYear;return f?f>e(0)&&(f=1,d++):(f=e(-1),d--),[f,d,l]}}),Tf=Object.assign({},wu,wf,{v:fo,hn:ji,q:Ri,G(t,n){if(!n)return[Ot,t]},sn:ho,L(){},B:Fi,cn:t=>t*Yt,U:Bi,fn:Yi,V:(t,n,e)=>({isoYear:t,isoMonth:n,isoDay:e}),p:Nn,un:(t,n,e)=>(t+=$e(e,Yt),(n+=Xr(e,Yt))<1?(t--,n+=Yt):n>Yt&&(t++,n-=Yt),[t,n]),year(t){return t.isoYear},month(t){return t.isoMonth},day:t=>t.isoDay}),Df={v:Ue,hn:nc,q:tc},vf={dayOfYear:ia,v:Ue,p:ee,weekOfYear:Ks,yearOfWeek:Vs,m(){return[]}},bf=Object.assign({},wu,vf,{v:Ue,hn:nc,q:tc,G(t,n,e){let r=this.id&&sn(this.id)==="chinese"?((c,l,f)=>{if(l)switch(c){case 1:return 1651;case 2:return f<30?1947:1765;case 3:return f<30?1966:1955;case 4:return f<30?1963:1944;case 5:return f<30?1971:1952;case 6:return f<30?1960:1941;case 7:return f<30?1968:1938;case 8:return f<30?1957:1718;case 9:return 1832;case 10:return 1870;case 11:return 1814;case 12:return 1890}return 1972})(t,n,e):Ot,[o,a,i]=Ue.call(this,{isoYear:r,isoMonth:Yt,isoDay:31}),s=ke.call(this,o),u=a===s;(xt(t,ca(a,s))||xt(Number(n),Number(u))||xt(e,i))===1&&o--;for(let c=0;c<100;c++){let l=o-c,f=ke.call(this,l),d=qe(t,n,f);if(n===(d===f)&&e<=gi.call(this,l,d))return[l,d]}},sn(t){let n=Ne.call(this,t);return n>Ne.call(this,t-1)&&n>Ne.call(this,t+1)},L:ke,B:Ce,cn(t,n){let e=n+t,r=Math.sign(t),o=r<0?-1:0,a=0;for(let i=n;i!==e;i+=r)a+=Ce.call(this,i+o);return a},U:gi,fn:Ne,V(t,n,e){return Ke(ee.call(this,t,n,e))},p:ee,un(t,n,e){if(e){if(n+=e,!Number.isSafeInteger(n))throw new RangeError(Jt);if(e<0)for(;n<1;)n+=Ce.call(this,--t);else{let r;for(;n>(r=Ce.call(this,t));)n-=r,t++}}return[t,n]},year(t){return this.h(t).year},month(t){let{year:n,o:e}=this.h(t),{u:r}=this.l(n);return r[e]+1},day(t){return this.h(t).day}}),Sr=ec(gf,Df),T=ec(Tf,bf),Ti={era:Se,eraYear:L,year:L,month:ui,monthCode(t){let n=Se(t);return sa(n),n},day:ui,...Mn(It,L),...Mn(P,oo),offset(t){let n=Se(t);return dn(n),n}},Ra=I(bi,It,st),If=I(bi,st,It),jt="numeric",we=["timeZoneName"],Tu={month:jt,day:jt},Fa={year:jt,month:jt},Ba=Object.assign({},Fa,{day:jt}),Ya={hour:jt,minute:jt,second:jt},xa=Object.assign({},Ba,Ya),Mf=Object.assign({},xa,{timeZoneName:"short"}),Ef=Object.keys(Fa),Pf=Object.keys(Tu),Nf=Object.keys(Ba),Cf=Object.keys(Ya),ja=["dateStyle"],Of=Ef.concat(ja),Sf=Pf.concat(ja),qa=Nf.concat(ja,["weekday"]),Te=Cf.concat(["dayPeriod","timeStyle","fractionalSecondDigits"]),Ua=qa.concat(Te),kf=we.concat(Te),Zf=we.concat(qa),Rf=we.concat(["day","weekday"],Te),Ff=we.concat(["year","weekday"],Te),Bf=mn(Ua,xa),Yf=mn(Ua,Mf),xf=mn(Ua,xa,we),jf=mn(qa,Ba,kf),qf=mn(Te,Ya,Zf),Uf=mn(Of,Fa,Rf),Af=mn(Sf,Tu,Ff),Lf={},Du=new Rt(void 0,{calendar:M}).resolvedOptions().calendar===M,Aa=[Bf,Xe],vu=[Yf,Xe,0,(t,n)=>{let e=t.timeZone;if(n&&n.timeZone!==e)throw new RangeError(Jc);return e}],La=[xf,$],$a=[jf,$],Ha=[qf,t=>Ut(t)/bt],Wa=[Uf,$,Du],za=[Af,$,Du],Di;function Qt(t,n,e,r,o){function a(...u){if(!(this instanceof a))throw new TypeError(wr);Mu(this,n(...u))}function i(u,c){return Object.defineProperties(function(...l){return u.call(this,s(this),...l)},En(c))}function s(u){let c=K(u);if(!c||c.branding!==t)throw new TypeError(wr);return c}return Object.defineProperties(a.prototype,{...vi(gt(i,e)),...cn(gt(i,r)),...Le("Temporal."+t)}),Object.defineProperties(a,{...cn(o),...En(t)}),[a,u=>{let c=Object.create(a.prototype);return Mu(c,u),c},s]}function Wn(t){if(K(t)||t.calendar!==void 0||t.timeZone!==void 0)throw new TypeError(Hc);return t}function be(t){return Eu(t)||M}function Eu(t){let{calendar:n}=t;if(n!==void 0)return Rr(n)}function Rr(t){if(U(t)){let{calendar:n}=K(t)||{};if(!n)throw new TypeError(ma(t));return n}return(n=>de(Ms(q(n))))(t)}function Ja(t){let n={};for(let e in t)n[e]=r=>{let{calendar:o}=r;return T(o)[e](r)};return n}function tn(){throw new TypeError(zc)}function ct(t){if(U(t)){let{timeZone:n}=K(t)||{};if(!n)throw new TypeError(pa(t));return n}return(n=>lr(Es(q(n))))(t)}function B(t){if(U(t)){let n=K(t);return n&&n.branding===Cr?n:lc(t)}return Is(t)}function De(t){if(t!==void 0){if(U(t)){let n=K(t)||{};switch(n.branding){case Ft:case qn:return n;case pn:return vt(n)}let e=be(t);return{...rc(ct,v,T(e),t),calendar:e}}return ys(t)}}function _t(t,n){if(U(t)){let r=K(t)||{};switch(r.branding){case Pr:return E(n),r;case pn:return E(n),ft(r);case Ft:return E(n),fa(v,r)}return uc(t,n)}let e=bs(t);return E(n),e}function _a(t){return t===void 0?void 0:_t(t)}function Un(t,n){if(U(t)){let r=K(t)||{};switch(r.branding){case pn:return E(n),r;case qn:return E(n),it({...r,...H});case Ft:return E(n),ua(v,r)}return ac(T(be(t)),t,n)}let e=Ts(t);return E(n),e}function bu(t,n){if(U(t)){let r=K(t);if(r&&r.branding===Er)return E(n),r;let o=Eu(t);return cc(T(o||M),!o,t,n)}let e=vs(T,t);return E(n),e}function An(t,n){if(U(t)){let r=K(t);return r&&r.branding===Mr?(E(n),r):sc(T(be(t)),t,n)}let e=Ds(T,t);return E(n),e}function Ln(t,n){if(U(t)){let r=K(t)||{};switch(r.branding){case qn:return E(n),r;case pn:return E(n),vt(r);case Ft:return E(n),la(v,r)}return ic(T(be(t)),t,n)}let e=cr(t);return E(n),e}function $n(t,n){if(U(t)){let e=K(t);if(e&&e.branding===Ft)return ce(n),e;let r=be(t);return oc(ct,v,T(r),r,t,n)}return ws(t,n)}function Iu(t){return gt(n=>e=>n(Ka(e)),t)}function Ka(t){return Q(t,v)}function Hn(t){if(U(t)){let n=K(t);if(n)switch(n.branding){case Nr:return n;case Ft:return Dt(n.epochNanoseconds)}}return gs(t)}function $f(){function t(a,i){return new n(a,i)}function n(a,i=Object.create(null)){Zr.set(this,((s,u)=>{let c=new Rt(s,u),l=c.resolvedOptions(),f=l.locale,d=ot(Object.keys(u),l),h=G(zf),m=(p,...g)=>{if(p){if(g.length!==2)throw new TypeError(Dr);for(let b of g)if(b===void 0)throw new TypeError(Dr)}p||g[0]!==void 0||(g=[]);let y=g.map(b=>K(b)||Number(b)),w,D=0;for(let b of y){let N=typeof b=="object"?b.branding:void 0;if(D++&&N!==w)throw new TypeError(Dr);w=N}return w?h(w)(f,d,...y):[c,...y]};return m.X=c,m})(a,i))}let e=Rt.prototype,r=Object.getOwnPropertyDescriptors(e),o=Object.getOwnPropertyDescriptors(Rt);for(let a in r){let i=r[a],s=a.startsWith("format")&&Hf(a);typeof i.value=="function"?i.value=a==="constructor"?t:s||Wf(a):s&&(i.get=function(){if(!Zr.has(this))throw new TypeError(wr);return(...u)=>s.apply(this,u)},Object.defineProperties(i.get,En(`get ${a}`)))}return o.prototype.value=n.prototype=Object.create({},r),Object.defineProperties(t,o),t}function Hf(t){return Object.defineProperties(function(...n){let e=Zr.get(this),[r,...o]=e(t.includes("Range"),...n);return r[t](...o)},En(t))}function Wf(t){return Object.defineProperties(function(...n){return Zr.get(this).X[t](...n)},En(t))}function zf(t){let n=Xf[t];if(!n)throw new TypeError(_c(t));return kt(n,G(da),1)}var kr=new WeakMap,K=kr.get.bind(kr),Mu=kr.set.bind(kr),Pu={era:Pi,eraYear:Qr,year:We,month:yt,daysInMonth:yt,daysInYear:yt,inLeapYear:su,monthsInYear:yt},Ga={monthCode:q},Nu={day:yt},Kf={dayOfWeek:yt,dayOfYear:yt,weekOfYear:Ni,yearOfWeek:Qr,daysInWeek:yt},Xa=Ja(Object.assign({},Pu,Ga,Nu,Kf)),Vf=Ja({...Pu,...Ga}),Jf=Ja({...Ga,...Nu}),Ie={calendarId:t=>t.calendar},_f=ae(t=>n=>n[t],P.concat("sign")),Qa=ae((t,n)=>e=>e[st[n]],It),Cu={epochMilliseconds:Xe,epochNanoseconds:Wi},[Gf,Z,Sd]=Qt(Cr,Nc,{..._f,blank:hs},{with:(t,n)=>Z(yc(t,n)),negated:t=>Z(ir(t)),abs:t=>Z(ds(t)),add:(t,n,e)=>Z(Lo(De,T,v,0,t,B(n),e)),subtract:(t,n,e)=>Z(Lo(De,T,v,1,t,B(n),e)),round:(t,n)=>Z(fs(De,T,v,t,n)),total:(t,n)=>Ki(De,T,v,t,n),toLocaleString(t,n,e){return Intl.DurationFormat?new Intl.DurationFormat(n,e).format(this):er(t)},toString:er,toJSON:t=>er(t),valueOf:tn},{from:t=>Z(B(t)),compare:(t,n,e)=>Rs(De,T,v,B(t),B(n),e)}),Xf={Instant:Aa,PlainDateTime:La,PlainDate:$a,PlainTime:Ha,PlainYearMonth:Wa,PlainMonthDay:za},Qf=kt(Aa),td=kt(vu),nd=kt(La),ed=kt($a),rd=kt(Ha),od=kt(Wa),ad=kt(za),[id,Xt]=Qt(Pr,Pc,Qa,{with(t,n,e){return Xt(gc(this,Wn(n),e))},add:(t,n)=>Xt(xo(0,t,B(n))),subtract:(t,n)=>Xt(xo(1,t,B(n))),until:(t,n,e)=>Z(oa(0,t,_t(n),e)),since:(t,n,e)=>Z(oa(1,t,_t(n),e)),round:(t,n)=>Xt(Gi(t,n)),equals:(t,n)=>Us(t,_t(n)),toLocaleString(t,n,e){let[r,o]=rd(n,e,t);return r.format(o)},toString:Co,toJSON:t=>Co(t),valueOf:tn},{from:(t,n)=>Xt(_t(t,n)),compare:(t,n)=>fr(_t(t),_t(n))}),[sd,Mt]=Qt(pn,I(bc,Bn),{...Ie,...Xa,...Qa},{with:(t,n,e)=>Mt(dc(T,t,Wn(n),e)),withCalendar:(t,n)=>Mt(pr(t,Rr(n))),withPlainTime:(t,n)=>Mt(Uc(t,_a(n))),add:(t,n,e)=>Mt(Fo(T,0,t,B(n),e)),subtract:(t,n,e)=>Mt(Fo(T,1,t,B(n),e)),until:(t,n,e)=>Z(na(T,0,t,Un(n),e)),since:(t,n,e)=>Z(na(T,1,t,Un(n),e)),round:(t,n)=>Mt(_i(t,n)),equals:(t,n)=>Ys(t,Un(n)),toZonedDateTime:(t,n,e)=>z(Sc(v,t,ct(n),e)),toPlainDate:t=>Et(vt(t)),toPlainTime:t=>Xt(ft(t)),toLocaleString(t,n,e){let[r,o]=nd(n,e,t);return r.format(o)},toString:Mo,toJSON:t=>Mo(t),valueOf:tn},{from:(t,n)=>Mt(Un(t,n)),compare:(t,n)=>Xo(Un(t),Un(n))}),[cd,Va,kd]=Qt(Er,I(Ec,Bn),{...Ie,...Jf},{with:(t,n,e)=>Va(pc(T,t,Wn(n),e)),equals:(t,n)=>qs(t,bu(n)),toPlainDate(t,n){return Et(Yc(T,t,this,n))},toLocaleString(t,n,e){let[r,o]=ad(n,e,t);return r.format(o)},toString:No,toJSON:t=>No(t),valueOf:tn},{from:(t,n)=>Va(bu(t,n))}),[ud,ve,Zd]=Qt(Mr,I(Mc,Bn),{...Ie,...Vf},{with:(t,n,e)=>ve(mc(T,t,Wn(n),e)),add:(t,n,e)=>ve(Yo(T,0,t,B(n),e)),subtract:(t,n,e)=>ve(Yo(T,1,t,B(n),e)),until:(t,n,e)=>Z(ra(T,0,t,An(n),e)),since:(t,n,e)=>Z(ra(T,1,t,An(n),e)),equals:(t,n)=>js(t,An(n)),toPlainDate(t,n){return Et(Bc(T,t,this,n))},toLocaleString(t,n,e){let[r,o]=od(n,e,t);return r.format(o)},toString:Po,toJSON:t=>Po(t),valueOf:tn},{from:(t,n)=>ve(An(t,n)),compare:(t,n)=>hn(An(t),An(n))}),[ld,Et,Rd]=Qt(qn,I(Ic,Bn),{...Ie,...Xa},{with:(t,n,e)=>Et(hc(T,t,Wn(n),e)),withCalendar:(t,n)=>Et(pr(t,Rr(n))),add:(t,n,e)=>Et(Bo(T,0,t,B(n),e)),subtract:(t,n,e)=>Et(Bo(T,1,t,B(n),e)),until:(t,n,e)=>Z(ea(T,0,t,Ln(n),e)),since:(t,n,e)=>Z(ea(T,1,t,Ln(n),e)),equals:(t,n)=>xs(t,Ln(n)),toZonedDateTime(t,n){let e=U(n)?n:{timeZone:n};return z(kc(ct,_t,v,t,e))},toPlainDateTime:(t,n)=>Mt(Zc(t,_a(n))),toPlainYearMonth(t){return ve(Rc(T,t,this))},toPlainMonthDay(t){return Va(Fc(T,t,this))},toLocaleString(t,n,e){let[r,o]=ed(n,e,t);return r.format(o)},toString:Eo,toJSON:t=>Eo(t),valueOf:tn},{from:(t,n)=>Et(Ln(t,n)),compare:(t,n)=>hn(Ln(t),Ln(n))}),[fd,z]=Qt(Ft,I(vc,Bn,Zs),{...Cu,...Ie,...Iu(Xa),...Iu(Qa),offset:t=>kn(Ka(t).offsetNanoseconds),offsetNanoseconds:t=>Ka(t).offsetNanoseconds,timeZoneId:t=>t.timeZone,hoursInDay:t=>Xi(v,t)},{with:(t,n,e)=>z(fc(T,v,t,Wn(n),e)),withCalendar:(t,n)=>z(pr(t,Rr(n))),withTimeZone:(t,n)=>z(Ac(t,ct(n))),withPlainTime:(t,n)=>z(qc(v,t,_a(n))),add:(t,n,e)=>z(Ro(T,v,0,t,B(n),e)),subtract:(t,n,e)=>z(Ro(T,v,1,t,B(n),e)),until:(t,n,e)=>Z(F(ta(T,v,0,t,$n(n),e))),since:(t,n,e)=>Z(F(ta(T,v,1,t,$n(n),e))),round:(t,n)=>z(Ji(v,t,n)),startOfDay:t=>z(Qi(v,t)),equals:(t,n)=>Bs(t,$n(n)),toInstant:t=>Gt(Oc(t)),toPlainDateTime:t=>Mt(ua(v,t)),toPlainDate:t=>Et(la(v,t)),toPlainTime:t=>Xt(fa(v,t)),toLocaleString(t,n,e={}){let[r,o]=td(n,e,t);return r.format(o)},toString:(t,n)=>Io(v,t,n),toJSON:t=>Io(v,t),valueOf:tn,getTimeZoneTransition(t,n){let{timeZone:e,epochNanoseconds:r}=t,o=Ai(n),a=v(e).O(r,o);return a?z({...t,epochNanoseconds:a}):null}},{from:(t,n)=>z($n(t,n)),compare:(t,n)=>Go($n(t),$n(n))}),[dd,Gt,Fd]=Qt(Nr,Dc,Cu,{add:(t,n)=>Gt(Zo(0,t,B(n))),subtract:(t,n)=>Gt(Zo(1,t,B(n))),until:(t,n,e)=>Z(Qo(0,t,Hn(n),e)),since:(t,n,e)=>Z(Qo(1,t,Hn(n),e)),round:(t,n)=>Gt(Vi(t,n)),equals:(t,n)=>Fs(t,Hn(n)),toZonedDateTimeISO:(t,n)=>z(Cc(t,ct(n))),toLocaleString(t,n,e){let[r,o]=Qf(n,e,t);return r.format(o)},toString:(t,n)=>bo(ct,v,t,n),toJSON:t=>bo(ct,v,t),valueOf:tn},{from:t=>Gt(Hn(t)),fromEpochMilliseconds:t=>Gt(xc(t)),fromEpochNanoseconds:t=>Gt(jc(t)),compare:(t,n)=>_o(Hn(t),Hn(n))}),hd=Object.defineProperties({},{...Le("Temporal.Now"),...cn({timeZoneId:()=>Yn(),instant:()=>Gt(Dt(yr())),zonedDateTimeISO:(t=Yn())=>z(at(yr(),ct(t),M)),plainDateTimeISO:(t=Yn())=>Mt(it(gr(v(ct(t))),M)),plainDateISO:(t=Yn())=>Et(vt(gr(v(ct(t))),M)),plainTimeISO:(t=Yn())=>Xt(ft(gr(v(ct(t)))))})}),ti=Object.defineProperties({},{...Le("Temporal"),...cn({PlainYearMonth:ud,PlainMonthDay:cd,PlainDate:ld,PlainTime:id,PlainDateTime:sd,ZonedDateTime:fd,Instant:dd,Duration:Gf,Now:hd})}),md=$f(),Zr=new WeakMap,ni=Object.defineProperties(Object.create(Intl),cn({DateTimeFormat:md}));function Fr(t){let n=Me().timeZone;return ti.Instant.from(t).toZonedDateTimeISO(n)}function Me(){return ni.DateTimeFormat().resolvedOptions()}var yn=class{element;contents;action;constructor(n,e){this.element=Ou(`${n}/error`,HTMLElement),this.contents=Ou(`${n}/error/content`,HTMLElement),this.action=e}clearError(){this.element.classList.add("collapse"),this.element.ariaHidden="true",this.contents.textContent=""}addError(n){if(this.contents.textContent===""){this.element.classList.remove("collapse"),this.element.ariaHidden="false",this.contents.textContent=`Could not ${this.action}: ${n}`;return}this.contents.textContent+=`, ${n}`}panic(){this.element.classList.remove("collapse"),this.element.ariaHidden="false",this.contents.textContent=`Something went wrong while trying to ${this.action}. Try again later.`}};function Ou(t,n){let e=document.getElementById(t);if(!e||!(e instanceof n))throw`element '${t}' does not exist`;return e}async function Br(){let t=await zn();return t?{bearer:t.bearer,act:t.claims.act??null,exp:t.claims.exp,sub:t.claims.sub,typ:t.claims.typ,tid:t.claims.tid}:null}async function nn(t){await Br()&&(await new A("POST",tt+"/revoked-tokens").setHeaders([nt]).fetch(),alert("Your session has expired")),await Dn();let e=t?`/login?redirect=${encodeURI(location.href)}`:"/login";return await Kn(e)}ii();var wn=await Br();if(!wn)throw await Kn("/login"),new Error;wn.typ==="provisioning"&&await Kn("/add-passkey");document.getElementById("logout").addEventListener("mouseup",async()=>{await Dn(),await nn(!1)});var ei=new yn("/deleteIdentity","delete your identity");document.getElementById("deleteIdentity").addEventListener("mouseup",async()=>{let t=await qr(wn,`DELETE /identities/${wn.sub}`);if(t.status==="cancelled"){ei.addError("the consent prompt was cancelled");return}else if(t.status==="unauthenticated"){await nn(!1);return}else if(t.status!=="ok"){ei.panic();return}let n=await new A("DELETE",tt+`/identities/${wn.sub}`).setHeaders([nt,["Authorization",t.data]]).fetch();if(n.status==="badRequest"&&n.problems.length!=0){for(let e of n.problems)e.detail&&Su.addError(e.detail);return}else if(n.status!=="ok"){ei.panic();return}await Dn(),await nn(!1)});var Ee=await new A("GET",tt+`/identities/${wn.sub}`).setHeaders([nt]).fetch(),pd=new yn("/identity","fetch your identity details");if(Ee.status==="ok"){let t=Fr(Ee.body.created).toPlainDate().toLocaleString(Me().locale,{dateStyle:"long"});document.getElementById("username").textContent=Ee.body.username,document.getElementById("displayName").textContent=Ee.body.displayName,document.getElementById("created").textContent=t}else Ee.status==="unauthenticated"?await nn(!1):pd.panic();var ri=await new A("GET",tt+`/public-keys?identityId=${wn.sub}`).setHeaders([nt]).fetch(),Su=new yn("/passkeys","fetch your passkey details"),Yr=new yn("/deletePasskey","delete your passkey");if(ri.status==="ok"){let t=document.getElementById("passkeys");for(let n of ri.body.publicKeys)gd(n,t)}else ri.status==="unauthenticated"?await nn(!1):Su.panic();function gd(t,n){let e=document.createElement("button");e.setHTMLUnsafe('<svg aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 512 512"><path d="M296,64H216a7.91,7.91,0,0,0-8,8V96h96V72A7.91,7.91,0,0,0,296,64Z" style="fill: none" /><path d="M432,96H336V72a40,40,0,0,0-40-40H216a40,40,0,0,0-40,40V96H80a16,16,0,0,0,0,32H97L116,432.92c1.42,26.85,22,47.08,48,47.08H348c26.13,0,46.3-19.78,48-47L415,128h17a16,16,0,0,0,0-32ZM192.57,416H192a16,16,0,0,1-16-15.43l-8-224a16,16,0,1,1,32-1.14l8,224A16,16,0,0,1,192.57,416ZM272,400a16,16,0,0,1-32,0V176a16,16,0,0,1,32,0ZM304,96H208V72a7.91,7.91,0,0,1,8-8h80a7.91,7.91,0,0,1,8,8Zm32,304.57A16,16,0,0,1,320,416h-.58A16,16,0,0,1,304,399.43l8-224a16,16,0,1,1,32,1.14Z" /></svg>'),e.addEventListener("mouseup",()=>{yd(t.rawId)}),e.className="icon-button circle ghost red",e.ariaLabel=`delete '${t.displayName}' passkey`;let r=document.createElement("h3");r.innerText=t.displayName;let o=document.createElement("div");o.appendChild(r),o.appendChild(e);let a=document.createElement("small"),i=Fr(t.created).toPlainDate().toLocaleString(Me().locale,{dateStyle:"long"});a.innerText=`Created: ${i}`;let s=document.createElement("small"),u=t.lastUsed?Fr(t.lastUsed).toPlainDate().toLocaleString(Me().locale,{dateStyle:"long"}):"Never";s.innerText=`Last Used: ${u}`;let c=document.createElement("div");c.appendChild(a),c.appendChild(s);let l=document.createElement("div");l.className="passkey",l.appendChild(o),l.appendChild(c),n.prepend(l)}async function yd(t){let n=await Br();if(!n){await nn(!1);return}let e=await qr(n,`DELETE /public-keys/${t}`);if(e.status==="cancelled"){Yr.addError("the prompt was cancelled");return}else if(e.status==="unauthenticated"){await nn(!1);return}else if(e.status!=="ok"){Yr.panic();return}let r=await new A("DELETE",tt+`/public-keys/${t}`).setHeaders([nt,["Authorization",e.data]]).fetch();if(r.status==="badRequest"&&r.problems.length!=0){for(let o of r.problems)o.detail&&Yr.addError(o.detail);return}else if(r.status!=="ok"){Yr.panic();return}location.reload()}
 //# sourceMappingURL=index.js.map
@@ -1,6 +1,7 @@
+import { FetchBuilder } from "../lib/fetch.ts";
 import { Form } from "../lib/form.ts";
 import { setHref } from "../lib/redirect.ts";
-import { setConfig } from "../scripts/config.ts";
+import { API_KEY, API_URL, setConfig } from "../scripts/config.ts";
 import { getToken } from "../scripts/token.ts";
 import { requestCommonToken } from "../scripts/webauthn.ts";
 
@@ -10,7 +11,7 @@ const token = await getToken();
 if (token) {
   switch (token.typ) {
     case "common":
-      await setHref("/identity");
+      await goToNextPage();
       break;
     case "provisioning":
       await setHref("/add-passkey");
@@ -68,7 +69,29 @@ form.form.addEventListener("submit", async (event) => {
 
 async function goToNextPage(): Promise<never> {
   const params = new URLSearchParams(document.location.search);
+
+  // An OAuth client sent the user here to sign in, so they are returned to the client with a code
+  const authorizationRequest = params.get("authorizationRequest");
+  if (authorizationRequest) {
+    return await completeAuthorization(authorizationRequest);
+  }
+
   const redirect = params.get("redirect");
   const nextPage = redirect ? decodeURI(redirect) : "/identity";
   return await setHref(nextPage);
 }
+
+async function completeAuthorization(authorizationRequest: string): Promise<never> {
+  const response = await new FetchBuilder("POST", API_URL + "/authorization-codes")
+    .setHeaders([API_KEY])
+    .setBody({ authorizationRequest })
+    .fetch<{ redirectUri: string }>();
+
+  if (response.status === "ok") {
+    return await setHref(response.body.redirectUri);
+  }
+
+  // The request has expired or was already used, so the client has to start again
+  alert("The sign in request has expired, return to the application and try again");
+  return await setHref("/identity");
+}
@@ -14,7 +14,7 @@ export async function requestPasskeyCreation(
   preferResidentKey: boolean,
   displayName: string,
 ): Promise<WebAuthNResult<object>> {
-  const challenge = await getChallenge(token.sub);
+  const challenge = await getChallenge(token.sub, { purpose: "registration" });
   if (challenge.status !== "ok") {
     return challenge;
   }
@@ -73,7 +73,7 @@ export async function requestPasskeyCreation(
 export async function requestCommonToken(
   username: string | null,
 ): Promise<WebAuthNResult<string>> {
-  const challenge = await getChallenge(null);
+  const challenge = await getChallenge(null, { purpose: "authentication" });
   if (challenge.status !== "ok") {
     return challenge;
   }
@@ -113,7 +113,7 @@ export async function requestConsentToken(
   originalToken: TokenDetails,
   action: string,
 ): Promise<WebAuthNResult<string>> {
-  const challenge = await getChallenge(originalToken.sub);
+  const challenge = await getChallenge(originalToken.sub, { purpose: "consent", act: action });
   if (challenge.status !== "ok") {
     return challenge;
   }
@@ -192,11 +192,17 @@ async function getExistingCredentials(
   }
 }
 
+type ChallengePurpose =
+  | { purpose: "registration" }
+  | { purpose: "authentication" }
+  | { purpose: "consent"; act: string };
+
 async function getChallenge(
   identityId: string | null,
+  purpose: ChallengePurpose,
 ): Promise<WebAuthNResult<string>> {
   const response = await new FetchBuilder("POST", API_URL + "/challenges")
-    .setBody({ identityId: identityId })
+    .setBody({ identityId: identityId, ...purpose })
     .setHeaders([API_KEY])
     .fetch<Challenge>();
   if (response.status === "ok") {
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+  token BYTEA NOT NULL PRIMARY KEY,
+  family BYTEA NOT NULL,
+  identity_id BYTEA NOT NULL REFERENCES identities (id) ON DELETE CASCADE,
+  access_token_id VARCHAR NOT NULL,
+  access_token_expires TIMESTAMP WITH TIME ZONE NOT NULL,
+  used TIMESTAMP WITH TIME ZONE DEFAULT NULL,
+  issued TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW())),
+  expires TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW()) + '30 days'::INTERVAL)
+);
+
+CREATE INDEX IF NOT EXISTS refresh_token_family_index ON refresh_tokens (family);
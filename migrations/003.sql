@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS sessions (
+  token VARCHAR NOT NULL PRIMARY KEY,
+  identity_id BYTEA NOT NULL REFERENCES identities (id) ON DELETE CASCADE,
+  token_type VARCHAR NOT NULL,
+  public_key_id BYTEA REFERENCES public_keys (raw_id) ON DELETE SET NULL,
+  user_agent VARCHAR,
+  issued TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW())),
+  expires TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS session_identity_index ON sessions (identity_id);
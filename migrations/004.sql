@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS clients (
+  id VARCHAR NOT NULL PRIMARY KEY,
+  display_name VARCHAR NOT NULL,
+  redirect_uris VARCHAR ARRAY NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW()))
+);
+
+CREATE TABLE IF NOT EXISTS authorization_requests (
+  id BYTEA NOT NULL PRIMARY KEY,
+  client_id VARCHAR NOT NULL REFERENCES clients (id) ON DELETE CASCADE,
+  redirect_uri VARCHAR NOT NULL,
+  code_challenge VARCHAR NOT NULL,
+  state VARCHAR,
+  scope VARCHAR,
+  issued TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW())),
+  expires TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW()) + '15 minutes'::INTERVAL)
+);
+
+CREATE TABLE IF NOT EXISTS authorization_codes (
+  code BYTEA NOT NULL PRIMARY KEY,
+  client_id VARCHAR NOT NULL REFERENCES clients (id) ON DELETE CASCADE,
+  identity_id BYTEA NOT NULL REFERENCES identities (id) ON DELETE CASCADE,
+  redirect_uri VARCHAR NOT NULL,
+  code_challenge VARCHAR NOT NULL,
+  scope VARCHAR,
+  issued TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW())),
+  expires TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW()) + '5 minutes'::INTERVAL)
+);
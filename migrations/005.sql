@@ -0,0 +1,6 @@
+ALTER TABLE authorization_requests ADD COLUMN IF NOT EXISTS nonce VARCHAR;
+
+ALTER TABLE authorization_codes ADD COLUMN IF NOT EXISTS nonce VARCHAR;
+
+ALTER TABLE authorization_codes
+ADD COLUMN IF NOT EXISTS auth_time TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW()));
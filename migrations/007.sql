@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS audit_events (
+  id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  actor_id BYTEA,
+  kind VARCHAR NOT NULL,
+  target VARCHAR,
+  ip_address VARCHAR,
+  user_agent VARCHAR,
+  outcome VARCHAR NOT NULL,
+  occurred TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT (timezone('utc', NOW()))
+);
+
+CREATE INDEX IF NOT EXISTS audit_event_actor_index ON audit_events (actor_id, id);
+
+-- Events outlive the identities they refer to and are never changed once written
+CREATE OR REPLACE RULE audit_events_no_update AS ON UPDATE TO audit_events DO INSTEAD NOTHING;
+
+CREATE OR REPLACE RULE audit_events_no_delete AS ON DELETE TO audit_events DO INSTEAD NOTHING;
@@ -0,0 +1,13 @@
+-- Challenges issued before purposes existed may only be used to log in
+ALTER TABLE challenges ADD COLUMN IF NOT EXISTS purpose VARCHAR NOT NULL DEFAULT 'authentication';
+
+ALTER TABLE challenges ALTER COLUMN purpose DROP DEFAULT;
+
+ALTER TABLE challenges ADD COLUMN IF NOT EXISTS act VARCHAR;
+
+ALTER TABLE challenges DROP CONSTRAINT IF EXISTS challenge_purpose_check;
+
+ALTER TABLE challenges ADD CONSTRAINT challenge_purpose_check CHECK (
+  (purpose IN ('registration', 'authentication') AND act IS NULL)
+  OR (purpose = 'consent' AND act IS NOT NULL)
+);
@@ -0,0 +1,115 @@
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use http::{header::AUTHORIZATION, request::Parts};
+use jiff::Timestamp;
+use ts_api_helper::{ErrorResponse, InlineErrorResponse};
+
+use crate::{
+    ApiState,
+    jwt::{Claims, verify_token},
+    routes::revoked_tokens::is_revoked,
+};
+
+/// A token issued by this service.
+#[derive(Debug)]
+pub struct Token {
+    pub claims: Claims,
+}
+
+impl Token {
+    /// Whether the token was issued to an OAuth client rather than for first-party use.
+    pub fn is_client_token(&self) -> bool {
+        self.claims.aud.is_some()
+    }
+}
+
+/// The bearer token of a request, verified against the keyring and the revocations table.
+///
+/// The service only accepts tokens it issued, so the token is checked against its own keys and
+/// revocations rather than with JWKS and revocation lookups over HTTP. Verifying a token then only
+/// makes a database query, which is traced as a child of the request span.
+///
+/// Tokens issued to OAuth clients are rejected as forbidden, they are only accepted by the routes
+/// that take a [`ClientAccessToken`].
+pub struct AccessToken(pub Token);
+
+impl FromRequestParts<ApiState> for AccessToken {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ApiState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = verify_request_token(parts, state).await?;
+        if token.is_client_token() {
+            return Err(ErrorResponse::forbidden());
+        }
+
+        Ok(Self(token))
+    }
+}
+
+impl OptionalFromRequestParts<ApiState> for AccessToken {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ApiState,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if !parts.headers.contains_key(AUTHORIZATION) {
+            return Ok(None);
+        }
+
+        <Self as FromRequestParts<_>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// The bearer token of a request, which may have been issued to an OAuth client.
+///
+/// Routes taking this token must limit themselves to the token's scope when it was issued to a
+/// client.
+pub struct ClientAccessToken(pub Token);
+
+impl FromRequestParts<ApiState> for ClientAccessToken {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ApiState,
+    ) -> Result<Self, Self::Rejection> {
+        verify_request_token(parts, state).await.map(Self)
+    }
+}
+
+async fn verify_request_token(parts: &Parts, state: &ApiState) -> Result<Token, ErrorResponse> {
+    let token = bearer_token(parts).ok_or_else(ErrorResponse::unauthenticated)?;
+
+    let claims = verify_token::<Claims>(&state.keyring.key_set(), token)
+        .filter(|claims| claims.exp > Timestamp::now())
+        .ok_or_else(ErrorResponse::unauthenticated)?;
+
+    let database = state.pool.get().await.internal_server_error()?;
+    if is_revoked(&*database, &claims.tid)
+        .await
+        .internal_server_error()?
+    {
+        return Err(ErrorResponse::unauthenticated());
+    }
+
+    Ok(Token { claims })
+}
+
+/// The token of a `bearer` authorization header.
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    let (scheme, token) = parts
+        .headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .split_once(' ')?;
+
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then_some(token.trim())
+}
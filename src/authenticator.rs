@@ -0,0 +1,746 @@
+//! A software WebAuthn authenticator, allowing tests and headless clients to perform passkey
+//! ceremonies without a browser or hardware.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use base64ct::{Base64UrlUnpadded, Encoding};
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::{EcGroup, EcKey},
+    error::ErrorStack,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Private},
+    sha::sha256,
+    sign::Signer,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, clap::Subcommand)]
+pub enum AuthenticatorCommand {
+    /// Create a software authenticator with a new key, saving it to a state file.
+    New {
+        /// The file to save the authenticator's key, credential and signature counter to.
+        #[arg(long)]
+        state: PathBuf,
+
+        /// The algorithm of the authenticator's key.
+        #[arg(long, value_enum, default_value = "es256")]
+        algorithm: Algorithm,
+    },
+
+    /// Print the attestation response creating the authenticator's credential.
+    Create {
+        /// The authenticator's state file.
+        #[arg(long)]
+        state: PathBuf,
+
+        #[command(flatten)]
+        ceremony: CeremonyArgs,
+
+        /// The base64url user handle, making the credential discoverable.
+        #[arg(long)]
+        user_handle: Option<String>,
+    },
+
+    /// Print the assertion response signing a challenge with the authenticator's credential.
+    Get {
+        /// The authenticator's state file.
+        #[arg(long)]
+        state: PathBuf,
+
+        #[command(flatten)]
+        ceremony: CeremonyArgs,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CeremonyArgs {
+    /// The base64url challenge issued by the relying party.
+    #[arg(long)]
+    challenge: String,
+
+    /// The relying party ID the credential is scoped to.
+    #[arg(long)]
+    rp_id: String,
+
+    /// The origin of the client performing the ceremony.
+    #[arg(long)]
+    origin: String,
+}
+
+impl AuthenticatorCommand {
+    pub fn execute(self) -> Result<(), AuthenticatorError> {
+        let response = match self {
+            Self::New { state, algorithm } => {
+                let authenticator = SoftwareAuthenticator::new(algorithm)?;
+                authenticator.save(&state, false)?;
+                eprintln!(
+                    "Created credential `{}` in `{}`",
+                    Base64UrlUnpadded::encode_string(authenticator.credential_id()),
+                    state.display()
+                );
+
+                return Ok(());
+            }
+
+            Self::Create {
+                state,
+                ceremony,
+                user_handle,
+            } => {
+                let mut authenticator = SoftwareAuthenticator::load(&state)?;
+                let challenge = decode(&ceremony.challenge)?;
+                let user_handle = user_handle.as_deref().map(decode).transpose()?;
+                let response =
+                    authenticator.create(ceremony.ceremony(&challenge), user_handle.as_deref())?;
+                authenticator.save(&state, true)?;
+
+                response
+            }
+
+            Self::Get { state, ceremony } => {
+                let mut authenticator = SoftwareAuthenticator::load(&state)?;
+                let challenge = decode(&ceremony.challenge)?;
+                let response = authenticator.get(ceremony.ceremony(&challenge))?;
+                authenticator.save(&state, true)?;
+                eprintln!(
+                    "Signed with counter {} from `{}`",
+                    authenticator.counter(),
+                    state.display()
+                );
+
+                response
+            }
+        };
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).map_err(AuthenticatorError::serialize)?
+        );
+
+        Ok(())
+    }
+}
+
+impl CeremonyArgs {
+    fn ceremony<'a>(&'a self, challenge: &'a [u8]) -> Ceremony<'a> {
+        Ceremony {
+            challenge,
+            rp_id: &self.rp_id,
+            origin: &self.origin,
+        }
+    }
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, AuthenticatorError> {
+    Base64UrlUnpadded::decode_vec(value).map_err(AuthenticatorError::decode)
+}
+
+/// The COSE algorithm of an authenticator's key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Algorithm {
+    /// ECDSA using P-256 and SHA-256.
+    Es256,
+    /// EdDSA using Ed25519.
+    #[value(name = "eddsa")]
+    EdDsa,
+}
+
+impl Algorithm {
+    /// The COSE algorithm identifier.
+    pub fn cose_identifier(self) -> i32 {
+        match self {
+            Self::Es256 => -7,
+            Self::EdDsa => -8,
+        }
+    }
+}
+
+/// The relying party and client details of a ceremony.
+#[derive(Debug, Clone, Copy)]
+pub struct Ceremony<'a> {
+    /// The challenge issued by the relying party.
+    pub challenge: &'a [u8],
+    /// The relying party ID the credential is scoped to.
+    pub rp_id: &'a str,
+    /// The origin of the client performing the ceremony.
+    pub origin: &'a str,
+}
+
+/// A `PublicKeyCredential` as sent to the API by the frontend.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyCredentialJson {
+    pub id: String,
+    pub raw_id: String,
+    pub authenticator_attachment: &'static str,
+    pub response: ResponseJson,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ResponseJson {
+    #[serde(rename_all = "camelCase")]
+    Attestation {
+        attestation_object: String,
+        #[serde(rename = "clientDataJSON")]
+        client_data_json: String,
+        authenticator_data: String,
+        public_key: String,
+        public_key_algorithm: i32,
+        transports: Vec<&'static str>,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    Assertion {
+        authenticator_data: String,
+        #[serde(rename = "clientDataJSON")]
+        client_data_json: String,
+        signature: String,
+        user_handle: Option<String>,
+    },
+}
+
+/// A platform authenticator holding a single credential, using `none` attestation.
+///
+/// The signature counter is incremented on every assertion.
+#[derive(Debug)]
+pub struct SoftwareAuthenticator {
+    key: PKey<Private>,
+    algorithm: Algorithm,
+    credential_id: Vec<u8>,
+    user_handle: Option<Vec<u8>>,
+    counter: u32,
+}
+
+/// The saved state of a [`SoftwareAuthenticator`], so headless clients can use one credential
+/// across runs.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticatorState {
+    algorithm: Algorithm,
+    /// The PKCS#8 PEM encoded private key.
+    key: String,
+    credential_id: String,
+    user_handle: Option<String>,
+    counter: u32,
+}
+
+/// User present and user verified.
+const FLAGS_UP_UV: u8 = 0x05;
+/// Attested credential data included.
+const FLAG_AT: u8 = 0x40;
+
+impl SoftwareAuthenticator {
+    /// Creates an authenticator with a new key and random credential ID.
+    pub fn new(algorithm: Algorithm) -> Result<Self, AuthenticatorError> {
+        let key = match algorithm {
+            Algorithm::Es256 => {
+                let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+                    .map_err(AuthenticatorError::key)?;
+                let key = EcKey::generate(&group).map_err(AuthenticatorError::key)?;
+                PKey::from_ec_key(key).map_err(AuthenticatorError::key)?
+            }
+            Algorithm::EdDsa => PKey::generate_ed25519().map_err(AuthenticatorError::key)?,
+        };
+
+        let mut credential_id = vec![0u8; 32];
+        rand::rng().fill_bytes(&mut credential_id);
+
+        Ok(Self {
+            key,
+            algorithm,
+            credential_id,
+            user_handle: None,
+            counter: 0,
+        })
+    }
+
+    pub fn credential_id(&self) -> &[u8] {
+        &self.credential_id
+    }
+
+    /// The signature counter of the last assertion.
+    pub fn counter(&self) -> u32 {
+        self.counter
+    }
+
+    /// Loads an authenticator from its state file.
+    pub fn load(path: &Path) -> Result<Self, AuthenticatorError> {
+        let contents = fs::read(path).map_err(AuthenticatorError::io)?;
+        let state: AuthenticatorState =
+            serde_json::from_slice(&contents).map_err(AuthenticatorError::state)?;
+
+        Ok(Self {
+            key: PKey::private_key_from_pem(state.key.as_bytes())
+                .map_err(AuthenticatorError::key)?,
+            algorithm: state.algorithm,
+            credential_id: decode(&state.credential_id)?,
+            user_handle: state.user_handle.as_deref().map(decode).transpose()?,
+            counter: state.counter,
+        })
+    }
+
+    /// Saves the authenticator to a state file readable only by its owner, refusing to replace an
+    /// existing file unless `replace` is set.
+    pub fn save(&self, path: &Path, replace: bool) -> Result<(), AuthenticatorError> {
+        let key = self
+            .key
+            .private_key_to_pem_pkcs8()
+            .map_err(AuthenticatorError::key)?;
+        let state = AuthenticatorState {
+            algorithm: self.algorithm,
+            key: String::from_utf8_lossy(&key).into_owned(),
+            credential_id: Base64UrlUnpadded::encode_string(&self.credential_id),
+            user_handle: self
+                .user_handle
+                .as_deref()
+                .map(Base64UrlUnpadded::encode_string),
+            counter: self.counter,
+        };
+        let json = serde_json::to_vec_pretty(&state).map_err(AuthenticatorError::state)?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).mode(0o600);
+        if replace {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+
+        let mut file = options.open(path).map_err(AuthenticatorError::io)?;
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .map_err(AuthenticatorError::io)?;
+        file.write_all(&json).map_err(AuthenticatorError::io)
+    }
+
+    /// Creates the credential, returning the attestation response.
+    ///
+    /// If a user handle is given the credential is discoverable and returns the handle in
+    /// assertions.
+    pub fn create(
+        &mut self,
+        ceremony: Ceremony<'_>,
+        user_handle: Option<&[u8]>,
+    ) -> Result<PublicKeyCredentialJson, AuthenticatorError> {
+        self.user_handle = user_handle.map(<[u8]>::to_vec);
+
+        let client_data = client_data("webauthn.create", ceremony)?;
+
+        // Attested credential data: AAGUID, credential ID length, credential ID, COSE key
+        let mut authenticator_data = self.authenticator_data(ceremony, FLAGS_UP_UV | FLAG_AT);
+        authenticator_data.extend([0u8; 16]);
+        let credential_id_length = u16::try_from(self.credential_id.len())
+            .map_err(|_| AuthenticatorError::CredentialId)?;
+        authenticator_data.extend(credential_id_length.to_be_bytes());
+        authenticator_data.extend(&self.credential_id);
+        authenticator_data.extend(self.cose_public_key()?);
+
+        let mut attestation_object = vec![];
+        cbor::map(&mut attestation_object, 3);
+        cbor::text(&mut attestation_object, "fmt");
+        cbor::text(&mut attestation_object, "none");
+        cbor::text(&mut attestation_object, "attStmt");
+        cbor::map(&mut attestation_object, 0);
+        cbor::text(&mut attestation_object, "authData");
+        cbor::bytes(&mut attestation_object, &authenticator_data);
+
+        let public_key = self
+            .key
+            .public_key_to_der()
+            .map_err(AuthenticatorError::key)?;
+
+        Ok(self.credential(ResponseJson::Attestation {
+            attestation_object: Base64UrlUnpadded::encode_string(&attestation_object),
+            client_data_json: Base64UrlUnpadded::encode_string(&client_data),
+            authenticator_data: Base64UrlUnpadded::encode_string(&authenticator_data),
+            public_key: Base64UrlUnpadded::encode_string(&public_key),
+            public_key_algorithm: self.algorithm.cose_identifier(),
+            transports: vec!["internal"],
+        }))
+    }
+
+    /// Signs a challenge using the credential, returning the assertion response.
+    pub fn get(
+        &mut self,
+        ceremony: Ceremony<'_>,
+    ) -> Result<PublicKeyCredentialJson, AuthenticatorError> {
+        self.counter = self.counter.wrapping_add(1);
+
+        let client_data = client_data("webauthn.get", ceremony)?;
+        let authenticator_data = self.authenticator_data(ceremony, FLAGS_UP_UV);
+
+        let mut message = authenticator_data.clone();
+        message.extend(sha256(&client_data));
+        let signature = self.sign(&message)?;
+
+        Ok(self.credential(ResponseJson::Assertion {
+            authenticator_data: Base64UrlUnpadded::encode_string(&authenticator_data),
+            client_data_json: Base64UrlUnpadded::encode_string(&client_data),
+            signature: Base64UrlUnpadded::encode_string(&signature),
+            user_handle: self
+                .user_handle
+                .as_deref()
+                .map(Base64UrlUnpadded::encode_string),
+        }))
+    }
+
+    fn credential(&self, response: ResponseJson) -> PublicKeyCredentialJson {
+        let id = Base64UrlUnpadded::encode_string(&self.credential_id);
+
+        PublicKeyCredentialJson {
+            raw_id: id.clone(),
+            id,
+            authenticator_attachment: "platform",
+            response,
+        }
+    }
+
+    fn authenticator_data(&self, ceremony: Ceremony<'_>, flags: u8) -> Vec<u8> {
+        let mut data = sha256(ceremony.rp_id.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend(self.counter.to_be_bytes());
+        data
+    }
+
+    /// Signs a message, ES256 signatures are DER encoded as WebAuthn requires.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, AuthenticatorError> {
+        let mut signer = match self.algorithm {
+            Algorithm::Es256 => Signer::new(MessageDigest::sha256(), &self.key),
+            Algorithm::EdDsa => Signer::new_without_digest(&self.key),
+        }
+        .map_err(AuthenticatorError::sign)?;
+
+        signer
+            .sign_oneshot_to_vec(message)
+            .map_err(AuthenticatorError::sign)
+    }
+
+    fn cose_public_key(&self) -> Result<Vec<u8>, AuthenticatorError> {
+        let mut key = vec![];
+
+        match self.algorithm {
+            Algorithm::Es256 => {
+                let ec_key = self.key.ec_key().map_err(AuthenticatorError::key)?;
+                let mut context = BigNumContext::new().map_err(AuthenticatorError::key)?;
+                let mut x = BigNum::new().map_err(AuthenticatorError::key)?;
+                let mut y = BigNum::new().map_err(AuthenticatorError::key)?;
+                ec_key
+                    .public_key()
+                    .affine_coordinates(ec_key.group(), &mut x, &mut y, &mut context)
+                    .map_err(AuthenticatorError::key)?;
+
+                // {kty: EC2, alg: ES256, crv: P-256, x, y}
+                cbor::map(&mut key, 5);
+                cbor::int(&mut key, 1);
+                cbor::int(&mut key, 2);
+                cbor::int(&mut key, 3);
+                cbor::int(&mut key, -7);
+                cbor::int(&mut key, -1);
+                cbor::int(&mut key, 1);
+                cbor::int(&mut key, -2);
+                cbor::bytes(
+                    &mut key,
+                    &x.to_vec_padded(32).map_err(AuthenticatorError::key)?,
+                );
+                cbor::int(&mut key, -3);
+                cbor::bytes(
+                    &mut key,
+                    &y.to_vec_padded(32).map_err(AuthenticatorError::key)?,
+                );
+            }
+
+            Algorithm::EdDsa => {
+                let x = self.key.raw_public_key().map_err(AuthenticatorError::key)?;
+
+                // {kty: OKP, alg: EdDSA, crv: Ed25519, x}
+                cbor::map(&mut key, 4);
+                cbor::int(&mut key, 1);
+                cbor::int(&mut key, 1);
+                cbor::int(&mut key, 3);
+                cbor::int(&mut key, -8);
+                cbor::int(&mut key, -1);
+                cbor::int(&mut key, 6);
+                cbor::int(&mut key, -2);
+                cbor::bytes(&mut key, &x);
+            }
+        }
+
+        Ok(key)
+    }
+}
+
+fn client_data(typ: &str, ceremony: Ceremony<'_>) -> Result<Vec<u8>, AuthenticatorError> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ClientData<'a> {
+        r#type: &'a str,
+        challenge: String,
+        origin: &'a str,
+        cross_origin: bool,
+    }
+
+    serde_json::to_vec(&ClientData {
+        r#type: typ,
+        challenge: Base64UrlUnpadded::encode_string(ceremony.challenge),
+        origin: ceremony.origin,
+        cross_origin: false,
+    })
+    .map_err(AuthenticatorError::serialize)
+}
+
+/// The subset of CBOR needed for attestation objects and COSE keys.
+mod cbor {
+    fn header(buffer: &mut Vec<u8>, major_type: u8, value: u64) {
+        let major_type = major_type << 5;
+        if let Ok(value) = u8::try_from(value) {
+            if value < 24 {
+                buffer.push(major_type | value);
+            } else {
+                buffer.extend([major_type | 24, value]);
+            }
+        } else if let Ok(value) = u16::try_from(value) {
+            buffer.push(major_type | 25);
+            buffer.extend(value.to_be_bytes());
+        } else if let Ok(value) = u32::try_from(value) {
+            buffer.push(major_type | 26);
+            buffer.extend(value.to_be_bytes());
+        } else {
+            buffer.push(major_type | 27);
+            buffer.extend(value.to_be_bytes());
+        }
+    }
+
+    pub fn int(buffer: &mut Vec<u8>, value: i64) {
+        match u64::try_from(value) {
+            Ok(value) => header(buffer, 0, value),
+            // Negative integers are encoded as `-1 - n`
+            Err(_) => header(buffer, 1, value.unsigned_abs() - 1),
+        }
+    }
+
+    pub fn bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+        header(buffer, 2, u64::try_from(bytes.len()).unwrap_or(u64::MAX));
+        buffer.extend(bytes);
+    }
+
+    pub fn text(buffer: &mut Vec<u8>, text: &str) {
+        header(buffer, 3, u64::try_from(text.len()).unwrap_or(u64::MAX));
+        buffer.extend(text.as_bytes());
+    }
+
+    pub fn map(buffer: &mut Vec<u8>, pairs: u64) {
+        header(buffer, 5, pairs);
+    }
+}
+
+/// Error variants for performing ceremonies with the software authenticator.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum AuthenticatorError {
+    #[non_exhaustive]
+    Key {
+        source: ErrorStack,
+    },
+
+    #[non_exhaustive]
+    Sign {
+        source: ErrorStack,
+    },
+
+    #[non_exhaustive]
+    Serialize {
+        source: serde_json::Error,
+    },
+
+    #[non_exhaustive]
+    State {
+        source: serde_json::Error,
+    },
+
+    #[non_exhaustive]
+    Io {
+        source: io::Error,
+    },
+
+    #[non_exhaustive]
+    Decode {
+        source: base64ct::Error,
+    },
+
+    CredentialId,
+}
+impl core::fmt::Display for AuthenticatorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Key { .. } => write!(f, "could not process the credential key"),
+            Self::Sign { .. } => write!(f, "could not sign the assertion"),
+            Self::Serialize { .. } => write!(f, "could not serialize the credential response"),
+            Self::State { .. } => write!(f, "could not read or write the authenticator state"),
+            Self::Io { .. } => write!(f, "could not access the authenticator state file"),
+            Self::Decode { .. } => write!(f, "a value is not valid base64url"),
+            Self::CredentialId => write!(f, "the credential ID is too long"),
+        }
+    }
+}
+impl core::error::Error for AuthenticatorError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Key { source, .. } => Some(source),
+            Self::Sign { source, .. } => Some(source),
+            Self::Serialize { source, .. } => Some(source),
+            Self::State { source, .. } => Some(source),
+            Self::Io { source, .. } => Some(source),
+            Self::Decode { source, .. } => Some(source),
+            Self::CredentialId => None,
+        }
+    }
+}
+impl AuthenticatorError {
+    #[allow(missing_docs)]
+    pub fn key(source: ErrorStack) -> Self {
+        Self::Key { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn sign(source: ErrorStack) -> Self {
+        Self::Sign { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn serialize(source: serde_json::Error) -> Self {
+        Self::Serialize { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn state(source: serde_json::Error) -> Self {
+        Self::State { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn io(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn decode(source: base64ct::Error) -> Self {
+        Self::Decode { source }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use base64ct::{Base64UrlUnpadded, Encoding};
+    use openssl::{hash::MessageDigest, pkey::PKey, sha::sha256, sign::Verifier};
+
+    use super::{Algorithm, Ceremony, ResponseJson, SoftwareAuthenticator};
+
+    const CEREMONY: Ceremony<'static> = Ceremony {
+        challenge: b"challenge",
+        rp_id: "localhost",
+        origin: "http://localhost:5500",
+    };
+
+    #[test]
+    fn signs_verifiable_assertions() {
+        for algorithm in [Algorithm::Es256, Algorithm::EdDsa] {
+            let mut authenticator = SoftwareAuthenticator::new(algorithm).unwrap();
+
+            let ResponseJson::Attestation {
+                public_key,
+                public_key_algorithm,
+                ..
+            } = authenticator.create(CEREMONY, None).unwrap().response
+            else {
+                panic!("expected an attestation response");
+            };
+            assert_eq!(algorithm.cose_identifier(), public_key_algorithm);
+            let public_key =
+                PKey::public_key_from_der(&Base64UrlUnpadded::decode_vec(&public_key).unwrap())
+                    .unwrap();
+
+            let ResponseJson::Assertion {
+                authenticator_data,
+                client_data_json,
+                signature,
+                ..
+            } = authenticator.get(CEREMONY).unwrap().response
+            else {
+                panic!("expected an assertion response");
+            };
+
+            let authenticator_data = Base64UrlUnpadded::decode_vec(&authenticator_data).unwrap();
+            let client_data = Base64UrlUnpadded::decode_vec(&client_data_json).unwrap();
+            let mut message = authenticator_data.clone();
+            message.extend(sha256(&client_data));
+
+            let mut verifier = match algorithm {
+                Algorithm::Es256 => Verifier::new(MessageDigest::sha256(), &public_key),
+                Algorithm::EdDsa => Verifier::new_without_digest(&public_key),
+            }
+            .unwrap();
+            let signature = Base64UrlUnpadded::decode_vec(&signature).unwrap();
+            assert!(
+                verifier.verify_oneshot(&signature, &message).unwrap(),
+                "{algorithm:?} signature should verify"
+            );
+
+            assert_eq!(sha256(b"localhost").as_slice(), &authenticator_data[..32]);
+        }
+    }
+
+    #[test]
+    fn increments_counter_on_assertion() {
+        let mut authenticator = SoftwareAuthenticator::new(Algorithm::Es256).unwrap();
+        authenticator.create(CEREMONY, Some(b"user")).unwrap();
+        assert_eq!(0, authenticator.counter());
+
+        for expected in 1..=3u32 {
+            let ResponseJson::Assertion {
+                authenticator_data,
+                user_handle,
+                ..
+            } = authenticator.get(CEREMONY).unwrap().response
+            else {
+                panic!("expected an assertion response");
+            };
+
+            let authenticator_data = Base64UrlUnpadded::decode_vec(&authenticator_data).unwrap();
+            assert_eq!(expected.to_be_bytes(), authenticator_data[33..37]);
+            assert_eq!(expected, authenticator.counter());
+            assert_eq!(Some(Base64UrlUnpadded::encode_string(b"user")), user_handle);
+        }
+    }
+
+    #[test]
+    fn keeps_credential_and_counter_in_state_file() {
+        let state = std::env::temp_dir().join(format!(
+            "identity_service_authenticator_test_{}.json",
+            uuid::Uuid::new_v4().simple()
+        ));
+
+        let mut authenticator = SoftwareAuthenticator::new(Algorithm::EdDsa).unwrap();
+        authenticator.create(CEREMONY, Some(b"user")).unwrap();
+        authenticator.get(CEREMONY).unwrap();
+        authenticator.save(&state, false).unwrap();
+        assert!(authenticator.save(&state, false).is_err());
+
+        let mut loaded = SoftwareAuthenticator::load(&state).unwrap();
+        assert_eq!(authenticator.credential_id(), loaded.credential_id());
+        assert_eq!(1, loaded.counter());
+
+        loaded.get(CEREMONY).unwrap();
+        assert_eq!(2, loaded.counter());
+
+        std::fs::remove_file(state).unwrap();
+    }
+}
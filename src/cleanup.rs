@@ -0,0 +1,271 @@
+use core::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Client;
+use ts_rust_helper::error::ErrorLogger;
+
+/// The tables with expiring rows and the query to delete a batch of expired rows from each.
+///
+/// Identities without an expiry are permanent and are never deleted.
+const RETENTION: [(&str, &str); 7] = [
+    (
+        "identities",
+        r#"
+        DELETE FROM
+            identities
+        WHERE
+            id IN (
+                SELECT id FROM identities
+                WHERE expires IS NOT NULL AND expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+    (
+        "challenges",
+        r#"
+        DELETE FROM
+            challenges
+        WHERE
+            challenge IN (
+                SELECT challenge FROM challenges
+                WHERE expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+    (
+        "revocations",
+        r#"
+        DELETE FROM
+            revocations
+        WHERE
+            token IN (
+                SELECT token FROM revocations
+                WHERE expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+    (
+        "refresh_tokens",
+        r#"
+        DELETE FROM
+            refresh_tokens
+        WHERE
+            token IN (
+                SELECT token FROM refresh_tokens
+                WHERE expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+    (
+        "sessions",
+        r#"
+        DELETE FROM
+            sessions
+        WHERE
+            token IN (
+                SELECT token FROM sessions
+                WHERE expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+    (
+        "authorization_requests",
+        r#"
+        DELETE FROM
+            authorization_requests
+        WHERE
+            id IN (
+                SELECT id FROM authorization_requests
+                WHERE expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+    (
+        "authorization_codes",
+        r#"
+        DELETE FROM
+            authorization_codes
+        WHERE
+            code IN (
+                SELECT code FROM authorization_codes
+                WHERE expires < timezone('utc', NOW())
+                LIMIT $1::INT8
+            );"#,
+    ),
+];
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupConfig {
+    /// How often expired rows are deleted, in seconds.
+    pub interval_seconds: u64,
+
+    /// The most rows deleted by a single statement, keeping each transaction short.
+    pub batch_size: i64,
+}
+
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 60 * 60,
+            batch_size: 1000,
+        }
+    }
+}
+
+impl CleanupConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds.max(1))
+    }
+}
+
+/// Deletes the expired rows from every table, returning the number of rows deleted from each.
+///
+/// A table that fails to clean up is logged and skipped so the other tables are still cleaned.
+pub async fn cleanup(client: &Client, batch_size: i64) -> Vec<(&'static str, u64)> {
+    let batch_size = batch_size.max(1);
+    let full_batch = u64::try_from(batch_size).unwrap_or(u64::MAX);
+    let mut counts = Vec::with_capacity(RETENTION.len());
+
+    for (table, query) in RETENTION {
+        let mut count = 0;
+        loop {
+            let Ok(deleted) = client.execute(query, &[&batch_size]).await.log_error() else {
+                break;
+            };
+            count += deleted;
+
+            if deleted < full_batch {
+                break;
+            }
+        }
+
+        if count > 0 {
+            tracing::info!("cleaned up {count} {}", table.replace('_', " "));
+        }
+        counts.push((table, count));
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use tokio_postgres::Client;
+
+    use super::{RETENTION, cleanup};
+    use crate::tests::TestDatabase;
+
+    const FIXTURES: &str = r#"
+        INSERT INTO identities (id, username, display_name, expires) VALUES
+            ('\x01', 'permanent', 'Permanent', NULL),
+            ('\x02', 'expired', 'Expired', timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('\x03', 'pending', 'Pending', timezone('utc', NOW()) + '1 hour'::INTERVAL);
+
+        INSERT INTO challenges (challenge, identity_id, origin, purpose, expires) VALUES
+            ('\x01', '\x01', 'origin', 'authentication', timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('\x02', '\x01', 'origin', 'authentication', timezone('utc', NOW()) + '1 hour'::INTERVAL);
+
+        INSERT INTO revocations (token, expires) VALUES
+            ('expired', timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('live', timezone('utc', NOW()) + '1 hour'::INTERVAL);
+
+        INSERT INTO refresh_tokens
+            (token, family, identity_id, access_token_id, access_token_expires, expires)
+        VALUES
+            ('\x01', '\x01', '\x01', 'a', timezone('utc', NOW()), timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('\x02', '\x02', '\x01', 'b', timezone('utc', NOW()), timezone('utc', NOW()) + '1 hour'::INTERVAL);
+
+        INSERT INTO sessions (token, identity_id, token_type, expires) VALUES
+            ('expired', '\x01', '{"typ":"common"}', timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('live', '\x01', '{"typ":"common"}', timezone('utc', NOW()) + '1 hour'::INTERVAL);
+
+        INSERT INTO clients (id, display_name, redirect_uris) VALUES
+            ('client', 'Client', ARRAY['https://client.example.com/callback']);
+
+        INSERT INTO authorization_requests (id, client_id, redirect_uri, code_challenge, expires) VALUES
+            ('\x01', 'client', 'https://client.example.com/callback', 'challenge', timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('\x02', 'client', 'https://client.example.com/callback', 'challenge', timezone('utc', NOW()) + '1 hour'::INTERVAL);
+
+        INSERT INTO authorization_codes (code, client_id, identity_id, redirect_uri, code_challenge, expires) VALUES
+            ('\x01', 'client', '\x01', 'https://client.example.com/callback', 'challenge', timezone('utc', NOW()) - '1 hour'::INTERVAL),
+            ('\x02', 'client', '\x01', 'https://client.example.com/callback', 'challenge', timezone('utc', NOW()) + '1 hour'::INTERVAL);
+    "#;
+
+    async fn row_count(client: &Client, table: &str) -> i64 {
+        client
+            .query_one(&format!("SELECT COUNT(*) FROM {table};"), &[])
+            .await
+            .unwrap()
+            .get(0)
+    }
+
+    #[tokio::test]
+    async fn deletes_only_expired_rows_from_every_table() {
+        let database = TestDatabase::new().await;
+        let client = &database.client;
+        client.batch_execute(FIXTURES).await.unwrap();
+
+        let counts = cleanup(client, 1000).await;
+
+        for (table, _) in RETENTION {
+            let deleted = counts.iter().find(|(name, _)| *name == table).unwrap().1;
+            assert_eq!(1, deleted, "{table} should have one expired row deleted");
+
+            let remaining = row_count(client, table).await;
+            let expected = if table == "identities" { 2 } else { 1 };
+            assert_eq!(expected, remaining, "{table} should keep its live rows");
+        }
+
+        // Running again finds nothing more to delete
+        let counts = cleanup(client, 1000).await;
+        assert!(counts.iter().all(|(_, count)| *count == 0));
+
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    async fn keeps_permanent_identities() {
+        let database = TestDatabase::new().await;
+        let client = &database.client;
+        client.batch_execute(FIXTURES).await.unwrap();
+
+        cleanup(client, 1000).await;
+
+        let usernames: Vec<String> = client
+            .query("SELECT username FROM identities ORDER BY username;", &[])
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+        assert_eq!(vec!["pending", "permanent"], usernames);
+
+        database.remove().await;
+    }
+
+    #[tokio::test]
+    async fn deletes_in_batches() {
+        let database = TestDatabase::new().await;
+        let client = &database.client;
+        client
+            .batch_execute(
+                r#"
+                INSERT INTO revocations (token, expires)
+                SELECT
+                    'token-' || n,
+                    timezone('utc', NOW()) - '1 hour'::INTERVAL
+                FROM generate_series(1, 25) AS n;"#,
+            )
+            .await
+            .unwrap();
+
+        let counts = cleanup(client, 10).await;
+
+        assert!(counts.contains(&("revocations", 25)));
+        assert_eq!(0, row_count(client, "revocations").await);
+
+        database.remove().await;
+    }
+}
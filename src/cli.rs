@@ -0,0 +1,29 @@
+use clap::Parser;
+use ts_rust_helper::command::Command;
+
+use crate::{authenticator::AuthenticatorCommand, keys::KeysCommand};
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Enable verbose logging.
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    #[command(subcommand)]
+    pub subcommand: Option<Subcommand>,
+}
+
+#[derive(clap::Subcommand)]
+pub enum Subcommand {
+    #[command(flatten)]
+    Common(Command),
+
+    /// Generate and export token signing keys.
+    #[command(subcommand)]
+    Keys(KeysCommand),
+
+    /// Perform passkey ceremonies with a software authenticator.
+    #[command(subcommand)]
+    Authenticator(AuthenticatorCommand),
+}
@@ -0,0 +1,102 @@
+use core::{convert::Infallible, net::IpAddr};
+use std::sync::Arc;
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts, connect_info::Connected},
+    serve::IncomingStream,
+};
+use http::{HeaderMap, header::USER_AGENT, request::Parts};
+use tokio::net::{TcpListener, UnixListener};
+
+/// The address of the peer a connection was accepted from, Unix socket peers have no address.
+#[derive(Debug, Clone, Copy)]
+pub struct PeerAddress(pub Option<IpAddr>);
+
+impl Connected<IncomingStream<'_, TcpListener>> for PeerAddress {
+    fn connect_info(stream: IncomingStream<'_, TcpListener>) -> Self {
+        Self(Some(stream.remote_addr().ip()))
+    }
+}
+
+impl Connected<IncomingStream<'_, UnixListener>> for PeerAddress {
+    fn connect_info(_: IncomingStream<'_, UnixListener>) -> Self {
+        Self(None)
+    }
+}
+
+/// The addresses of the reverse proxies whose `X-Forwarded-For` header is trusted, added to each
+/// request as an extension.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(pub Arc<[IpAddr]>);
+
+impl TrustedProxies {
+    fn contains(&self, ip_address: &IpAddr) -> bool {
+        self.0.contains(ip_address)
+    }
+}
+
+/// The client that sent a request.
+#[derive(Debug, Clone)]
+pub struct ClientInfo {
+    /// The IP address of the client.
+    ///
+    /// For connections from a trusted proxy, or over the Unix socket which only a reverse proxy on
+    /// the same host can connect to, the address the proxies forwarded in `X-Forwarded-For` is used
+    /// instead.
+    pub ip_address: Option<IpAddr>,
+    pub user_agent: Option<String>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let trusted_proxies = parts
+            .extensions
+            .get::<TrustedProxies>()
+            .cloned()
+            .unwrap_or_default();
+
+        let ip_address = match parts.extensions.get::<ConnectInfo<PeerAddress>>() {
+            Some(ConnectInfo(PeerAddress(Some(ip_address))))
+                if trusted_proxies.contains(ip_address) =>
+            {
+                Some(forwarded_for(&parts.headers, &trusted_proxies).unwrap_or(*ip_address))
+            }
+            Some(ConnectInfo(PeerAddress(Some(ip_address)))) => Some(*ip_address),
+            Some(ConnectInfo(PeerAddress(None))) => forwarded_for(&parts.headers, &trusted_proxies),
+            None => None,
+        };
+
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|user_agent| user_agent.to_str().ok())
+            .map(str::to_string);
+
+        Ok(Self {
+            ip_address,
+            user_agent,
+        })
+    }
+}
+
+/// The address the nearest untrusted hop sent the request from.
+///
+/// Each proxy appends the address it received the request from, so the entries are read from the
+/// last, skipping those added for the trusted proxies. Entries before an untrusted hop may have been
+/// written by the client and are ignored.
+fn forwarded_for(headers: &HeaderMap, trusted_proxies: &TrustedProxies) -> Option<IpAddr> {
+    let mut entries = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .rev()
+        .flat_map(|value| value.to_str().unwrap_or_default().rsplit(','));
+
+    loop {
+        let ip_address: IpAddr = entries.next()?.trim().parse().ok()?;
+        if !trusted_proxies.contains(&ip_address) {
+            return Some(ip_address);
+        }
+    }
+}
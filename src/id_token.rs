@@ -0,0 +1,247 @@
+use base64ct::{Base64UrlUnpadded, Encoding};
+use jiff::{SignedDuration, Timestamp};
+use rand::RngCore;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_api_helper::token::json_web_token::TokenType;
+
+use crate::{jwt::jws_signature, keyring::SigningKey};
+
+type BoxError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+/// How long an ID token is valid for after issuing.
+const ID_TOKEN_LIFETIME: SignedDuration = SignedDuration::from_mins(15);
+
+/// How long an access token issued to an OAuth client is valid for after issuing.
+const CLIENT_TOKEN_LIFETIME: SignedDuration = SignedDuration::from_hours(1);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenIdConfig {
+    /// The issuer identifier, the URL this service is reachable at.
+    pub issuer: String,
+}
+
+impl Default for OpenIdConfig {
+    fn default() -> Self {
+        Self {
+            issuer: "http://localhost:8081".to_string(),
+        }
+    }
+}
+
+impl OpenIdConfig {
+    pub fn id_token_signer(&self) -> IdTokenSigner {
+        IdTokenSigner {
+            issuer: self.issuer.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+/// Issues the tokens given to OAuth clients, signed by the same key as every other token: OpenID
+/// Connect ID tokens, and access tokens restricted to the client they were issued to.
+#[derive(Debug)]
+pub struct IdTokenSigner {
+    issuer: String,
+}
+
+/// The subject specific claims of an ID token.
+#[derive(Debug)]
+pub struct IdToken {
+    pub sub: String,
+    pub aud: String,
+    pub auth_time: Timestamp,
+    pub nonce: Option<String>,
+    pub preferred_username: Option<String>,
+    pub name: Option<String>,
+}
+
+/// An access token for an OAuth client, carrying the client as its audience so first-party routes
+/// reject it.
+#[derive(Debug)]
+pub struct ClientToken {
+    pub sub: String,
+    pub aud: String,
+    pub scope: Option<String>,
+}
+
+/// A signed access token for an OAuth client.
+#[derive(Debug)]
+pub struct IssuedClientToken {
+    pub token: String,
+    pub tid: String,
+    pub typ: TokenType,
+    pub exp: Timestamp,
+}
+
+#[derive(Serialize)]
+struct Header<'a> {
+    alg: &'static str,
+    typ: &'static str,
+    kid: &'a str,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    sub: String,
+    aud: String,
+    exp: i64,
+    iat: i64,
+    auth_time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ClientTokenClaims<'a> {
+    iss: &'a str,
+    sub: String,
+    aud: String,
+    tid: &'a str,
+    exp: i64,
+    iat: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(flatten)]
+    typ: &'a TokenType,
+}
+
+impl IdTokenSigner {
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /// Issues an ID token signed by the keyring's signing key, so it verifies against the published
+    /// key set for as long as the key is published.
+    pub fn issue(
+        &self,
+        signing_key: &SigningKey,
+        id_token: IdToken,
+    ) -> Result<String, IdTokenError> {
+        let now = Timestamp::now();
+
+        let claims = Claims {
+            iss: &self.issuer,
+            sub: id_token.sub,
+            aud: id_token.aud,
+            exp: (now + ID_TOKEN_LIFETIME).as_second(),
+            iat: now.as_second(),
+            auth_time: id_token.auth_time.as_second(),
+            nonce: id_token.nonce,
+            preferred_username: id_token.preferred_username,
+            name: id_token.name,
+        };
+
+        sign(signing_key, &claims)
+    }
+
+    /// Issues an access token for an OAuth client, signed by the keyring's signing key.
+    ///
+    /// The token is a common token with the client as its audience and the granted scope, so it is
+    /// only accepted by the routes serving OAuth clients.
+    pub fn issue_client_token(
+        &self,
+        signing_key: &SigningKey,
+        client_token: ClientToken,
+    ) -> Result<IssuedClientToken, IdTokenError> {
+        let now = Timestamp::now();
+        let exp = now + CLIENT_TOKEN_LIFETIME;
+        let typ = TokenType::Common;
+
+        let mut tid = [0u8; 16];
+        rand::rng().fill_bytes(&mut tid);
+        let tid = Base64UrlUnpadded::encode_string(&tid);
+
+        let claims = ClientTokenClaims {
+            iss: &self.issuer,
+            sub: client_token.sub,
+            aud: client_token.aud,
+            tid: &tid,
+            exp: exp.as_second(),
+            iat: now.as_second(),
+            scope: client_token.scope,
+            typ: &typ,
+        };
+        let token = sign(signing_key, &claims)?;
+
+        Ok(IssuedClientToken {
+            token,
+            tid,
+            typ,
+            exp,
+        })
+    }
+}
+
+/// Signs claims as a JWS in compact serialization.
+fn sign(signing_key: &SigningKey, claims: &impl Serialize) -> Result<String, IdTokenError> {
+    let header = Header {
+        alg: "ES256",
+        typ: "JWT",
+        kid: &signing_key.key_id,
+    };
+
+    let header = serde_json::to_vec(&header).map_err(IdTokenError::serialize)?;
+    let claims = serde_json::to_vec(claims).map_err(IdTokenError::serialize)?;
+    let message = format!(
+        "{}.{}",
+        Base64UrlUnpadded::encode_string(&header),
+        Base64UrlUnpadded::encode_string(&claims)
+    );
+
+    let signature = signing_key
+        .jwk
+        .sign(message.as_bytes())
+        .map_err(|source| IdTokenError::sign(Box::new(source)))?;
+    let signature = jws_signature(signature)
+        .ok_or_else(|| IdTokenError::sign("the signature is not an ES256 signature".into()))?;
+
+    Ok(format!(
+        "{message}.{}",
+        Base64UrlUnpadded::encode_string(&signature)
+    ))
+}
+
+/// Error variants for issuing ID tokens.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum IdTokenError {
+    #[non_exhaustive]
+    Serialize { source: serde_json::Error },
+
+    #[non_exhaustive]
+    Sign { source: BoxError },
+}
+impl core::fmt::Display for IdTokenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Serialize { .. } => write!(f, "could not serialize the ID token"),
+            Self::Sign { .. } => write!(f, "could not sign the ID token"),
+        }
+    }
+}
+impl core::error::Error for IdTokenError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Serialize { source, .. } => Some(source),
+            Self::Sign { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+impl IdTokenError {
+    #[allow(missing_docs)]
+    pub fn serialize(source: serde_json::Error) -> Self {
+        Self::Serialize { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn sign(source: BoxError) -> Self {
+        Self::Sign { source }
+    }
+}
@@ -0,0 +1,133 @@
+use base64ct::{Base64UrlUnpadded, Encoding};
+use jiff::Timestamp;
+use openssl::{
+    bn::BigNum,
+    ec::{EcGroup, EcKey},
+    ecdsa::EcdsaSig,
+    nid::Nid,
+    pkey::Public,
+    sha::sha256,
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use ts_api_helper::token::{json_web_key::JsonWebKeySet, json_web_token::TokenType};
+
+/// The claims of a token issued by this service.
+#[derive(Debug, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub tid: String,
+    #[serde(with = "jiff::fmt::serde::timestamp::second::required")]
+    pub exp: Timestamp,
+    #[serde(flatten)]
+    pub typ: TokenType,
+    /// The OAuth client the token was issued to, tokens for first-party use have no audience.
+    pub aud: Option<String>,
+    /// The scope granted to the OAuth client.
+    pub scope: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    crv: Option<String>,
+    kid: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+/// Verifies the signature of a serialized token against a key set, returning the claims if the
+/// token was signed by one of the keys.
+///
+/// This does not check the expiry or revocation of the token.
+pub fn verify_token<C: DeserializeOwned>(key_set: &JsonWebKeySet, token: &str) -> Option<C> {
+    let (message, signature) = token.rsplit_once('.')?;
+    let (header, claims) = message.split_once('.')?;
+
+    let header: Header =
+        serde_json::from_slice(&Base64UrlUnpadded::decode_vec(header).ok()?).ok()?;
+    if header.alg != "ES256" {
+        return None;
+    }
+
+    let signature = Base64UrlUnpadded::decode_vec(signature).ok()?;
+
+    let key_set: Jwks = serde_json::from_value(serde_json::to_value(key_set).ok()?).ok()?;
+    let verified = key_set
+        .keys
+        .iter()
+        .filter(|key| header.kid.is_none() || key.kid == header.kid)
+        .any(|key| verifies(key, message.as_bytes(), &signature));
+    if !verified {
+        return None;
+    }
+
+    serde_json::from_slice(&Base64UrlUnpadded::decode_vec(claims).ok()?).ok()
+}
+
+/// Whether a published key verifies an ES256 signature of the message, the signature being the raw
+/// `r || s` a JWS carries.
+pub fn key_verifies(key: &serde_json::Value, message: &[u8], signature: &[u8]) -> bool {
+    serde_json::from_value(key.clone()).is_ok_and(|key| verifies(&key, message, signature))
+}
+
+fn verifies(key: &Jwk, message: &[u8], signature: &[u8]) -> bool {
+    // A JWS carries the raw `r || s` of the signature rather than its DER encoding
+    if signature.len() != 64 {
+        return false;
+    }
+    let (r, s) = signature.split_at(32);
+    let signature = BigNum::from_slice(r)
+        .and_then(|r| EcdsaSig::from_private_components(r, BigNum::from_slice(s)?));
+    let Ok(signature) = signature else {
+        return false;
+    };
+
+    p256_public_key(key)
+        .is_some_and(|key| signature.verify(&sha256(message), &key).unwrap_or(false))
+}
+
+/// The raw `r || s` of an ES256 signature as a JWS carries it, converting a DER encoded signature.
+pub fn jws_signature(signature: Vec<u8>) -> Option<Vec<u8>> {
+    let der = EcdsaSig::from_der(&signature)
+        .ok()
+        .filter(|der| der.to_der().is_ok_and(|encoded| encoded == signature));
+    let Some(der) = der else {
+        return (signature.len() == 64).then_some(signature);
+    };
+
+    let mut raw = der.r().to_vec_padded(32).ok()?;
+    raw.extend(der.s().to_vec_padded(32).ok()?);
+
+    Some(raw)
+}
+
+fn p256_public_key(key: &Jwk) -> Option<EcKey<Public>> {
+    if key.kty != "EC" || key.crv.as_deref() != Some("P-256") {
+        return None;
+    }
+
+    let x = Base64UrlUnpadded::decode_vec(key.x.as_deref()?).ok()?;
+    let y = Base64UrlUnpadded::decode_vec(key.y.as_deref()?).ok()?;
+
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).ok()?;
+    let key = EcKey::from_public_key_affine_coordinates(
+        &group,
+        &BigNum::from_slice(&x).ok()?,
+        &BigNum::from_slice(&y).ok()?,
+    )
+    .ok()?;
+    key.check_key().ok()?;
+
+    Some(key)
+}
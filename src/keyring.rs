@@ -0,0 +1,332 @@
+use std::{
+    fs, io,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use jiff::{SignedDuration, Timestamp};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_api_helper::token::{
+    SigningJsonWebKey, config::TokenIssuingConfig, json_web_key::JsonWebKeySet,
+};
+
+use crate::jwt::{jws_signature, key_verifies};
+
+type BoxError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+/// How long verifiers may cache the published key set, new keys should be published at least this
+/// long before they are promoted.
+pub const KEY_SET_MAX_AGE: SignedDuration = SignedDuration::from_hours(1);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKeyConfig {
+    /// The token issuing config for this key.
+    #[serde(flatten)]
+    pub token_issuing_config: TokenIssuingConfig,
+
+    /// When this key is promoted to be the signing key.
+    /// Keys are published before they are promoted so verifiers can cache them ahead of time.
+    #[schemars(with = "String")]
+    pub active_from: Timestamp,
+
+    /// When this key is retired and no longer published, tokens signed by this key will no longer
+    /// verify once retired.
+    #[schemars(with = "Option<String>")]
+    pub retire_at: Option<Timestamp>,
+}
+
+/// The key new tokens are signed with and the `kid` it is published under.
+#[derive(Debug, Clone)]
+pub struct SigningKey {
+    pub jwk: Arc<SigningJsonWebKey>,
+    pub key_id: String,
+}
+
+#[derive(Debug)]
+struct KeyringEntry {
+    signing_jwk: Arc<SigningJsonWebKey>,
+    key_id: String,
+    public_keys: Vec<serde_json::Value>,
+    active_from: Timestamp,
+    retire_at: Option<Timestamp>,
+}
+
+impl KeyringEntry {
+    fn load(config: &SigningKeyConfig) -> Result<Self, KeyringError> {
+        let signing_jwk = config
+            .token_issuing_config
+            .signing_jwk()
+            .map_err(|source| KeyringError::load_key(Box::new(source)))?;
+
+        let key_set = config
+            .token_issuing_config
+            .jwks()
+            .map_err(|source| KeyringError::load_key(Box::new(source)))?;
+        let public_keys = match serde_json::to_value(key_set)
+            .map_err(KeyringError::key_set)?
+            .get_mut("keys")
+            .map(serde_json::Value::take)
+        {
+            Some(serde_json::Value::Array(keys)) => keys,
+            _ => vec![],
+        };
+        let key_id = signing_key_id(&signing_jwk, &public_keys)?;
+
+        Ok(Self {
+            signing_jwk: Arc::new(signing_jwk),
+            key_id,
+            public_keys,
+            active_from: config.active_from,
+            retire_at: config.retire_at,
+        })
+    }
+
+    fn is_retired(&self, now: Timestamp) -> bool {
+        self.retire_at.is_some_and(|retire_at| retire_at <= now)
+    }
+}
+
+/// The set of keys used to sign and verify tokens.
+///
+/// The signing key and published keys are chosen from the schedule on each use, so promoting and
+/// retiring keys does not require a reload.
+#[derive(Debug, Clone)]
+pub struct Keyring {
+    entries: Arc<RwLock<Vec<KeyringEntry>>>,
+}
+
+impl Keyring {
+    pub fn load(configs: &[SigningKeyConfig]) -> Result<Self, KeyringError> {
+        let keyring = Self {
+            entries: Arc::new(RwLock::new(vec![])),
+        };
+        keyring.reload(configs)?;
+
+        Ok(keyring)
+    }
+
+    /// Replaces the keys in the keyring, the existing keys are kept if the new keys are invalid.
+    pub fn reload(&self, configs: &[SigningKeyConfig]) -> Result<(), KeyringError> {
+        let entries = configs
+            .iter()
+            .map(KeyringEntry::load)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if select_signing_entry(&entries, Timestamp::now()).is_none() {
+            return Err(KeyringError::NoActiveKey);
+        }
+
+        *self.entries.write().unwrap() = entries;
+
+        Ok(())
+    }
+
+    /// The key that new tokens are signed with, the most recently promoted key that is not retired.
+    pub fn signing_jwk(&self) -> Option<Arc<SigningJsonWebKey>> {
+        let entries = self.entries.read().unwrap();
+        select_signing_entry(&entries, Timestamp::now()).map(|entry| Arc::clone(&entry.signing_jwk))
+    }
+
+    /// The key that new tokens are signed with and the `kid` it is published under, for signing ID
+    /// tokens.
+    pub fn signing_key(&self) -> Option<SigningKey> {
+        let entries = self.entries.read().unwrap();
+        select_signing_entry(&entries, Timestamp::now()).map(|entry| SigningKey {
+            jwk: Arc::clone(&entry.signing_jwk),
+            key_id: entry.key_id.clone(),
+        })
+    }
+
+    /// The published key set, every key that is not retired including keys awaiting promotion.
+    pub fn key_set(&self) -> JsonWebKeySet {
+        let now = Timestamp::now();
+        let entries = self.entries.read().unwrap();
+
+        let keys: Vec<_> = entries
+            .iter()
+            .filter(|entry| !entry.is_retired(now))
+            .flat_map(|entry| entry.public_keys.iter().cloned())
+            .collect();
+
+        serde_json::from_value(serde_json::json!({ "keys": keys })).unwrap()
+    }
+}
+
+/// The `kid` a signing key config publishes its key under, checking that the key loads.
+pub fn published_key_id(config: &SigningKeyConfig) -> Result<String, KeyringError> {
+    KeyringEntry::load(config).map(|entry| entry.key_id)
+}
+
+/// The `kid` the signing key is published under, the key set may hold other keys in any order.
+///
+/// The published key is found by verifying a signature made with the signing key.
+fn signing_key_id(
+    signing_jwk: &SigningJsonWebKey,
+    public_keys: &[serde_json::Value],
+) -> Result<String, KeyringError> {
+    const MESSAGE: &[u8] = b"signing key probe";
+
+    let signature = signing_jwk
+        .sign(MESSAGE)
+        .map_err(|source| KeyringError::load_key(Box::new(source)))?;
+    let signature = jws_signature(signature)
+        .ok_or_else(|| KeyringError::load_key("the signing key is not an ES256 key".into()))?;
+
+    public_keys
+        .iter()
+        .find(|key| key_verifies(key, MESSAGE, &signature))
+        .and_then(|key| key.get("kid"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .ok_or(KeyringError::MissingKeyId)
+}
+
+/// The `signingKeys` of a config file, the rest of the config is not parsed.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SigningKeysFile {
+    signing_keys: Vec<SigningKeyConfig>,
+}
+
+/// Reads only the `signingKeys` of a config file, for reloading the keyring.
+pub fn read_signing_keys(path: &Path) -> Result<Vec<SigningKeyConfig>, KeyringError> {
+    let contents = fs::read(path).map_err(KeyringError::read_config)?;
+    let file: SigningKeysFile =
+        serde_json::from_slice(&contents).map_err(KeyringError::parse_config)?;
+
+    Ok(file.signing_keys)
+}
+
+fn select_signing_entry(entries: &[KeyringEntry], now: Timestamp) -> Option<&KeyringEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.active_from <= now && !entry.is_retired(now))
+        .max_by_key(|entry| entry.active_from)
+}
+
+/// Error variants for loading the keyring.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum KeyringError {
+    #[non_exhaustive]
+    LoadKey {
+        source: BoxError,
+    },
+
+    #[non_exhaustive]
+    KeySet {
+        source: serde_json::Error,
+    },
+
+    #[non_exhaustive]
+    ReadConfig {
+        source: io::Error,
+    },
+
+    #[non_exhaustive]
+    ParseConfig {
+        source: serde_json::Error,
+    },
+
+    MissingKeyId,
+
+    NoActiveKey,
+}
+impl core::fmt::Display for KeyringError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::LoadKey { .. } => write!(f, "could not load a signing key"),
+            Self::KeySet { .. } => write!(f, "could not read the key set of a signing key"),
+            Self::ReadConfig { .. } => write!(f, "could not read the config file"),
+            Self::ParseConfig { .. } => {
+                write!(f, "could not parse the signing keys of the config file")
+            }
+            Self::MissingKeyId => write!(f, "a signing key's key set does not publish it"),
+            Self::NoActiveKey => write!(f, "no signing key is currently active"),
+        }
+    }
+}
+impl core::error::Error for KeyringError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::LoadKey { source, .. } => Some(source.as_ref()),
+            Self::KeySet { source, .. } => Some(source),
+            Self::ReadConfig { source, .. } => Some(source),
+            Self::ParseConfig { source, .. } => Some(source),
+            Self::MissingKeyId | Self::NoActiveKey => None,
+        }
+    }
+}
+impl KeyringError {
+    #[allow(missing_docs)]
+    pub fn load_key(source: BoxError) -> Self {
+        Self::LoadKey { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn key_set(source: serde_json::Error) -> Self {
+        Self::KeySet { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn read_config(source: io::Error) -> Self {
+        Self::ReadConfig { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn parse_config(source: serde_json::Error) -> Self {
+        Self::ParseConfig { source }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use jiff::Timestamp;
+
+    use super::Keyring;
+    use crate::keys::generate_signing_key;
+
+    #[test]
+    fn finds_the_signing_key_in_its_key_set() {
+        let key_dir = std::env::temp_dir().join(format!(
+            "identity_service_keyring_test_{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let other_dir = key_dir.join("other");
+        fs::create_dir_all(&other_dir).unwrap();
+
+        let (entry, key) = generate_signing_key(
+            &key_dir,
+            Some("signing".to_string()),
+            false,
+            None,
+            Timestamp::UNIX_EPOCH,
+        )
+        .unwrap();
+        let (_, other_key) = generate_signing_key(
+            &other_dir,
+            Some("other".to_string()),
+            false,
+            None,
+            Timestamp::UNIX_EPOCH,
+        )
+        .unwrap();
+
+        // The signing key is not the first key of its key set
+        fs::write(
+            key_dir.join("jwks.json"),
+            serde_json::to_vec(&serde_json::json!({ "keys": [other_key, key] })).unwrap(),
+        )
+        .unwrap();
+
+        let keyring = Keyring::load(&[serde_json::from_value(entry).unwrap()]).unwrap();
+        assert_eq!("signing", keyring.signing_key().unwrap().key_id);
+
+        fs::remove_dir_all(&key_dir).unwrap();
+    }
+}
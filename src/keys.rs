@@ -0,0 +1,473 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use base64ct::{Base64UrlUnpadded, Encoding};
+use jiff::Timestamp;
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::{EcGroup, EcKey},
+    error::ErrorStack,
+    nid::Nid,
+    pkey::{PKey, Public},
+};
+use rand::RngCore;
+use ts_api_helper::token::json_web_key::JsonWebKeySet;
+use ts_rust_helper::config::ConfigFile;
+
+use crate::{
+    config::Config,
+    keyring::{KEY_SET_MAX_AGE, SigningKeyConfig, published_key_id},
+};
+
+type BoxError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum KeysCommand {
+    /// Generate a P-256 keypair for signing tokens and print its JWK.
+    Generate {
+        /// The directory to write `ec-private.pem`, `ec-public.pem` and `jwks.json` to.
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+
+        /// The `kid` of the key, a random ID is used if not provided.
+        #[arg(long)]
+        kid: Option<String>,
+
+        /// A JWKS file to also add the key to, it is created if it does not exist.
+        #[arg(long)]
+        jwks: Option<PathBuf>,
+
+        /// Add the key to `signingKeys` in `config.json`.
+        #[arg(long)]
+        update_config: bool,
+
+        /// When the key is promoted to be the signing key, defaults to once verifiers have refreshed
+        /// their cached key set, an hour from now.
+        #[arg(long)]
+        active_from: Option<Timestamp>,
+
+        /// Overwrite existing key files in the output directory.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print the JWK of a PEM encoded P-256 public key.
+    ExportJwk {
+        /// The path to the PEM encoded public key.
+        public_key: PathBuf,
+
+        /// The `kid` of the key, a random ID is used if not provided.
+        #[arg(long)]
+        kid: Option<String>,
+
+        /// A JWKS file to add the key to, it is created if it does not exist.
+        #[arg(long)]
+        jwks: Option<PathBuf>,
+    },
+}
+
+impl KeysCommand {
+    pub fn execute(self) -> Result<(), KeysError> {
+        match self {
+            Self::Generate {
+                out_dir,
+                kid,
+                jwks,
+                update_config,
+                active_from,
+                force,
+            } => {
+                // Verifiers that cached the key set before the key was published must have refreshed
+                // it before the key signs tokens
+                let active_from = active_from.unwrap_or_else(|| Timestamp::now() + KEY_SET_MAX_AGE);
+
+                let mut config = if update_config {
+                    let contents = fs::read(Config::config_file_path()).map_err(KeysError::io)?;
+                    Some(
+                        serde_json::from_slice::<serde_json::Value>(&contents)
+                            .map_err(KeysError::json)?,
+                    )
+                } else {
+                    None
+                };
+
+                // New keys are configured like the most recent key, so settings such as token
+                // lifetimes carry over
+                let template = config
+                    .as_ref()
+                    .and_then(|config| config.get("signingKeys"))
+                    .and_then(serde_json::Value::as_array)
+                    .and_then(|keys| keys.last())
+                    .cloned();
+
+                let (entry, key) =
+                    generate_signing_key(&out_dir, kid, force, template, active_from)?;
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&key).map_err(KeysError::json)?
+                );
+
+                if let Some(path) = jwks {
+                    add_to_key_set(&path, &key)?;
+                }
+
+                if let Some(config) = &mut config {
+                    add_signing_key(config, entry)?;
+                }
+
+                Ok(())
+            }
+
+            Self::ExportJwk {
+                public_key,
+                kid,
+                jwks,
+            } => {
+                let pem = fs::read(public_key).map_err(KeysError::io)?;
+                let public_key = EcKey::public_key_from_pem(&pem).map_err(KeysError::key)?;
+
+                let key = public_jwk(&public_key, kid.unwrap_or_else(random_kid))?;
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&key).map_err(KeysError::json)?
+                );
+
+                if let Some(path) = jwks {
+                    add_to_key_set(&path, &key)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Generates a P-256 signing key in the directory, returning its `signingKeys` entry and JWK.
+///
+/// The entry is based on the template, or the default signing key config if there is none, with
+/// its key files and key ID pointed at the new key. Existing key files are only replaced if
+/// `force` is set.
+pub fn generate_signing_key(
+    out_dir: &Path,
+    kid: Option<String>,
+    force: bool,
+    template: Option<serde_json::Value>,
+    active_from: Timestamp,
+) -> Result<(serde_json::Value, serde_json::Value), KeysError> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).map_err(KeysError::key)?;
+    let private_key = EcKey::generate(&group).map_err(KeysError::key)?;
+    let public_key =
+        EcKey::from_public_key(&group, private_key.public_key()).map_err(KeysError::key)?;
+
+    let private_pem = PKey::from_ec_key(private_key)
+        .and_then(|key| key.private_key_to_pem_pkcs8())
+        .map_err(KeysError::key)?;
+    let public_pem = public_key.public_key_to_pem().map_err(KeysError::key)?;
+
+    let files = KeyFiles {
+        private_key: out_dir.join("ec-private.pem"),
+        public_key: out_dir.join("ec-public.pem"),
+        key_set: out_dir.join("jwks.json"),
+        kid: kid.unwrap_or_else(random_kid),
+    };
+
+    let key = public_jwk(&public_key, files.kid.clone())?;
+    let key_set = key_set(vec![key.clone()])?;
+
+    write_key_file(&files.private_key, &private_pem, 0o600, force)?;
+    write_key_file(&files.public_key, &public_pem, 0o644, force)?;
+    write_key_file(
+        &files.key_set,
+        serde_json::to_string_pretty(&key_set)
+            .map_err(KeysError::json)?
+            .as_bytes(),
+        0o644,
+        force,
+    )?;
+    eprintln!(
+        "Wrote `{}`, `{}` and `{}`",
+        files.private_key.display(),
+        files.public_key.display(),
+        files.key_set.display()
+    );
+
+    // New keys are configured like the template, with the key files and schedule set for the
+    // new key
+    let mut entry = match template {
+        Some(template) => template,
+        None => {
+            serde_json::to_value(&Config::default().signing_keys[0]).map_err(KeysError::json)?
+        }
+    };
+    let fields = entry.as_object_mut().ok_or(KeysError::InvalidConfig)?;
+    fields.insert(
+        PRIVATE_KEY_FIELD.to_string(),
+        files.private_key.display().to_string().into(),
+    );
+    fields.insert(
+        KEY_SET_FIELD.to_string(),
+        files.key_set.display().to_string().into(),
+    );
+
+    let mut config: SigningKeyConfig = serde_json::from_value(entry).map_err(KeysError::json)?;
+    config.active_from = active_from;
+    config.retire_at = None;
+
+    // The entry must load as the new key, so the config is never left unable to start or signing
+    // with another key
+    let key_id =
+        published_key_id(&config).map_err(|source| KeysError::invalid_key(Box::new(source)))?;
+    if key_id != files.kid {
+        return Err(KeysError::invalid_key(
+            format!("the entry publishes the key `{key_id}` instead").into(),
+        ));
+    }
+
+    let entry = serde_json::to_value(&config).map_err(KeysError::json)?;
+
+    Ok((entry, key))
+}
+
+/// The field of a `signingKeys` entry holding the path of its PEM encoded private key.
+const PRIVATE_KEY_FIELD: &str = "signingJwkFile";
+
+/// The field of a `signingKeys` entry holding the path of its JWKS.
+const KEY_SET_FIELD: &str = "jwksFile";
+
+/// The files a generated key is written to.
+struct KeyFiles {
+    private_key: PathBuf,
+    public_key: PathBuf,
+    key_set: PathBuf,
+    kid: String,
+}
+
+/// Writes a key file with the permissions, refusing to replace an existing file unless forced.
+fn write_key_file(path: &Path, contents: &[u8], mode: u32, force: bool) -> Result<(), KeysError> {
+    let mut options = OpenOptions::new();
+    options.write(true).mode(mode);
+    if force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+
+    let mut file = options.open(path).map_err(|error| {
+        if error.kind() == io::ErrorKind::AlreadyExists {
+            KeysError::AlreadyExists {
+                path: path.to_path_buf(),
+            }
+        } else {
+            KeysError::io(error)
+        }
+    })?;
+
+    // The mode only applies to new files, so a replaced file is restricted too
+    file.set_permissions(fs::Permissions::from_mode(mode))
+        .map_err(KeysError::io)?;
+    file.write_all(contents).map_err(KeysError::io)?;
+
+    Ok(())
+}
+
+/// Replaces a file with a temporary file written beside it, so the service never reads a partially
+/// written file while reloading. An existing file's permissions are kept.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the path is not a file"))?;
+    let temporary = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    let permissions = match fs::metadata(path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+        Err(error) => return Err(error),
+    };
+
+    let write = || {
+        let mut file = File::create(&temporary)?;
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temporary, path)
+    };
+
+    write().inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
+
+/// Adds a key to a JWKS file, replacing any key with the same ID.
+fn add_to_key_set(path: &Path, key: &serde_json::Value) -> Result<(), KeysError> {
+    let mut keys = match fs::read(path) {
+        Ok(contents) => serde_json::from_slice::<serde_json::Value>(&contents)
+            .map_err(KeysError::json)?
+            .get_mut("keys")
+            .map(serde_json::Value::take)
+            .and_then(|keys| match keys {
+                serde_json::Value::Array(keys) => Some(keys),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => vec![],
+        Err(error) => return Err(KeysError::io(error)),
+    };
+
+    let kid = key.get("kid");
+    keys.retain(|existing| existing.get("kid") != kid);
+    keys.push(key.clone());
+
+    let json = serde_json::to_string_pretty(&key_set(keys)?).map_err(KeysError::json)?;
+    write_atomically(path, json.as_bytes()).map_err(KeysError::io)?;
+    eprintln!("Added the key to `{}`", path.display());
+
+    Ok(())
+}
+
+/// Appends a signing key entry to the config, other values in the config are left as is.
+fn add_signing_key(
+    config: &mut serde_json::Value,
+    entry: serde_json::Value,
+) -> Result<(), KeysError> {
+    let config_object = config.as_object_mut().ok_or(KeysError::InvalidConfig)?;
+    let signing_keys = config_object
+        .entry("signingKeys")
+        .or_insert_with(|| serde_json::Value::Array(vec![]))
+        .as_array_mut()
+        .ok_or(KeysError::InvalidConfig)?;
+    signing_keys.push(entry);
+
+    let path = Config::config_file_path();
+    let json = serde_json::to_string_pretty(&config).map_err(KeysError::json)?;
+    write_atomically(&path, json.as_bytes()).map_err(KeysError::io)?;
+    eprintln!("Added the key to `signingKeys` in `{}`", path.display());
+
+    Ok(())
+}
+
+/// A key set holding the keys, checked to be one the service can publish.
+fn key_set(keys: Vec<serde_json::Value>) -> Result<JsonWebKeySet, KeysError> {
+    serde_json::from_value(serde_json::json!({ "keys": keys })).map_err(KeysError::json)
+}
+
+/// The public JWK of a P-256 key.
+fn public_jwk(public_key: &EcKey<Public>, kid: String) -> Result<serde_json::Value, KeysError> {
+    let group = public_key.group();
+    if group.curve_name() != Some(Nid::X9_62_PRIME256V1) {
+        return Err(KeysError::UnsupportedCurve);
+    }
+
+    let mut context = BigNumContext::new().map_err(KeysError::key)?;
+    let mut x = BigNum::new().map_err(KeysError::key)?;
+    let mut y = BigNum::new().map_err(KeysError::key)?;
+    public_key
+        .public_key()
+        .affine_coordinates(group, &mut x, &mut y, &mut context)
+        .map_err(KeysError::key)?;
+
+    Ok(serde_json::json!({
+        "kty": "EC",
+        "crv": "P-256",
+        "x": Base64UrlUnpadded::encode_string(&x.to_vec_padded(32).map_err(KeysError::key)?),
+        "y": Base64UrlUnpadded::encode_string(&y.to_vec_padded(32).map_err(KeysError::key)?),
+        "kid": kid,
+        "use": "sig",
+        "alg": "ES256",
+    }))
+}
+
+fn random_kid() -> String {
+    let mut kid = [0u8; 16];
+    rand::rng().fill_bytes(&mut kid);
+    Base64UrlUnpadded::encode_string(&kid)
+}
+
+/// Error variants for generating and exporting keys.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum KeysError {
+    #[non_exhaustive]
+    Key {
+        source: ErrorStack,
+    },
+
+    #[non_exhaustive]
+    Io {
+        source: io::Error,
+    },
+
+    #[non_exhaustive]
+    Json {
+        source: serde_json::Error,
+    },
+
+    #[non_exhaustive]
+    InvalidKey {
+        source: BoxError,
+    },
+
+    #[non_exhaustive]
+    AlreadyExists {
+        path: PathBuf,
+    },
+
+    InvalidConfig,
+
+    UnsupportedCurve,
+}
+impl core::fmt::Display for KeysError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Key { .. } => write!(f, "could not process the key"),
+            Self::Io { .. } => write!(f, "could not read or write a file"),
+            Self::Json { .. } => write!(f, "could not process the JSON"),
+            Self::InvalidKey { .. } => write!(f, "the generated key could not be loaded"),
+            Self::AlreadyExists { path } => write!(
+                f,
+                "`{}` already exists, pass `--force` to overwrite it",
+                path.display()
+            ),
+            Self::InvalidConfig => write!(f, "the config or its `signingKeys` is not valid"),
+            Self::UnsupportedCurve => write!(f, "only P-256 keys are supported"),
+        }
+    }
+}
+impl core::error::Error for KeysError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Key { source, .. } => Some(source),
+            Self::Io { source, .. } => Some(source),
+            Self::Json { source, .. } => Some(source),
+            Self::InvalidKey { source, .. } => Some(source.as_ref()),
+            Self::AlreadyExists { .. } | Self::InvalidConfig | Self::UnsupportedCurve => None,
+        }
+    }
+}
+impl KeysError {
+    #[allow(missing_docs)]
+    pub fn key(source: ErrorStack) -> Self {
+        Self::Key { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn io(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn json(source: serde_json::Error) -> Self {
+        Self::Json { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn invalid_key(source: BoxError) -> Self {
+        Self::InvalidKey { source }
+    }
+}
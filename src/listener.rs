@@ -0,0 +1,464 @@
+use core::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+use std::{
+    fs, io,
+    os::unix::fs::FileTypeExt,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+use axum::{
+    Router,
+    extract::connect_info::Connected,
+    serve::{IncomingStream, Listener},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    net::{TcpListener, TcpStream, UnixListener},
+    sync::mpsc,
+    task::{JoinError, JoinHandle, JoinSet},
+};
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        self, ServerConfig,
+        crypto::{CryptoProvider, ring},
+        pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+        server::{ClientHello, ResolvesServerCert},
+        sign::CertifiedKey,
+    },
+    server::TlsStream,
+};
+use ts_rust_helper::error::ErrorLogger;
+
+use crate::{client_info::PeerAddress, shutdown::Shutdown};
+
+/// How long a client has to complete the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the certificate files are checked for changes.
+const CERTIFICATE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenConfig {
+    /// The TCP addresses to listen on.
+    #[schemars(with = "Vec<String>")]
+    pub addresses: Vec<SocketAddr>,
+
+    /// The path of a Unix domain socket to listen on, a socket left at this path is replaced.
+    /// The socket never uses TLS.
+    pub unix_socket: Option<PathBuf>,
+
+    /// Terminates TLS on the TCP addresses when set.
+    pub tls: Option<TlsConfig>,
+
+    /// How long in-flight requests have to finish after a shutdown signal, in seconds.
+    pub drain_timeout_seconds: u64,
+
+    /// The addresses of reverse proxies that connect over TCP.
+    /// For their connections the client address is taken from `X-Forwarded-For`.
+    #[schemars(with = "Vec<String>")]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl Default for ListenConfig {
+    fn default() -> Self {
+        Self {
+            addresses: vec![SocketAddr::from(([0, 0, 0, 0], 8081))],
+            unix_socket: None,
+            tls: None,
+            drain_timeout_seconds: 30,
+            trusted_proxies: vec![],
+        }
+    }
+}
+
+impl ListenConfig {
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.drain_timeout_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsConfig {
+    /// The path to the PEM encoded certificate chain.
+    pub certificate: PathBuf,
+
+    /// The path to the PEM encoded private key.
+    /// The certificate and key are reloaded when either file changes.
+    pub private_key: PathBuf,
+}
+
+/// Serves the app on every configured listener until one of them fails or shutdown is requested.
+///
+/// On shutdown the listeners stop accepting connections and in-flight requests are given the
+/// drain timeout to finish before their connections are dropped.
+pub async fn serve(
+    config: &ListenConfig,
+    app: Router,
+    shutdown: &Shutdown,
+) -> Result<(), ListenerError> {
+    let (acceptor, certificate_reload) = match &config.tls {
+        Some(tls_config) => {
+            let (acceptor, certificate_reload) = tls_acceptor(tls_config, shutdown)?;
+            (Some(acceptor), Some(certificate_reload))
+        }
+        None => (None, None),
+    };
+
+    let mut servers = JoinSet::new();
+
+    for address in &config.addresses {
+        let listener = TcpListener::bind(address)
+            .await
+            .map_err(|source| ListenerError::bind(address.to_string(), source))?;
+
+        match &acceptor {
+            Some(acceptor) => {
+                tracing::info!("listening on https://{address}");
+                let listener = TlsListener::new(listener, acceptor.clone(), shutdown)
+                    .map_err(|source| ListenerError::bind(address.to_string(), source))?;
+                servers.spawn(
+                    axum::serve(
+                        listener,
+                        app.clone()
+                            .into_make_service_with_connect_info::<PeerAddress>(),
+                    )
+                    .with_graceful_shutdown(shutdown.requested_owned())
+                    .into_future(),
+                );
+            }
+            None => {
+                tracing::info!("listening on http://{address}");
+                servers.spawn(
+                    axum::serve(
+                        listener,
+                        app.clone()
+                            .into_make_service_with_connect_info::<PeerAddress>(),
+                    )
+                    .with_graceful_shutdown(shutdown.requested_owned())
+                    .into_future(),
+                );
+            }
+        }
+    }
+
+    if let Some(path) = &config.unix_socket {
+        // A socket left by a previous run would prevent binding, any other file is left alone and
+        // fails the bind
+        match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path)
+                .map_err(|source| ListenerError::bind(path.display().to_string(), source))?,
+            Ok(_) => {
+                return Err(ListenerError::bind(
+                    path.display().to_string(),
+                    io::Error::new(io::ErrorKind::AlreadyExists, "the path is not a socket"),
+                ));
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(ListenerError::bind(path.display().to_string(), error)),
+        }
+
+        let listener = UnixListener::bind(path)
+            .map_err(|source| ListenerError::bind(path.display().to_string(), source))?;
+        tracing::info!("listening on unix:{}", path.display());
+        servers.spawn(
+            axum::serve(
+                listener,
+                app.clone()
+                    .into_make_service_with_connect_info::<PeerAddress>(),
+            )
+            .with_graceful_shutdown(shutdown.requested_owned())
+            .into_future(),
+        );
+    }
+
+    let drain_deadline = async {
+        shutdown.requested().await;
+        tokio::time::sleep(config.drain_timeout()).await;
+    };
+
+    // Dropping the servers aborts any connections that are still open
+    let result = tokio::select! {
+        result = join(&mut servers) => result,
+        () = drain_deadline => {
+            tracing::warn!(
+                "requests were still in flight after {}s, dropping their connections",
+                config.drain_timeout_seconds
+            );
+            Ok(())
+        }
+    };
+
+    // The reload task stops once shutdown is requested, if a listener failed instead it is stopped
+    // here
+    if let Some(certificate_reload) = certificate_reload {
+        if result.is_err() {
+            certificate_reload.abort();
+        }
+        match certificate_reload.await {
+            Ok(()) => {}
+            Err(error) if error.is_cancelled() => {}
+            Err(error) => return result.and(Err(ListenerError::task(error))),
+        }
+    }
+
+    result
+}
+
+async fn join(servers: &mut JoinSet<io::Result<()>>) -> Result<(), ListenerError> {
+    while let Some(result) = servers.join_next().await {
+        result
+            .map_err(ListenerError::task)?
+            .map_err(ListenerError::serve)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the TLS acceptor and the task that reloads its certificate when the files change, which
+/// runs until shutdown is requested.
+fn tls_acceptor(
+    config: &TlsConfig,
+    shutdown: &Shutdown,
+) -> Result<(TlsAcceptor, JoinHandle<()>), ListenerError> {
+    let provider = Arc::new(ring::default_provider());
+
+    let resolver = Arc::new(CertificateResolver {
+        certified_key: RwLock::new(load_certified_key(config, &provider)?),
+    });
+
+    let mut server_config = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(ListenerError::tls)?
+        .with_no_client_auth()
+        .with_cert_resolver(Arc::clone(&resolver));
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    // Repeating task to pick up renewed certificates
+    let certificate_reload = {
+        let config = config.clone();
+        let resolver = Arc::clone(&resolver);
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            let mut last_modified = modified(&config);
+
+            let mut interval = tokio::time::interval(CERTIFICATE_POLL_INTERVAL);
+            interval.tick().await;
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    () = shutdown.requested() => break,
+                }
+
+                let modified = modified(&config);
+                if modified == last_modified {
+                    continue;
+                }
+
+                let Ok(certified_key) = load_certified_key(&config, &provider).log_error() else {
+                    continue;
+                };
+                *resolver.certified_key.write().unwrap() = certified_key;
+                last_modified = modified;
+                tracing::info!("reloaded TLS certificate");
+            }
+        })
+    };
+
+    Ok((
+        TlsAcceptor::from(Arc::new(server_config)),
+        certificate_reload,
+    ))
+}
+
+fn modified(config: &TlsConfig) -> [Option<SystemTime>; 2] {
+    let modified = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
+    [modified(&config.certificate), modified(&config.private_key)]
+}
+
+fn load_certified_key(
+    config: &TlsConfig,
+    provider: &CryptoProvider,
+) -> Result<Arc<CertifiedKey>, ListenerError> {
+    let certificates = CertificateDer::pem_file_iter(&config.certificate)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(ListenerError::read_pem)?;
+    let private_key =
+        PrivateKeyDer::from_pem_file(&config.private_key).map_err(ListenerError::read_pem)?;
+
+    let signing_key = provider
+        .key_provider
+        .load_private_key(private_key)
+        .map_err(ListenerError::tls)?;
+
+    Ok(Arc::new(CertifiedKey::new(certificates, signing_key)))
+}
+
+/// Resolves the current certificate, which is replaced when the files change.
+#[derive(Debug)]
+struct CertificateResolver {
+    certified_key: RwLock<Arc<CertifiedKey>>,
+}
+
+impl ResolvesServerCert for CertificateResolver {
+    fn resolve(&self, _: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(Arc::clone(&self.certified_key.read().unwrap()))
+    }
+}
+
+/// A TCP listener that yields connections once their TLS handshake completes.
+///
+/// Handshakes run in their own tasks so a slow client cannot hold up other connections.
+struct TlsListener {
+    connections: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+    local_addr: SocketAddr,
+}
+
+impl TlsListener {
+    fn new(
+        mut listener: TcpListener,
+        acceptor: TlsAcceptor,
+        shutdown: &Shutdown,
+    ) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (sender, connections) = mpsc::channel(64);
+        let shutdown = shutdown.clone();
+
+        tokio::spawn(async move {
+            loop {
+                // Stop accepting as soon as shutdown is requested or the server stops, rather than
+                // after the next connection arrives
+                let (stream, address) = tokio::select! {
+                    connection = Listener::accept(&mut listener) => connection,
+                    () = shutdown.requested() => break,
+                    () = sender.closed() => break,
+                };
+
+                let acceptor = acceptor.clone();
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(stream)) => {
+                            let _ = sender.send((stream, address)).await;
+                        }
+                        Ok(Err(error)) => {
+                            tracing::debug!("TLS handshake with {address} failed: {error}");
+                        }
+                        Err(_) => tracing::debug!("TLS handshake with {address} timed out"),
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            connections,
+            local_addr,
+        })
+    }
+}
+
+impl Connected<IncomingStream<'_, TlsListener>> for PeerAddress {
+    fn connect_info(stream: IncomingStream<'_, TlsListener>) -> Self {
+        Self(Some(stream.remote_addr().ip()))
+    }
+}
+
+impl Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.connections.recv().await {
+            Some(connection) => connection,
+            // The accept task only stops once this listener is dropped
+            None => core::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// Error variants for listening for and serving connections.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum ListenerError {
+    #[non_exhaustive]
+    Bind { address: String, source: io::Error },
+
+    #[non_exhaustive]
+    ReadPem {
+        source: rustls::pki_types::pem::Error,
+    },
+
+    #[non_exhaustive]
+    Tls { source: rustls::Error },
+
+    #[non_exhaustive]
+    Serve { source: io::Error },
+
+    #[non_exhaustive]
+    Task { source: JoinError },
+}
+impl core::fmt::Display for ListenerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Bind { address, .. } => write!(f, "could not listen on `{address}`"),
+            Self::ReadPem { .. } => write!(f, "could not read the TLS certificate or key"),
+            Self::Tls { .. } => write!(f, "could not configure TLS"),
+            Self::Serve { .. } => write!(f, "could not serve connections"),
+            Self::Task { .. } => write!(f, "a listener stopped unexpectedly"),
+        }
+    }
+}
+impl core::error::Error for ListenerError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Bind { source, .. } => Some(source),
+            Self::ReadPem { source, .. } => Some(source),
+            Self::Tls { source, .. } => Some(source),
+            Self::Serve { source, .. } => Some(source),
+            Self::Task { source, .. } => Some(source),
+        }
+    }
+}
+impl ListenerError {
+    #[allow(missing_docs)]
+    pub fn bind(address: String, source: io::Error) -> Self {
+        Self::Bind { address, source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn read_pem(source: rustls::pki_types::pem::Error) -> Self {
+        Self::ReadPem { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn tls(source: rustls::Error) -> Self {
+        Self::Tls { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn serve(source: io::Error) -> Self {
+        Self::Serve { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn task(source: JoinError) -> Self {
+        Self::Task { source }
+    }
+}
@@ -1,30 +1,49 @@
 //! Personal identity provider and authorisation server.
 
 use core::{str::FromStr, time::Duration};
-use std::sync::Arc;
+use std::{fs, sync::Arc};
 
-use axum::Router;
 use http::{HeaderName, Uri};
 use tokio::task;
-use tokio_postgres::{Client, GenericClient};
 use tracing::Level;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use ts_api_helper::cors_layer;
 use ts_rust_helper::{
-    command::{Cli, Command},
-    config::try_load_config,
+    command::Command,
+    config::{ConfigFile, try_load_config},
     error::{ErrorLogger, ReportProgramExit},
 };
-use ts_sql_helper_lib::perform_migrations_async;
 
-use crate::config::Config;
+use crate::{
+    cleanup::cleanup,
+    cli::{Cli, Subcommand},
+    config::Config,
+    keyring::read_signing_keys,
+    shutdown::Shutdown,
+};
 
 pub use crate::state::ApiState;
 
+mod access_token;
+mod authenticator;
+mod cleanup;
+mod cli;
+mod client_info;
 mod config;
+mod id_token;
+mod jwt;
+mod keyring;
+mod keys;
+mod listener;
+mod metrics;
 mod models;
+mod rate_limit;
 mod routes;
+mod shutdown;
 mod state;
+mod telemetry;
+#[cfg(test)]
+mod tests;
+mod username_protection;
 
 #[tokio::main]
 async fn main() -> ReportProgramExit {
@@ -36,16 +55,11 @@ async fn main() -> ReportProgramExit {
         Level::INFO
     };
 
-    let filter = tracing_subscriber::filter::LevelFilter::from_level(level);
-
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
-        .with(filter)
-        .init();
-
     if let Some(subcommand) = cli.subcommand {
+        let _telemetry = telemetry::init(level, None)?;
+
         match subcommand {
-            Command::Config(config_subcommand) => {
+            Subcommand::Common(Command::Config(config_subcommand)) => {
                 config_subcommand.execute::<Config>()?;
 
                 eprintln!(
@@ -55,56 +69,93 @@ async fn main() -> ReportProgramExit {
 
                 return Ok(());
             }
+
+            Subcommand::Keys(keys_subcommand) => {
+                keys_subcommand.execute()?;
+
+                return Ok(());
+            }
+
+            Subcommand::Authenticator(authenticator_subcommand) => {
+                authenticator_subcommand.execute()?;
+
+                return Ok(());
+            }
         }
     }
 
     let config: Config = try_load_config()?;
 
+    let _telemetry = telemetry::init(level, Some(&config.telemetry_config))?;
+
     // Setup database pool
     let pool = config.database_pool().await?;
 
+    let state = ApiState::new(&config, pool.clone())?;
+
     // Migrate database
-    {
-        let connection = pool.get().await?;
-        perform_migrations_async(connection.client(), None).await?;
-    }
+    state.migrate().await?;
 
-    let state = {
-        let jwks_file = config.token_issuing_config.jwks()?;
-        let signing_jwk = Arc::new(config.token_issuing_config.signing_jwk()?);
-        let jwks_cache = config.token_validating_config.jwks_cache();
-        let api_key_config = config.api_key_validation_config.clone();
-        let http_client = config.http_client_config.http_client()?;
-        let revocation_endpoint = config.token_validating_config.revocation_endpoint;
-        let relying_party = config.relying_party;
-
-        ApiState {
-            pool: pool.clone(),
-            jwks_file,
-            signing_jwk,
-            jwks_cache,
-            api_key_config,
-            http_client,
-            revocation_endpoint,
-            relying_party,
-        }
-    };
+    let shutdown = Shutdown::on_signal();
 
     // Repeating task to remove expired items
-    let _cleanup_task = {
+    let cleanup_task = {
         let pool = pool.clone();
+        let metrics = Arc::clone(&state.metrics);
+        let batch_size = config.cleanup_config.batch_size;
+        let mut interval = tokio::time::interval(config.cleanup_config.interval());
+        let shutdown = shutdown.clone();
         task::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    () = shutdown.requested() => break,
+                }
+
+                let Ok(connection) = pool.get().await.log_error() else {
+                    continue;
+                };
+                let counts = cleanup(&connection, batch_size).await;
+                metrics.record_cleanup(&counts);
+            }
+        })
+    };
+
+    // Repeating task to pick up changes to the signing keys, only the `signingKeys` of the config
+    // file are read and only once the file has changed
+    let keyring_reload_task = {
+        let keyring = state.keyring.clone();
+        let shutdown = shutdown.clone();
+        task::spawn(async move {
+            let path = Config::config_file_path();
+            let modified = || {
+                fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            };
+            let mut last_modified = modified();
+
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            interval.tick().await;
 
             loop {
-                {
-                    let Ok(connection) = pool.get().await.log_error() else {
-                        interval.tick().await;
-                        continue;
-                    };
-                    cleanup(&connection).await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    () = shutdown.requested() => break,
+                }
+
+                let modified = modified();
+                if modified == last_modified {
+                    continue;
+                }
+
+                let Ok(signing_keys) = read_signing_keys(&path).log_error() else {
+                    continue;
+                };
+                if keyring.reload(&signing_keys).log_error().is_ok() {
+                    last_modified = modified;
+                    tracing::info!("reloaded signing keys");
                 }
-                interval.tick().await;
             }
         })
     };
@@ -123,65 +174,17 @@ async fn main() -> ReportProgramExit {
         &[],
     );
 
-    #[rustfmt::skip]
-    let app = Router::new()
-        .merge(routes::challenges::router(state.clone()))
-        .merge(routes::well_known::router(state.clone()))
-        .merge(routes::existing_credentials::router(state.clone()))
-        .merge(routes::identities::router(state.clone()))
-        .merge(routes::revoked_tokens::router(state.clone()))
-        .merge(routes::tokens::router(state.clone()))
-        .merge(routes::public_keys::router(state.clone()))
-        .layer(cors);
-
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8081").await.unwrap();
-    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    let app = routes::router(state).layer(cors);
 
-    axum::serve(listener, app).await?;
+    listener::serve(&config.listen_config, app, &shutdown).await?;
 
-    Ok(())
-}
-
-async fn cleanup(client: &Client) {
-    let Ok(count) = client
-        .execute(
-            "DELETE FROM identities WHERE expires > timezone('utc', NOW());",
-            &[],
-        )
-        .await
-        .log_error()
-    else {
-        return;
-    };
-    if count > 0 {
-        tracing::info!("cleaned up {count} identities");
-    }
+    // A cleanup run in progress is left to finish so its batches are not cut off
+    cleanup_task.await.log_error();
+    keyring_reload_task.await.log_error();
 
-    let Ok(count) = client
-        .execute(
-            "DELETE FROM challenges WHERE expires > timezone('utc', NOW());",
-            &[],
-        )
-        .await
-        .log_error()
-    else {
-        return;
-    };
-    if count > 0 {
-        tracing::info!("cleaned up {count} challenges");
-    }
+    // The server and background tasks have released their handles, so this closes the connections
+    drop(pool);
+    tracing::info!("shut down");
 
-    let Ok(count) = client
-        .execute(
-            "DELETE FROM revocations WHERE expires > timezone('utc', NOW());",
-            &[],
-        )
-        .await
-        .log_error()
-    else {
-        return;
-    };
-    if count > 0 {
-        tracing::info!("cleaned up {count} revocations");
-    }
+    Ok(())
 }
@@ -0,0 +1,347 @@
+use core::time::Duration;
+use std::sync::Mutex;
+
+use http::StatusCode;
+use prometheus::{
+    Counter, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder, core::Collector,
+};
+use ts_api_helper::{
+    ConnectionPool, token::json_web_token::TokenType, webauthn::verification::VerificationResult,
+};
+
+/// The WebAuthN ceremony a credential was verified for.
+#[derive(Debug, Clone, Copy)]
+pub enum Ceremony {
+    Registration,
+    Login,
+    Consent,
+}
+
+impl Ceremony {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Registration => "registration",
+            Self::Login => "login",
+            Self::Consent => "consent",
+        }
+    }
+}
+
+/// The revocation that was recorded.
+#[derive(Debug, Clone, Copy)]
+pub enum Revocation {
+    Token,
+    RefreshTokenFamily,
+}
+
+impl Revocation {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Token => "token",
+            Self::RefreshTokenFamily => "refresh_token_family",
+        }
+    }
+}
+
+/// The Prometheus metrics of the service, exposed at `/metrics`.
+#[derive(Debug)]
+pub struct Metrics {
+    registry: Registry,
+    pool: ConnectionPool,
+    /// Held while the pool statistics are copied in and the registry is gathered, so concurrent
+    /// scrapes do not see each other's partial updates.
+    scrape: Mutex<()>,
+
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    verifications: IntCounterVec,
+    tokens_issued: IntCounterVec,
+    revocations: IntCounterVec,
+    cleanup_deleted_rows: IntCounterVec,
+
+    pool_connections: IntGauge,
+    pool_idle_connections: IntGauge,
+    pool_gets: IntCounterVec,
+    pool_get_wait_seconds: Counter,
+    pool_connections_created: IntCounter,
+    pool_connections_closed: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new(pool: ConnectionPool) -> Result<Self, MetricsError> {
+        let registry = Registry::new_custom(Some("identity_service".to_string()), None)
+            .map_err(MetricsError::register)?;
+
+        let metrics = Self {
+            http_requests: IntCounterVec::new(
+                Opts::new("http_requests_total", "HTTP requests handled"),
+                &["method", "route", "status"],
+            )
+            .map_err(MetricsError::register)?,
+            http_request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "Time taken to handle HTTP requests",
+                ),
+                &["method", "route"],
+            )
+            .map_err(MetricsError::register)?,
+            verifications: IntCounterVec::new(
+                Opts::new(
+                    "webauthn_verifications_total",
+                    "WebAuthN credential verifications by ceremony and result",
+                ),
+                &["ceremony", "result"],
+            )
+            .map_err(MetricsError::register)?,
+            tokens_issued: IntCounterVec::new(
+                Opts::new("tokens_issued_total", "Tokens issued by type"),
+                &["typ"],
+            )
+            .map_err(MetricsError::register)?,
+            revocations: IntCounterVec::new(
+                Opts::new(
+                    "revocations_total",
+                    "Tokens and refresh token families revoked",
+                ),
+                &["kind"],
+            )
+            .map_err(MetricsError::register)?,
+            cleanup_deleted_rows: IntCounterVec::new(
+                Opts::new(
+                    "cleanup_deleted_rows_total",
+                    "Expired rows deleted by the cleanup task",
+                ),
+                &["table"],
+            )
+            .map_err(MetricsError::register)?,
+            pool_connections: IntGauge::new(
+                "pool_connections",
+                "Open database connections, idle or in use",
+            )
+            .map_err(MetricsError::register)?,
+            pool_idle_connections: IntGauge::new(
+                "pool_idle_connections",
+                "Idle database connections",
+            )
+            .map_err(MetricsError::register)?,
+            pool_gets: IntCounterVec::new(
+                Opts::new(
+                    "pool_gets_total",
+                    "Connections requested from the pool by whether they were available, had to \
+                     be waited for, or timed out",
+                ),
+                &["result"],
+            )
+            .map_err(MetricsError::register)?,
+            pool_get_wait_seconds: Counter::new(
+                "pool_get_wait_seconds_total",
+                "Time spent waiting for connections from the pool",
+            )
+            .map_err(MetricsError::register)?,
+            pool_connections_created: IntCounter::new(
+                "pool_connections_created_total",
+                "Database connections opened",
+            )
+            .map_err(MetricsError::register)?,
+            pool_connections_closed: IntCounterVec::new(
+                Opts::new(
+                    "pool_connections_closed_total",
+                    "Database connections closed by reason",
+                ),
+                &["reason"],
+            )
+            .map_err(MetricsError::register)?,
+            registry,
+            pool,
+            scrape: Mutex::new(()),
+        };
+
+        let collectors: [Box<dyn Collector>; 12] = [
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.http_request_duration.clone()),
+            Box::new(metrics.verifications.clone()),
+            Box::new(metrics.tokens_issued.clone()),
+            Box::new(metrics.revocations.clone()),
+            Box::new(metrics.cleanup_deleted_rows.clone()),
+            Box::new(metrics.pool_connections.clone()),
+            Box::new(metrics.pool_idle_connections.clone()),
+            Box::new(metrics.pool_gets.clone()),
+            Box::new(metrics.pool_get_wait_seconds.clone()),
+            Box::new(metrics.pool_connections_created.clone()),
+            Box::new(metrics.pool_connections_closed.clone()),
+        ];
+        for collector in collectors {
+            metrics
+                .registry
+                .register(collector)
+                .map_err(MetricsError::register)?;
+        }
+
+        Ok(metrics)
+    }
+
+    /// Encodes every metric in the Prometheus text format.
+    pub fn encode(&self) -> Result<String, MetricsError> {
+        let _scrape = self.scrape.lock().unwrap();
+
+        // The pool keeps its own statistics, so they are copied in on each scrape
+        let state = self.pool.state();
+        let statistics = &state.statistics;
+        self.pool_connections.set(state.connections.into());
+        self.pool_idle_connections
+            .set(state.idle_connections.into());
+
+        for (counter, value) in [
+            (
+                self.pool_gets.with_label_values(&["direct"]),
+                statistics.get_direct,
+            ),
+            (
+                self.pool_gets.with_label_values(&["waited"]),
+                statistics.get_waited,
+            ),
+            (
+                self.pool_gets.with_label_values(&["timed_out"]),
+                statistics.get_timed_out,
+            ),
+            (
+                self.pool_connections_created.clone(),
+                statistics.connections_created,
+            ),
+            (
+                self.pool_connections_closed.with_label_values(&["broken"]),
+                statistics.connections_closed_broken,
+            ),
+            (
+                self.pool_connections_closed.with_label_values(&["invalid"]),
+                statistics.connections_closed_invalid,
+            ),
+            (
+                self.pool_connections_closed
+                    .with_label_values(&["max_lifetime"]),
+                statistics.connections_closed_max_lifetime,
+            ),
+            (
+                self.pool_connections_closed
+                    .with_label_values(&["idle_timeout"]),
+                statistics.connections_closed_idle_timeout,
+            ),
+        ] {
+            counter.reset();
+            counter.inc_by(value);
+        }
+        self.pool_get_wait_seconds.reset();
+        self.pool_get_wait_seconds
+            .inc_by(statistics.get_wait_time.as_secs_f64());
+
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .map_err(MetricsError::encode)
+    }
+
+    pub fn record_request(
+        &self,
+        method: &str,
+        route: &str,
+        status: StatusCode,
+        duration: Duration,
+    ) {
+        self.http_requests
+            .with_label_values(&[method, route, status.as_str()])
+            .inc();
+        self.http_request_duration
+            .with_label_values(&[method, route])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Records the outcome of `credential.verify`, where an error means the credential could not be
+    /// checked at all.
+    pub fn record_verification<E>(
+        &self,
+        ceremony: Ceremony,
+        result: Result<&VerificationResult, &E>,
+    ) {
+        let result = match result {
+            Ok(VerificationResult::Valid { .. }) => "valid",
+            Ok(_) => "invalid",
+            Err(_) => "error",
+        };
+
+        self.verifications
+            .with_label_values(&[ceremony.label(), result])
+            .inc();
+    }
+
+    pub fn record_token_issued(&self, typ: &TokenType) {
+        // The `typ` claim without any data carried by the type, such as the consent action
+        let typ = serde_json::to_value(typ)
+            .ok()
+            .and_then(|value| value.get("typ")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        self.tokens_issued.with_label_values(&[typ.as_str()]).inc();
+    }
+
+    pub fn record_id_token_issued(&self) {
+        self.tokens_issued.with_label_values(&["id"]).inc();
+    }
+
+    pub fn record_revocation(&self, revocation: Revocation) {
+        self.record_revocations(revocation, 1);
+    }
+
+    pub fn record_revocations(&self, revocation: Revocation, count: u64) {
+        self.revocations
+            .with_label_values(&[revocation.label()])
+            .inc_by(count);
+    }
+
+    pub fn record_cleanup(&self, counts: &[(&str, u64)]) {
+        for (table, count) in counts {
+            self.cleanup_deleted_rows
+                .with_label_values(&[*table])
+                .inc_by(*count);
+        }
+    }
+}
+
+/// Error variants for the Prometheus metrics.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum MetricsError {
+    #[non_exhaustive]
+    Register { source: prometheus::Error },
+
+    #[non_exhaustive]
+    Encode { source: prometheus::Error },
+}
+impl core::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Register { .. } => write!(f, "could not register the metrics"),
+            Self::Encode { .. } => write!(f, "could not encode the metrics"),
+        }
+    }
+}
+impl core::error::Error for MetricsError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Register { source, .. } => Some(source),
+            Self::Encode { source, .. } => Some(source),
+        }
+    }
+}
+impl MetricsError {
+    #[allow(missing_docs)]
+    pub fn register(source: prometheus::Error) -> Self {
+        Self::Register { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn encode(source: prometheus::Error) -> Self {
+        Self::Encode { source }
+    }
+}
@@ -11,3 +11,47 @@ pub struct Identity {
     pub expires: Option<SqlTimestamp>,
     pub created: SqlTimestamp,
 }
+
+/// The ceremony a challenge is issued for, a challenge can only complete the ceremony it was
+/// issued for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "purpose", rename_all = "camelCase")]
+pub enum ChallengePurpose {
+    /// Registering a passkey.
+    Registration,
+
+    /// Logging in for a common token.
+    Authentication,
+
+    /// Consenting to an action for a consent token.
+    Consent { act: String },
+}
+
+impl ChallengePurpose {
+    /// The value of the `purpose` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Registration => "registration",
+            Self::Authentication => "authentication",
+            Self::Consent { .. } => "consent",
+        }
+    }
+
+    /// The value of the `act` column.
+    pub fn act(&self) -> Option<&str> {
+        match self {
+            Self::Consent { act } => Some(act),
+            _ => None,
+        }
+    }
+
+    /// Reads the purpose from the `purpose` and `act` columns of a challenge.
+    pub fn from_columns(purpose: &str, act: Option<String>) -> Option<Self> {
+        match (purpose, act) {
+            ("registration", None) => Some(Self::Registration),
+            ("authentication", None) => Some(Self::Authentication),
+            ("consent", Some(act)) => Some(Self::Consent { act }),
+            _ => None,
+        }
+    }
+}
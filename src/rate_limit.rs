@@ -0,0 +1,384 @@
+use core::{net::IpAddr, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{MatchedPath, Query, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{HeaderValue, Method, StatusCode, header::RETRY_AFTER};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_api_helper::ErrorResponse;
+
+use crate::client_info::ClientInfo;
+
+/// The anonymous routes that are rate limited.
+const LIMITED_ROUTES: [(Method, &str); 5] = [
+    (Method::POST, "/challenges"),
+    (Method::POST, "/identities"),
+    (Method::GET, "/existing-credentials"),
+    (Method::GET, "/credential-request-options"),
+    (Method::GET, "/authorize"),
+];
+
+/// The largest body buffered to find the username, matching the default limit of the extractors.
+const BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// How often buckets that have refilled are forgotten.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The most buckets tracked at once, requests that would need another bucket are limited until the
+/// next prune.
+const MAX_BUCKETS: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketConfig {
+    /// The most requests allowed at once.
+    pub burst: u32,
+
+    /// The requests allowed again each minute once the burst is used.
+    pub per_minute: u32,
+}
+
+impl BucketConfig {
+    fn capacity(self) -> f64 {
+        f64::from(self.burst.max(1))
+    }
+
+    fn per_second(self) -> f64 {
+        f64::from(self.per_minute.max(1)) / 60.0
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitConfig {
+    /// Whether `POST /challenges`, `POST /identities` and `GET /existing-credentials` are
+    /// rate limited.
+    pub enabled: bool,
+
+    /// The limit for each client IP address.
+    pub per_ip_address: BucketConfig,
+
+    /// The limit for each API key, which is shared by every user of a frontend.
+    pub per_api_key: BucketConfig,
+
+    /// The limit for each username that is registered or looked up.
+    pub per_username: BucketConfig,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            per_ip_address: BucketConfig {
+                burst: 30,
+                per_minute: 60,
+            },
+            per_api_key: BucketConfig {
+                burst: 1000,
+                per_minute: 6000,
+            },
+            per_username: BucketConfig {
+                burst: 10,
+                per_minute: 10,
+            },
+        }
+    }
+}
+
+/// What a bucket counts the requests of.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum BucketKey {
+    IpAddress(IpAddr),
+    ApiKey(String),
+    Username(String),
+}
+
+/// The tracked buckets and when those that refilled were last forgotten.
+#[derive(Debug)]
+struct Buckets {
+    buckets: HashMap<BucketKey, Bucket>,
+    pruned: Instant,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Adds the tokens refilled since the bucket was last updated.
+    fn refill(&mut self, config: BucketConfig, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * config.per_second()).min(config.capacity());
+        self.updated = now;
+    }
+
+    /// How long until the bucket has a token.
+    fn wait(&self, config: BucketConfig) -> Duration {
+        Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / config.per_second())
+    }
+}
+
+/// Token buckets for the client IP address, API key and username of anonymous requests.
+#[derive(Debug)]
+pub struct RateLimiter {
+    enabled: bool,
+    per_ip_address: BucketConfig,
+    per_api_key: BucketConfig,
+    per_username: BucketConfig,
+    /// The header the API key is sent in.
+    api_key_header: String,
+    max_buckets: usize,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig, api_key_header: String) -> Self {
+        Self {
+            enabled: config.enabled,
+            per_ip_address: config.per_ip_address,
+            per_api_key: config.per_api_key,
+            per_username: config.per_username,
+            api_key_header,
+            max_buckets: MAX_BUCKETS,
+            buckets: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                pruned: Instant::now(),
+            }),
+        }
+    }
+
+    fn config(&self, key: &BucketKey) -> BucketConfig {
+        match key {
+            BucketKey::IpAddress(_) => self.per_ip_address,
+            BucketKey::ApiKey(_) => self.per_api_key,
+            BucketKey::Username(_) => self.per_username,
+        }
+    }
+
+    /// Takes a token from the bucket of every key, or none of them if any bucket is empty.
+    ///
+    /// Returns how long to wait before retrying when a bucket is empty.
+    fn take(&self, keys: Vec<BucketKey>, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        // Pruning visits every bucket, so it runs on an interval rather than on every request
+        let since_pruned = now.saturating_duration_since(buckets.pruned);
+        if since_pruned >= PRUNE_INTERVAL {
+            buckets.buckets.retain(|key, bucket| {
+                let config = self.config(key);
+                bucket.refill(config, now);
+                bucket.tokens < config.capacity()
+            });
+            buckets.pruned = now;
+        }
+        let buckets = &mut buckets.buckets;
+
+        let new_buckets = keys.iter().filter(|key| !buckets.contains_key(key)).count();
+        if buckets.len() + new_buckets > self.max_buckets {
+            tracing::warn!("too many clients are rate limited, limiting new clients");
+            return Err(PRUNE_INTERVAL
+                .saturating_sub(since_pruned)
+                .max(Duration::from_secs(1)));
+        }
+
+        let mut wait = Duration::ZERO;
+        for key in &keys {
+            let config = self.config(key);
+            let bucket = buckets.entry(key.clone()).or_insert_with(|| Bucket {
+                tokens: config.capacity(),
+                updated: now,
+            });
+            bucket.refill(config, now);
+            wait = wait.max(bucket.wait(config));
+        }
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+
+        for key in &keys {
+            if let Some(bucket) = buckets.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct Username {
+    username: Option<String>,
+}
+
+/// Rejects anonymous requests with `429 Too Many Requests` once a client, API key or username has
+/// used its requests.
+pub async fn limit_requests(
+    State(limiter): State<Arc<RateLimiter>>,
+    client_info: ClientInfo,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(route) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+    else {
+        return next.run(request).await;
+    };
+    let is_limited = LIMITED_ROUTES
+        .iter()
+        .any(|(method, path)| request.method() == method && route == *path);
+    if !limiter.enabled || !is_limited {
+        return next.run(request).await;
+    }
+
+    let mut keys = vec![];
+    if let Some(ip_address) = client_info.ip_address {
+        keys.push(BucketKey::IpAddress(ip_address));
+    }
+    if let Some(api_key) = request
+        .headers()
+        .get(&limiter.api_key_header)
+        .and_then(|api_key| api_key.to_str().ok())
+    {
+        keys.push(BucketKey::ApiKey(api_key.to_string()));
+    }
+
+    // The username is in the query when looking up credentials and in the body when registering
+    let (request, username) = if request.method() == Method::GET {
+        let username = Query::<Username>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|Query(query)| query.username);
+        (request, username)
+    } else {
+        let (parts, body) = request.into_parts();
+        let Ok(body) = to_bytes(body, BODY_LIMIT).await else {
+            return ErrorResponse {
+                status: StatusCode::PAYLOAD_TOO_LARGE,
+                problems: vec![],
+            }
+            .into_response();
+        };
+        let username = serde_json::from_slice::<Username>(&body)
+            .ok()
+            .and_then(|body| body.username);
+        (Request::from_parts(parts, Body::from(body)), username)
+    };
+    if let Some(username) = username {
+        keys.push(BucketKey::Username(username));
+    }
+
+    if let Err(wait) = limiter.take(keys, Instant::now()) {
+        tracing::info!(route, "rate limited");
+
+        // Retry-After is in whole seconds, so the wait is rounded up
+        let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        return (
+            [(RETRY_AFTER, HeaderValue::from(retry_after))],
+            ErrorResponse {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                problems: vec![],
+            },
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{BucketConfig, BucketKey, PRUNE_INTERVAL, RateLimitConfig, RateLimiter};
+
+    fn limiter() -> RateLimiter {
+        let bucket = BucketConfig {
+            burst: 2,
+            per_minute: 60,
+        };
+        RateLimiter::new(
+            &RateLimitConfig {
+                enabled: true,
+                per_ip_address: bucket,
+                per_api_key: bucket,
+                per_username: bucket,
+            },
+            "x-api-key".to_string(),
+        )
+    }
+
+    fn username(username: &str) -> BucketKey {
+        BucketKey::Username(username.to_string())
+    }
+
+    #[test]
+    fn allows_burst_then_refills() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        assert_eq!(Ok(()), limiter.take(vec![username("a")], now));
+        assert_eq!(Ok(()), limiter.take(vec![username("a")], now));
+        assert_eq!(
+            Err(Duration::from_secs(1)),
+            limiter.take(vec![username("a")], now)
+        );
+
+        // Other keys have their own bucket
+        assert_eq!(Ok(()), limiter.take(vec![username("b")], now));
+
+        let later = now + Duration::from_secs(1);
+        assert_eq!(Ok(()), limiter.take(vec![username("a")], later));
+        assert!(limiter.take(vec![username("a")], later).is_err());
+    }
+
+    #[test]
+    fn takes_from_no_bucket_when_any_is_empty() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        limiter.take(vec![username("a")], now).unwrap();
+        limiter.take(vec![username("a")], now).unwrap();
+
+        let api_key = BucketKey::ApiKey("key".to_string());
+        assert!(
+            limiter
+                .take(vec![api_key.clone(), username("a")], now)
+                .is_err()
+        );
+
+        // The API key bucket is still full
+        assert_eq!(Ok(()), limiter.take(vec![api_key.clone()], now));
+        assert_eq!(Ok(()), limiter.take(vec![api_key], now));
+    }
+
+    #[test]
+    fn limits_new_keys_until_pruned_when_full() {
+        let mut limiter = limiter();
+        limiter.max_buckets = 2;
+        let now = Instant::now();
+
+        limiter.take(vec![username("a")], now).unwrap();
+        limiter.take(vec![username("b")], now).unwrap();
+        assert!(limiter.take(vec![username("c")], now).is_err());
+
+        // Known keys are still served
+        assert_eq!(Ok(()), limiter.take(vec![username("a")], now));
+
+        // Once the buckets refill they are forgotten, making room for new keys
+        let later = now + PRUNE_INTERVAL;
+        assert_eq!(Ok(()), limiter.take(vec![username("c")], later));
+    }
+}
@@ -0,0 +1,127 @@
+use axum::extract::{Query, State};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use ts_api_helper::{ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem};
+use ts_sql_helper_lib::{FromRow, SqlTimestamp, query};
+
+use crate::{ApiState, access_token::AccessToken, telemetry::query_span};
+
+/// The most events returned in one page.
+const MAX_PAGE_SIZE: i64 = 100;
+
+query! {
+    name: GetAuditEvents,
+    row: {
+        id: i64,
+        kind: String,
+        target: Option<String>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+        outcome: String,
+        occurred: SqlTimestamp
+    },
+    optional_params: [2],
+    query: r#"
+        SELECT
+            id,
+            kind,
+            target,
+            ip_address,
+            user_agent,
+            outcome,
+            occurred
+        FROM
+            audit_events
+        WHERE
+            actor_id = $1::BYTEA
+            AND ($2::INT8 IS NULL OR id < $2::INT8)
+        ORDER BY
+            id DESC
+        LIMIT
+            $3::INT8;"#
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestQuery {
+    /// The `nextCursor` of the previous page.
+    cursor: Option<i64>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEvent {
+    pub id: i64,
+    pub kind: String,
+    pub target: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub outcome: String,
+    pub occurred: SqlTimestamp,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub audit_events: Vec<AuditEvent>,
+    /// The cursor for the next page of older events, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<i64>,
+}
+
+pub async fn get_handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, .. }): State<ApiState>,
+    Query(RequestQuery { cursor, limit }): Query<RequestQuery>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_SIZE);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(ErrorResponse::bad_request(vec![Problem::new(
+            "/limit",
+            "must be between 1 and 100",
+        )]));
+    }
+
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+
+    let database = pool.get().await.internal_server_error()?;
+    let audit_events: Vec<_> = database
+        .query(
+            GetAuditEvents::QUERY,
+            GetAuditEvents::params(&identity_id, cursor.as_ref(), &limit)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(GetAuditEvents))
+        .await
+        .internal_server_error()?
+        .into_iter()
+        .map(|row| GetAuditEventsRow::from_row(&row).unwrap())
+        .map(|event| AuditEvent {
+            id: event.id,
+            kind: event.kind,
+            target: event.target,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            outcome: event.outcome,
+            occurred: event.occurred,
+        })
+        .collect();
+
+    // A full page may be followed by more events
+    let next_cursor = match audit_events.last() {
+        Some(last) if i64::try_from(audit_events.len()) == Ok(limit) => Some(last.id),
+        _ => None,
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(Response {
+            audit_events,
+            next_cursor,
+        }),
+    ))
+}
@@ -0,0 +1,161 @@
+use axum::{Router, routing::get};
+use get_handler::get_handler;
+use tokio_postgres::Client;
+use tracing::Instrument;
+use ts_api_helper::token::json_web_token::TokenType;
+use ts_rust_helper::error::ErrorLogger;
+use ts_sql_helper_lib::query;
+
+use crate::{ApiState, client_info::ClientInfo, telemetry::query_span};
+
+mod get_handler;
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/audit-events", get(get_handler))
+        .with_state(state)
+}
+
+/// The security relevant actions that are recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditKind {
+    IdentityCreated,
+    IdentityUpdated,
+    IdentityDeleted,
+    PasskeyRegistered,
+    PasskeyRenamed,
+    PasskeyDeleted,
+    TokenIssued,
+    TokenRefreshed,
+    TokenRevoked,
+    ConsentConsumed,
+    SessionsRevoked,
+    RefreshTokenReused,
+    AuthorizationCodeIssued,
+    AuthorizationCodeExchanged,
+    ChallengeIssued,
+    TokenIntrospected,
+    AuthorizationRequested,
+}
+
+impl AuditKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::IdentityCreated => "identity_created",
+            Self::IdentityUpdated => "identity_updated",
+            Self::IdentityDeleted => "identity_deleted",
+            Self::PasskeyRegistered => "passkey_registered",
+            Self::PasskeyRenamed => "passkey_renamed",
+            Self::PasskeyDeleted => "passkey_deleted",
+            Self::TokenIssued => "token_issued",
+            Self::TokenRefreshed => "token_refreshed",
+            Self::TokenRevoked => "token_revoked",
+            Self::ConsentConsumed => "consent_consumed",
+            Self::SessionsRevoked => "sessions_revoked",
+            Self::RefreshTokenReused => "refresh_token_reused",
+            Self::AuthorizationCodeIssued => "authorization_code_issued",
+            Self::AuthorizationCodeExchanged => "authorization_code_exchanged",
+            Self::ChallengeIssued => "challenge_issued",
+            Self::TokenIntrospected => "token_introspected",
+            Self::AuthorizationRequested => "authorization_requested",
+        }
+    }
+}
+
+/// Whether the action was performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOutcome {
+    Success,
+    /// The request was authenticated but was not allowed to perform the action, such as the
+    /// consent being for a different action.
+    Denied,
+    /// The credential presented for the action could not be verified.
+    Failed,
+}
+
+impl AuditOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Denied => "denied",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+query! {
+    name: CreateAuditEvent,
+    optional_params: [1, 3, 4, 5],
+    query: r#"
+        INSERT INTO
+            audit_events (actor_id, kind, target, ip_address, user_agent, outcome)
+        VALUES
+            (
+                $1::BYTEA,
+                $2::VARCHAR,
+                $3::VARCHAR,
+                $4::VARCHAR,
+                $5::VARCHAR,
+                $6::VARCHAR
+            );"#
+}
+
+/// Appends events to the audit log on behalf of the identity making a request.
+pub struct AuditLog<'a> {
+    client: &'a Client,
+    /// The identity performing the actions, if known.
+    actor_id: Option<&'a [u8]>,
+    client_info: &'a ClientInfo,
+}
+
+impl<'a> AuditLog<'a> {
+    pub fn new(
+        client: &'a Client,
+        actor_id: Option<&'a [u8]>,
+        client_info: &'a ClientInfo,
+    ) -> Self {
+        Self {
+            client,
+            actor_id,
+            client_info,
+        }
+    }
+
+    /// Appends an event, the target is what the action was performed on such as a token ID.
+    ///
+    /// The action has already happened by the time it is recorded, so a failure to record it is
+    /// logged rather than failing the request.
+    pub async fn record(&self, kind: AuditKind, target: Option<&str>, outcome: AuditOutcome) {
+        let ip_address = self
+            .client_info
+            .ip_address
+            .map(|ip_address| ip_address.to_string());
+
+        self.client
+            .execute(
+                CreateAuditEvent::QUERY,
+                CreateAuditEvent::params(
+                    self.actor_id,
+                    kind.as_str(),
+                    target,
+                    ip_address.as_deref(),
+                    self.client_info.user_agent.as_deref(),
+                    outcome.as_str(),
+                )
+                .as_array()
+                .as_slice(),
+            )
+            .instrument(query_span!(CreateAuditEvent))
+            .await
+            .log_error()
+            .ok();
+    }
+
+    /// Records the consumption of a consent token, targeting the action it consented to.
+    pub async fn consent_consumed(&self, typ: &TokenType) {
+        if let TokenType::Consent { act } = typ {
+            self.record(AuditKind::ConsentConsumed, Some(act), AuditOutcome::Success)
+                .await;
+        }
+    }
+}
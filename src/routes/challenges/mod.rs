@@ -1,6 +1,12 @@
 use axum::{Router, routing::post};
+use http::header::{HeaderMap, ORIGIN};
+use rand::RngCore;
+use tokio_postgres::Client;
+use tracing::Instrument;
+use ts_api_helper::{ErrorResponse, InlineErrorResponse, webauthn::challenge::Challenge};
+use ts_sql_helper_lib::{ParseFromRow, SqlError, query};
 
-use crate::ApiState;
+use crate::{ApiState, models::ChallengePurpose, telemetry::query_span};
 
 use post_handler::post_handler;
 
@@ -11,3 +17,71 @@ pub fn router(state: ApiState) -> Router {
         .route("/challenges", post(post_handler))
         .with_state(state)
 }
+
+query! {
+    name: CreateChallenge,
+    optional_params: [2, 5],
+    query: r#"
+        INSERT INTO
+            challenges (challenge, identity_id, origin, purpose, act)
+        VALUES
+            ($1::BYTEA, $2::BYTEA, $3::VARCHAR, $4::VARCHAR, $5::VARCHAR)
+        RETURNING
+            challenge,
+            identity_id,
+            origin,
+            issued,
+            expires;"#
+}
+
+/// The origin of the request, which challenges may only be issued to if it is one of the WebAuthN
+/// origins.
+pub fn webauthn_origin<'a>(
+    headers: &'a HeaderMap,
+    webauthn_origins: &[String],
+) -> Result<&'a str, ErrorResponse> {
+    let origin = headers
+        .get(ORIGIN)
+        .unprocessable_entity()?
+        .to_str()
+        .unprocessable_entity()?;
+
+    if !webauthn_origins.iter().any(|allowed| allowed == origin) {
+        tracing::info!(origin, "refused a challenge for a disallowed origin");
+        return Err(ErrorResponse::forbidden());
+    }
+
+    Ok(origin)
+}
+
+pub async fn create_challenge(
+    client: &Client,
+    identity_id: Option<&[u8]>,
+    origin: &str,
+    purpose: &ChallengePurpose,
+) -> Result<Challenge, ErrorResponse> {
+    let mut challenge = [0u8; 32];
+    rand::rng().fill_bytes(&mut challenge);
+
+    let challenge = client
+        .query_one(
+            CreateChallenge::QUERY,
+            CreateChallenge::params(
+                &challenge,
+                identity_id,
+                origin,
+                purpose.as_str(),
+                purpose.act(),
+            )
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(CreateChallenge))
+        .await
+        .fk_violation(|| ErrorResponse::unauthenticated())?
+        .internal_server_error()?
+        .parse()
+        .unwrap();
+
+    Ok(challenge)
+}
@@ -1,87 +1,96 @@
 use axum::extract::State;
-use http::{
-    StatusCode,
-    header::{HeaderMap, ORIGIN},
-};
-use rand::RngCore;
+use http::{StatusCode, header::HeaderMap};
 use serde::{Deserialize, Serialize};
 use ts_api_helper::{
-    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, token::extractor::Token,
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
     webauthn::challenge::Challenge,
 };
-use ts_sql_helper_lib::{ParseFromRow, SqlError, query};
-
-use crate::ApiState;
 
-query! {
-    name: CreateChallenge,
-    optional_params: [2],
-    query: r#"
-        INSERT INTO
-            challenges (challenge, identity_id, origin)
-        VALUES
-            ($1::BYTEA, $2::BYTEA, $3::VARCHAR)
-        RETURNING
-            challenge,
-            identity_id,
-            origin,
-            issued,
-            expires;"#
-}
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    models::ChallengePurpose,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        challenges::{create_challenge, webauthn_origin},
+    },
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PostBody {
     pub identity_id: Option<String>,
+    /// Challenges are for authentication unless another purpose is given.
+    pub purpose: Option<String>,
+    pub act: Option<String>,
 }
 
 pub async fn post_handler(
     State(state): State<ApiState>,
     ApiKey(_): ApiKey,
-    token: Option<Token>,
+    token: Option<AccessToken>,
     headers: HeaderMap,
+    client_info: ClientInfo,
     body: Option<Json<PostBody>>,
 ) -> Result<(StatusCode, Json<Challenge>), ErrorResponse> {
-    let origin = headers
-        .get(ORIGIN)
-        .unprocessable_entity()?
-        .to_str()
-        .unprocessable_entity()?;
+    let origin = webauthn_origin(&headers, &state.webauthn_origins)?;
 
-    let identity_id = {
-        if let Some(Json(body)) = body
-            && let Some(identity_id) = body.identity_id
-        {
-            let Token(token) = token.unauthenticated()?;
+    let PostBody {
+        identity_id,
+        purpose,
+        act,
+    } = body.map(|Json(body)| body).unwrap_or_default();
+
+    let purpose =
+        ChallengePurpose::from_columns(purpose.as_deref().unwrap_or("authentication"), act)
+            .ok_or_else(|| {
+                ErrorResponse::bad_request(vec![Problem::new(
+                    "/purpose",
+                    "must be registration, authentication, or consent with an act",
+                )])
+            })?;
+
+    let identity_id = match identity_id {
+        Some(identity_id) => {
+            let AccessToken(token) = token.unauthenticated()?;
             if token.claims.sub == identity_id {
                 Some(identity_id.decode_base64().unprocessable_entity()?)
             } else {
+                let actor_id = token.claims.sub.decode_base64().ok();
+                let database = state.pool.get().await.internal_server_error()?;
+                AuditLog::new(&database, actor_id.as_deref(), &client_info)
+                    .record(
+                        AuditKind::ChallengeIssued,
+                        Some(purpose.as_str()),
+                        AuditOutcome::Denied,
+                    )
+                    .await;
+
                 return Err(ErrorResponse::forbidden());
             }
-        } else {
-            None
         }
+        None => None,
     };
 
-    let challenge = {
-        let database = state.pool.get().await.internal_server_error()?;
+    // Passkeys are registered for, and consent is given by, a known identity
+    if identity_id.is_none() && purpose != ChallengePurpose::Authentication {
+        return Err(ErrorResponse::bad_request(vec![Problem::new(
+            "/identityId",
+            "must be present for registration and consent",
+        )]));
+    }
 
-        let mut challenge = [0u8; 32];
-        rand::rng().fill_bytes(&mut challenge);
+    let database = state.pool.get().await.internal_server_error()?;
+    let challenge = create_challenge(&database, identity_id.as_deref(), origin, &purpose).await?;
 
-        database
-            .query_one(
-                CreateChallenge::QUERY,
-                CreateChallenge::params(&challenge, identity_id.as_deref(), origin)
-                    .as_array()
-                    .as_slice(),
-            )
-            .await
-            .fk_violation(|| ErrorResponse::unauthenticated())?
-            .internal_server_error()?
-            .parse()
-            .unwrap()
-    };
+    AuditLog::new(&database, identity_id.as_deref(), &client_info)
+        .record(
+            AuditKind::ChallengeIssued,
+            Some(purpose.as_str()),
+            AuditOutcome::Success,
+        )
+        .await;
 
     Ok((StatusCode::CREATED, Json(challenge)))
 }
@@ -0,0 +1,163 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    routing::get,
+};
+use http::{StatusCode, header::HeaderMap};
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json,
+    webauthn::{
+        public_key_credential_creation_options::{PublicKeyParameters, RelyingParty},
+        public_key_credential_request_options::AllowCredentials,
+    },
+};
+use ts_sql_helper_lib::{ParseFromRow, query};
+
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    models::{ChallengePurpose, Identity},
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        challenges::{create_challenge, webauthn_origin},
+        existing_credentials::get_credentials,
+    },
+    telemetry::query_span,
+};
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/credential-creation-options", get(handler))
+        .with_state(state)
+}
+
+query! {
+    name: GetIdentity,
+    query: r#"
+    SELECT
+        id,
+        username,
+        display_name,
+        created,
+        expires
+    FROM
+        identities
+    WHERE
+        id = $1::BYTEA;"#
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(with = "ts_api_helper::serde_base64")]
+    pub id: Vec<u8>,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: &'static str,
+    pub user_verification: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(with = "ts_api_helper::serde_base64")]
+    pub challenge: Vec<u8>,
+    pub rp: RelyingParty,
+    pub user: User,
+    pub pub_key_cred_params: Vec<PublicKeyParameters>,
+    pub exclude_credentials: Vec<AllowCredentials>,
+    pub hints: Vec<&'static str>,
+    pub authenticator_selection: AuthenticatorSelection,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestQuery {
+    prefer_resident_key: Option<bool>,
+}
+
+pub async fn handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState {
+        pool,
+        relying_party,
+        webauthn_origins,
+        ..
+    }): State<ApiState>,
+    headers: HeaderMap,
+    client_info: ClientInfo,
+    Query(RequestQuery {
+        prefer_resident_key,
+    }): Query<RequestQuery>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    let origin = webauthn_origin(&headers, &webauthn_origins)?;
+
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+
+    let database = pool.get().await.internal_server_error()?;
+
+    let identity: Identity = database
+        .query_opt(
+            GetIdentity::QUERY,
+            GetIdentity::params(&identity_id).as_array().as_slice(),
+        )
+        .instrument(query_span!(GetIdentity))
+        .await
+        .internal_server_error()?
+        .ok_or_else(ErrorResponse::unauthenticated)?
+        .parse()
+        .unwrap();
+
+    let exclude_credentials = get_credentials(&database, None, Some(&identity_id)).await?;
+
+    let challenge = create_challenge(
+        &database,
+        Some(&identity_id),
+        origin,
+        &ChallengePurpose::Registration,
+    )
+    .await?;
+
+    AuditLog::new(&database, Some(&identity_id), &client_info)
+        .record(
+            AuditKind::ChallengeIssued,
+            Some(ChallengePurpose::Registration.as_str()),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    let resident_key = if prefer_resident_key.unwrap_or(false) {
+        "preferred"
+    } else {
+        "discouraged"
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(Response {
+            challenge: challenge.challenge,
+            rp: relying_party,
+            user: User {
+                id: identity.id,
+                name: identity.username,
+                display_name: identity.display_name,
+            },
+            pub_key_cred_params: PublicKeyParameters::ALL.to_vec(),
+            exclude_credentials,
+            hints: vec!["security-key", "hybrid", "client-device"],
+            authenticator_selection: AuthenticatorSelection {
+                resident_key,
+                user_verification: "preferred",
+            },
+        }),
+    ))
+}
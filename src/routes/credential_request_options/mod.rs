@@ -0,0 +1,113 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    routing::get,
+};
+use http::{StatusCode, header::HeaderMap};
+use serde::{Deserialize, Serialize};
+use ts_api_helper::{
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json,
+    webauthn::public_key_credential_request_options::AllowCredentials,
+};
+
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    models::ChallengePurpose,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        challenges::{create_challenge, webauthn_origin},
+        existing_credentials::lookup_credentials,
+    },
+};
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/credential-request-options", get(handler))
+        .with_state(state)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(with = "ts_api_helper::serde_base64")]
+    pub challenge: Vec<u8>,
+    pub rp_id: String,
+    pub allow_credentials: Vec<AllowCredentials>,
+    pub hints: Vec<&'static str>,
+    pub user_verification: &'static str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestQuery {
+    username: Option<String>,
+    /// The action being consented to, making the challenge for a consent token.
+    act: Option<String>,
+}
+
+pub async fn handler(
+    _: ApiKey,
+    token: Option<AccessToken>,
+    State(ApiState {
+        pool,
+        relying_party,
+        webauthn_origins,
+        username_protection,
+        ..
+    }): State<ApiState>,
+    headers: HeaderMap,
+    client_info: ClientInfo,
+    Query(RequestQuery { username, act }): Query<RequestQuery>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    let origin = webauthn_origin(&headers, &webauthn_origins)?;
+
+    // A token scopes the ceremony to its subject, such as when requesting consent
+    let identity_id = match token.as_ref() {
+        Some(AccessToken(token)) => Some(token.claims.sub.decode_base64().unprocessable_entity()?),
+        None => None,
+    };
+
+    // Consent is always given by the subject of a token
+    let purpose = match act {
+        Some(act) if identity_id.is_some() => ChallengePurpose::Consent { act },
+        Some(_) => return Err(ErrorResponse::unauthenticated()),
+        None => ChallengePurpose::Authentication,
+    };
+
+    let database = pool.get().await.internal_server_error()?;
+
+    let allow_credentials = if identity_id.is_some() || username.is_some() {
+        lookup_credentials(
+            &database,
+            &username_protection,
+            username.as_deref(),
+            identity_id.as_deref(),
+        )
+        .await?
+    } else {
+        vec![]
+    };
+
+    let challenge = create_challenge(&database, identity_id.as_deref(), origin, &purpose).await?;
+
+    AuditLog::new(&database, identity_id.as_deref(), &client_info)
+        .record(
+            AuditKind::ChallengeIssued,
+            Some(purpose.as_str()),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(Response {
+            challenge: challenge.challenge,
+            rp_id: relying_party.id,
+            allow_credentials,
+            hints: vec!["security-key", "hybrid", "client-device"],
+            user_verification: "required",
+        }),
+    ))
+}
@@ -5,6 +5,9 @@ use axum::{
 };
 use http::StatusCode;
 use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tokio_postgres::Client;
+use tracing::Instrument;
 use ts_api_helper::{
     ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json,
     webauthn::{
@@ -14,7 +17,7 @@ use ts_api_helper::{
 };
 use ts_sql_helper_lib::{FromRow, query};
 
-use crate::ApiState;
+use crate::{ApiState, telemetry::query_span, username_protection::UsernameProtection};
 
 pub fn router(state: ApiState) -> Router {
     Router::new()
@@ -70,9 +73,71 @@ pub struct RequestQuery {
     identity_id: Option<String>,
 }
 
+pub async fn get_credentials(
+    client: &Client,
+    username: Option<&str>,
+    identity_id: Option<&[u8]>,
+) -> Result<Vec<AllowCredentials>, ErrorResponse> {
+    let credentials = client
+        .query(
+            GetCredentials::QUERY,
+            GetCredentials::params(username, identity_id)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(GetCredentials))
+        .await
+        .internal_server_error()?
+        .into_iter()
+        .map(|row| GetCredentialsRow::from_row(&row).unwrap())
+        .map(|credential| AllowCredentials {
+            id: credential.raw_id,
+            transports: credential.transports,
+            r#type: Type::PublicKey,
+        })
+        .collect();
+
+    Ok(credentials)
+}
+
+/// Looks up the credentials of a username or identity.
+///
+/// When decoys are enabled, a username without credentials gets decoy credentials instead and the
+/// lookup is held to a minimum time, so the response does not reveal whether the username exists.
+pub async fn lookup_credentials(
+    client: &Client,
+    protection: &UsernameProtection,
+    username: Option<&str>,
+    identity_id: Option<&[u8]>,
+) -> Result<Vec<AllowCredentials>, ErrorResponse> {
+    let Some(username) = username else {
+        return get_credentials(client, None, identity_id).await;
+    };
+    let Some(minimum_lookup) = protection.minimum_lookup() else {
+        return get_credentials(client, Some(username), identity_id).await;
+    };
+
+    let deadline = Instant::now() + minimum_lookup;
+
+    let mut credentials = get_credentials(client, Some(username), identity_id).await?;
+    if credentials.is_empty() {
+        credentials = protection
+            .decoy_credentials(username)
+            .internal_server_error()?;
+    }
+
+    tokio::time::sleep_until(deadline).await;
+
+    Ok(credentials)
+}
+
 pub async fn handler(
     _: ApiKey,
-    State(ApiState { pool, .. }): State<ApiState>,
+    State(ApiState {
+        pool,
+        username_protection,
+        ..
+    }): State<ApiState>,
     Query(RequestQuery {
         username,
         identity_id,
@@ -95,23 +160,13 @@ pub async fn handler(
         None
     };
 
-    let credentials = database
-        .query(
-            GetCredentials::QUERY,
-            GetCredentials::params(username.as_deref(), identity_id.as_deref())
-                .as_array()
-                .as_slice(),
-        )
-        .await
-        .internal_server_error()?
-        .into_iter()
-        .map(|row| GetCredentialsRow::from_row(&row).unwrap())
-        .map(|credential| AllowCredentials {
-            id: credential.raw_id,
-            transports: credential.transports,
-            r#type: Type::PublicKey,
-        })
-        .collect();
+    let credentials = lookup_credentials(
+        &database,
+        &username_protection,
+        username.as_deref(),
+        identity_id.as_deref(),
+    )
+    .await?;
 
     Ok((StatusCode::OK, Json(Response { credentials })))
 }
@@ -0,0 +1,101 @@
+use core::{sync::atomic::Ordering, time::Duration};
+
+use axum::{Router, extract::State, routing::get};
+use http::StatusCode;
+use serde::Serialize;
+use ts_api_helper::{ConnectionPool, Json};
+use ts_rust_helper::error::ErrorLogger;
+
+use crate::ApiState;
+
+/// How long the database has to answer before it is considered unavailable.
+const DATABASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/healthz", get(get_health))
+        .route("/readyz", get(get_readiness))
+        .with_state(state)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum CheckStatus {
+    Pass,
+    Fail,
+}
+
+impl From<bool> for CheckStatus {
+    fn from(passed: bool) -> Self {
+        if passed { Self::Pass } else { Self::Fail }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HealthResponse {
+    status: CheckStatus,
+}
+
+/// The process is alive and serving requests.
+async fn get_health() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: CheckStatus::Pass,
+    })
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadinessChecks {
+    database: CheckStatus,
+    migrations: CheckStatus,
+    signing_key: CheckStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadinessResponse {
+    status: CheckStatus,
+    checks: ReadinessChecks,
+}
+
+/// The service can handle requests, responding with `503 Service Unavailable` if any check fails.
+async fn get_readiness(
+    State(ApiState {
+        pool,
+        keyring,
+        migrations_complete,
+        ..
+    }): State<ApiState>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let checks = ReadinessChecks {
+        database: check_database(&pool).await.into(),
+        migrations: migrations_complete.load(Ordering::Acquire).into(),
+        signing_key: keyring.signing_jwk().is_some().into(),
+    };
+
+    let status = CheckStatus::from(
+        [checks.database, checks.migrations, checks.signing_key]
+            .iter()
+            .all(|check| *check == CheckStatus::Pass),
+    );
+    let status_code = match status {
+        CheckStatus::Pass => StatusCode::OK,
+        CheckStatus::Fail => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    (status_code, Json(ReadinessResponse { status, checks }))
+}
+
+/// Performs a round trip to the database through the pool.
+async fn check_database(pool: &ConnectionPool) -> bool {
+    let round_trip = async {
+        let connection = pool.get().await.log_error().ok()?;
+        connection.simple_query("SELECT 1;").await.log_error().ok()
+    };
+
+    matches!(
+        tokio::time::timeout(DATABASE_TIMEOUT, round_trip).await,
+        Ok(Some(_))
+    )
+}
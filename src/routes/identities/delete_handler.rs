@@ -1,12 +1,21 @@
 use axum::extract::{Path, State};
 use http::StatusCode;
+use tracing::Instrument;
 use ts_api_helper::{
-    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse,
-    token::{extractor::Token, json_web_token::TokenType},
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, token::json_web_token::TokenType,
 };
 use ts_sql_helper_lib::query;
 
-use crate::{ApiState, routes::revoked_tokens::revoke_token};
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        revoked_tokens::revoke_token,
+    },
+    telemetry::query_span,
+};
 
 query! {
     name: DeleteIdentity,
@@ -19,36 +28,56 @@ query! {
 
 pub async fn delete_handler(
     _: ApiKey,
-    Token(token): Token,
-    State(ApiState { pool, .. }): State<ApiState>,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, metrics, .. }): State<ApiState>,
     Path(identity_id): Path<String>,
+    client_info: ClientInfo,
 ) -> Result<StatusCode, ErrorResponse> {
     let expected_consent = TokenType::Consent {
         act: format!("DELETE /identities/{identity_id}"),
     };
 
     let database = pool.get().await.internal_server_error()?;
-    revoke_token(&database, &token.claims.tid, token.claims.exp).await;
+    revoke_token(&database, &token.claims.tid, token.claims.exp, &metrics).await;
 
-    if token.claims.typ != expected_consent {
-        return Err(ErrorResponse::forbidden());
-    }
+    let actor_id = token.claims.sub.decode_base64().ok();
+    let audit_log = AuditLog::new(&database, actor_id.as_deref(), &client_info);
+    audit_log.consent_consumed(&token.claims.typ).await;
 
-    if token.claims.sub != identity_id {
+    if token.claims.typ != expected_consent || token.claims.sub != identity_id {
+        audit_log
+            .record(
+                AuditKind::IdentityDeleted,
+                Some(&identity_id),
+                AuditOutcome::Denied,
+            )
+            .await;
         return Err(ErrorResponse::forbidden());
     }
 
-    let identity_id = identity_id
+    let identity_id_bytes = identity_id
         .decode_base64()
         .map_err(|_| ErrorResponse::unprocessable_entity())?;
 
     database
         .execute(
             DeleteIdentity::QUERY,
-            DeleteIdentity::params(&identity_id).as_array().as_slice(),
+            DeleteIdentity::params(&identity_id_bytes)
+                .as_array()
+                .as_slice(),
         )
+        .instrument(query_span!(DeleteIdentity))
         .await
         .internal_server_error()?;
 
+    // The events of a deleted identity are kept, they are only unreachable through the API
+    audit_log
+        .record(
+            AuditKind::IdentityDeleted,
+            Some(&identity_id),
+            AuditOutcome::Success,
+        )
+        .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,11 +1,10 @@
 use axum::extract::{Path, State};
 use http::StatusCode;
-use ts_api_helper::{
-    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, token::extractor::Token,
-};
+use tracing::Instrument;
+use ts_api_helper::{ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json};
 use ts_sql_helper_lib::{ParseFromRow, query};
 
-use crate::{ApiState, models::Identity};
+use crate::{ApiState, access_token::AccessToken, models::Identity, telemetry::query_span};
 
 query! {
     name: GetIdentity,
@@ -25,7 +24,7 @@ query! {
 pub async fn get_handler(
     _: ApiKey,
     State(ApiState { pool, .. }): State<ApiState>,
-    Token(token): Token,
+    AccessToken(token): AccessToken,
     Path(identity_id): Path<String>,
 ) -> Result<(StatusCode, Json<Identity>), ErrorResponse> {
     if identity_id != token.claims.sub {
@@ -40,6 +39,7 @@ pub async fn get_handler(
             GetIdentity::QUERY,
             GetIdentity::params(&identity_id).as_array().as_slice(),
         )
+        .instrument(query_span!(GetIdentity))
         .await
         .internal_server_error()?
         .ok_or_else(ErrorResponse::forbidden)?
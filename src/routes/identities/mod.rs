@@ -2,15 +2,18 @@ use axum::{
     Router,
     routing::{get, post},
 };
+use ts_api_helper::Problem;
 
 use crate::ApiState;
 
 use delete_handler::delete_handler;
 use get_handler::get_handler;
+use patch_handler::patch_handler;
 use post_handler::post_handler;
 
 mod delete_handler;
 mod get_handler;
+mod patch_handler;
 mod post_handler;
 
 pub fn router(state: ApiState) -> Router {
@@ -18,7 +21,34 @@ pub fn router(state: ApiState) -> Router {
         .route("/identities", post(post_handler))
         .route(
             "/identities/{identityId}",
-            get(get_handler).delete(delete_handler),
+            get(get_handler).patch(patch_handler).delete(delete_handler),
         )
         .with_state(state)
 }
+
+fn validate_username(username: &str, problems: &mut Vec<Problem>) {
+    if username.chars().count() < 4 {
+        problems.push(Problem::new(
+            "/username",
+            "must be at least four characters",
+        ));
+    }
+    if username.chars().count() > 64 {
+        problems.push(Problem::new("/username", "must be at most 64 characters"));
+    }
+}
+
+fn validate_display_name(display_name: &str, problems: &mut Vec<Problem>) {
+    if display_name.chars().count() < 4 {
+        problems.push(Problem::new(
+            "/displayName",
+            "name must be at least four characters",
+        ));
+    }
+    if display_name.chars().count() > 64 {
+        problems.push(Problem::new(
+            "/displayName",
+            "name must be at most 64 characters",
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use axum::extract::{Path, State};
+use http::StatusCode;
+use serde::Deserialize;
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
+    token::json_web_token::TokenType,
+};
+use ts_sql_helper_lib::{ParseFromRow, SqlError, query};
+
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    models::Identity,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        identities::{validate_display_name, validate_username},
+        revoked_tokens::revoke_token,
+    },
+    telemetry::query_span,
+};
+
+query! {
+    name: UpdateIdentity,
+    optional_params: [1, 2],
+    query: r#"
+    UPDATE
+        identities
+    SET
+        username = COALESCE($1::VARCHAR, username),
+        display_name = COALESCE($2::VARCHAR, display_name)
+    WHERE
+        id = $3::BYTEA
+    RETURNING
+        id,
+        username,
+        display_name,
+        created,
+        expires;"#
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchIdentityBody {
+    username: Option<String>,
+    display_name: Option<String>,
+}
+
+pub async fn patch_handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, metrics, .. }): State<ApiState>,
+    Path(identity_id): Path<String>,
+    client_info: ClientInfo,
+    Json(PatchIdentityBody {
+        username,
+        display_name,
+    }): Json<PatchIdentityBody>,
+) -> Result<(StatusCode, Json<Identity>), ErrorResponse> {
+    let database = pool.get().await.internal_server_error()?;
+
+    let actor_id = token.claims.sub.decode_base64().ok();
+    let audit_log = AuditLog::new(&database, actor_id.as_deref(), &client_info);
+
+    // Changing the username requires consent for this identity, other changes a common token
+    let expected_typ = if username.is_some() {
+        TokenType::Consent {
+            act: format!("PATCH /identities/{identity_id}"),
+        }
+    } else {
+        TokenType::Common
+    };
+    if token.claims.sub != identity_id || token.claims.typ != expected_typ {
+        audit_log
+            .record(
+                AuditKind::IdentityUpdated,
+                Some(&identity_id),
+                AuditOutcome::Denied,
+            )
+            .await;
+        return Err(ErrorResponse::forbidden());
+    }
+
+    // The consent is only consumed once it has been checked, it is revoked on receipt
+    if matches!(token.claims.typ, TokenType::Consent { .. }) {
+        revoke_token(&database, &token.claims.tid, token.claims.exp, &metrics).await;
+        audit_log.consent_consumed(&token.claims.typ).await;
+    }
+
+    // Validate body
+    {
+        let mut problems: Vec<Problem> = vec![];
+
+        if let Some(username) = username.as_deref() {
+            validate_username(username, &mut problems);
+        }
+        if let Some(display_name) = display_name.as_deref() {
+            validate_display_name(display_name, &mut problems);
+        }
+
+        if !problems.is_empty() {
+            return Err(ErrorResponse::bad_request(problems));
+        }
+    }
+
+    let identity_id_bytes = identity_id.decode_base64().unprocessable_entity()?;
+
+    let identity = database
+        .query_opt(
+            UpdateIdentity::QUERY,
+            UpdateIdentity::params(
+                username.as_deref(),
+                display_name.as_deref(),
+                &identity_id_bytes,
+            )
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(UpdateIdentity))
+        .await
+        .unique_violation(|| {
+            ErrorResponse::bad_request(vec![Problem::new(
+                "/username",
+                "an identity with this username already exists",
+            )])
+        })?
+        .internal_server_error()?
+        .ok_or_else(ErrorResponse::forbidden)?
+        .parse()
+        .unwrap();
+
+    audit_log
+        .record(
+            AuditKind::IdentityUpdated,
+            Some(&identity_id),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    Ok((StatusCode::OK, Json(identity)))
+}
@@ -2,13 +2,23 @@ use axum::extract::State;
 use http::{HeaderMap, HeaderValue, StatusCode, header::AUTHORIZATION};
 use rand::RngCore;
 use serde::Deserialize;
+use tracing::Instrument;
 use ts_api_helper::{
     ApiKey, EncodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
     token::json_web_token::TokenType,
 };
 use ts_sql_helper_lib::{ParseFromRow, SqlError, query};
 
-use crate::{ApiState, models::Identity};
+use crate::{
+    ApiState,
+    client_info::ClientInfo,
+    models::Identity,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        identities::{validate_display_name, validate_username},
+    },
+    telemetry::query_span,
+};
 
 query! {
     name: CreateIdentity,
@@ -35,8 +45,12 @@ pub struct PostIdentitiesBody {
 pub async fn post_handler(
     _: ApiKey,
     State(ApiState {
-        pool, signing_jwk, ..
+        pool,
+        keyring,
+        metrics,
+        ..
     }): State<ApiState>,
+    client_info: ClientInfo,
     Json(PostIdentitiesBody {
         username,
         display_name,
@@ -46,30 +60,8 @@ pub async fn post_handler(
     {
         let mut problems: Vec<Problem> = vec![];
 
-        // Validate username
-        if username.chars().count() < 4 {
-            problems.push(Problem::new(
-                "/username",
-                "must be at least four characters",
-            ));
-        }
-        if username.chars().count() > 64 {
-            problems.push(Problem::new("/username", "must be at most 64 characters"));
-        }
-
-        // Validate display name
-        if display_name.chars().count() < 4 {
-            problems.push(Problem::new(
-                "/displayName",
-                "name must be at least four characters",
-            ));
-        }
-        if display_name.chars().count() > 64 {
-            problems.push(Problem::new(
-                "/displayName",
-                "name must be at most 64 characters",
-            ));
-        }
+        validate_username(&username, &mut problems);
+        validate_display_name(&display_name, &mut problems);
 
         if !problems.is_empty() {
             return Err(ErrorResponse::bad_request(problems));
@@ -79,32 +71,46 @@ pub async fn post_handler(
     let mut id = [0u8; 32];
     rand::rng().fill_bytes(&mut id);
 
+    let database = pool.get().await.internal_server_error()?;
+
     // Create identity
-    let identity: Identity = {
-        let database = pool.get().await.internal_server_error()?;
+    let identity: Identity = database
+        .query_one(
+            CreateIdentity::QUERY,
+            CreateIdentity::params(&id, &username, &display_name)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(CreateIdentity))
+        .await
+        .unique_violation(|| {
+            ErrorResponse::bad_request(vec![Problem::new(
+                "/username",
+                "an identity with this username already exists",
+            )])
+        })?
+        .internal_server_error()?
+        .parse()
+        .unwrap();
 
-        database
-            .query_one(
-                CreateIdentity::QUERY,
-                CreateIdentity::params(&id, &username, &display_name)
-                    .as_array()
-                    .as_slice(),
-            )
-            .await
-            .unique_violation(|| {
-                ErrorResponse::bad_request(vec![Problem::new(
-                    "/username",
-                    "an identity with this username already exists",
-                )])
-            })?
-            .internal_server_error()?
-            .parse()
-            .unwrap()
-    };
+    let audit_log = AuditLog::new(&database, Some(&id), &client_info);
+    audit_log
+        .record(AuditKind::IdentityCreated, None, AuditOutcome::Success)
+        .await;
 
-    let token = signing_jwk
+    let token = keyring
+        .signing_jwk()
+        .internal_server_error()?
         .issue(identity.id.encode_base64(), TokenType::Provisioning)
         .internal_server_error()?;
+    metrics.record_token_issued(&token.claims.typ);
+    audit_log
+        .record(
+            AuditKind::TokenIssued,
+            Some(&token.claims.tid),
+            AuditOutcome::Success,
+        )
+        .await;
     let mut headers = HeaderMap::new();
     headers.append(
         AUTHORIZATION,
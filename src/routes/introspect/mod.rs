@@ -0,0 +1,109 @@
+use axum::{Form, Router, extract::State, routing::post};
+use http::StatusCode;
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use ts_api_helper::{
+    ApiKey, ErrorResponse, InlineErrorResponse, Json, token::json_web_token::TokenType,
+};
+
+use crate::{
+    ApiState,
+    client_info::ClientInfo,
+    jwt::{Claims, verify_token},
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        revoked_tokens::is_revoked,
+    },
+};
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/introspect", post(handler))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+pub struct IntrospectForm {
+    token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(flatten)]
+    pub typ: Option<TokenType>,
+}
+
+impl Response {
+    fn inactive() -> Self {
+        Self {
+            active: false,
+            sub: None,
+            exp: None,
+            jti: None,
+            client_id: None,
+            scope: None,
+            typ: None,
+        }
+    }
+}
+
+pub async fn handler(
+    _: ApiKey,
+    State(ApiState { pool, keyring, .. }): State<ApiState>,
+    client_info: ClientInfo,
+    Form(IntrospectForm { token }): Form<IntrospectForm>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    let database = pool.get().await.internal_server_error()?;
+    let audit_log = AuditLog::new(&database, None, &client_info);
+
+    // Tokens are verified against this service's own keys and revocations, so introspection does
+    // not depend on the JWKS and revocation lookups of the token extractor. RFC 7662 does not
+    // distinguish why a token is inactive, but a failed lookup is an error rather than inactive
+    let claims = verify_token::<Claims>(&keyring.key_set(), &token)
+        .filter(|claims| claims.exp > Timestamp::now());
+    let revoked = match &claims {
+        Some(claims) => is_revoked(&*database, &claims.tid)
+            .await
+            .internal_server_error()?,
+        None => false,
+    };
+    let Some(claims) = claims.filter(|_| !revoked) else {
+        audit_log
+            .record(AuditKind::TokenIntrospected, None, AuditOutcome::Failed)
+            .await;
+
+        return Ok((StatusCode::OK, Json(Response::inactive())));
+    };
+
+    audit_log
+        .record(
+            AuditKind::TokenIntrospected,
+            Some(&claims.tid),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(Response {
+            active: true,
+            sub: Some(claims.sub),
+            exp: Some(claims.exp.as_second()),
+            jti: Some(claims.tid),
+            client_id: claims.aud,
+            scope: claims.scope,
+            typ: Some(claims.typ),
+        }),
+    ))
+}
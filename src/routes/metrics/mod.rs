@@ -0,0 +1,57 @@
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+    Router,
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+    routing::get,
+};
+use http::{HeaderName, HeaderValue, header::CONTENT_TYPE};
+use prometheus::TEXT_FORMAT;
+use ts_api_helper::{ApiKey, ErrorResponse, InlineErrorResponse};
+
+use crate::{ApiState, metrics::Metrics};
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/metrics", get(get_metrics))
+        .with_state(state)
+}
+
+async fn get_metrics(
+    _: ApiKey,
+    State(ApiState { metrics, .. }): State<ApiState>,
+) -> Result<([(HeaderName, HeaderValue); 1], String), ErrorResponse> {
+    let body = metrics.encode().internal_server_error()?;
+
+    Ok((
+        [(CONTENT_TYPE, HeaderValue::from_static(TEXT_FORMAT))],
+        body,
+    ))
+}
+
+/// Records the count and latency of each request by its route.
+pub async fn track_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    metrics.record_request(
+        method.as_str(),
+        route.as_deref().unwrap_or("unmatched"),
+        response.status(),
+        start.elapsed(),
+    );
+
+    response
+}
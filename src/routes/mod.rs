@@ -1,7 +1,73 @@
+pub mod audit_events;
 pub mod challenges;
+pub mod credential_creation_options;
+pub mod credential_request_options;
 pub mod existing_credentials;
+pub mod health;
 pub mod identities;
+pub mod introspect;
+pub mod metrics;
+pub mod oauth;
 pub mod public_keys;
+pub mod refresh_tokens;
 pub mod revoked_tokens;
+pub mod sessions;
 pub mod tokens;
+pub mod userinfo;
 pub mod well_known;
+
+use std::sync::Arc;
+
+use axum::{Extension, Router, middleware};
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
+
+use crate::{
+    ApiState, rate_limit,
+    telemetry::{REQUEST_ID_HEADER, log_response, request_span},
+};
+
+/// Creates the router for every route of the API.
+pub fn router(state: ApiState) -> Router {
+    #[rustfmt::skip]
+    let router = Router::new()
+        .merge(challenges::router(state.clone()))
+        .merge(well_known::router(state.clone()))
+        .merge(existing_credentials::router(state.clone()))
+        .merge(credential_creation_options::router(state.clone()))
+        .merge(credential_request_options::router(state.clone()))
+        .merge(identities::router(state.clone()))
+        .merge(revoked_tokens::router(state.clone()))
+        .merge(tokens::router(state.clone()))
+        .merge(refresh_tokens::router(state.clone()))
+        .merge(sessions::router(state.clone()))
+        .merge(oauth::router(state.clone()))
+        .merge(userinfo::router(state.clone()))
+        .merge(introspect::router(state.clone()))
+        .merge(public_keys::router(state.clone()))
+        .merge(audit_events::router(state.clone()))
+        .merge(health::router(state.clone()))
+        .merge(metrics::router(state.clone()));
+
+    // Applied to each route so the matched path is known
+    router
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state.rate_limiter),
+            rate_limit::limit_requests,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state.metrics),
+            metrics::track_requests,
+        ))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_response(log_response)
+                .on_failure(()),
+        )
+        .layer(PropagateRequestIdLayer::new(REQUEST_ID_HEADER))
+        .layer(SetRequestIdLayer::new(REQUEST_ID_HEADER, MakeRequestUuid))
+        .layer(Extension(state.trusted_proxies))
+}
@@ -0,0 +1,174 @@
+use axum::extract::State;
+use http::StatusCode;
+use openssl::sha::sha256;
+use rand::RngCore;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, EncodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
+    token::json_web_token::TokenType,
+};
+use ts_sql_helper_lib::{FromRow, SqlError, query};
+
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    routes::audit_events::{AuditKind, AuditLog, AuditOutcome},
+    telemetry::query_span,
+};
+
+query! {
+    name: TakeAuthorizationRequest,
+    row: {
+        client_id: String,
+        redirect_uri: String,
+        code_challenge: String,
+        state: Option<String>,
+        scope: Option<String>,
+        nonce: Option<String>
+    },
+    query: r#"
+        DELETE FROM
+            authorization_requests
+        WHERE
+            id = $1::BYTEA
+            AND expires > timezone('utc', NOW())
+        RETURNING
+            client_id,
+            redirect_uri,
+            code_challenge,
+            state,
+            scope,
+            nonce;"#
+}
+
+query! {
+    name: CreateAuthorizationCode,
+    optional_params: [6, 7],
+    query: r#"
+        INSERT INTO
+            authorization_codes (
+                code,
+                client_id,
+                identity_id,
+                redirect_uri,
+                code_challenge,
+                scope,
+                nonce,
+                auth_time
+            )
+        VALUES
+            (
+                $1::BYTEA,
+                $2::VARCHAR,
+                $3::BYTEA,
+                $4::VARCHAR,
+                $5::VARCHAR,
+                $6::VARCHAR,
+                $7::VARCHAR,
+                COALESCE(
+                    (SELECT issued FROM sessions WHERE token = $8::VARCHAR),
+                    timezone('utc', NOW())
+                )
+            );"#
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    authorization_request: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub redirect_uri: String,
+}
+
+pub async fn authorization_codes_handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, .. }): State<ApiState>,
+    client_info: ClientInfo,
+    Json(Body {
+        authorization_request,
+    }): Json<Body>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    if token.claims.typ != TokenType::Common {
+        return Err(ErrorResponse::forbidden());
+    }
+
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+    let authorization_request = authorization_request
+        .decode_base64()
+        .unprocessable_entity()?;
+
+    let database = pool.get().await.internal_server_error()?;
+
+    let request = database
+        .query_opt(
+            TakeAuthorizationRequest::QUERY,
+            TakeAuthorizationRequest::params(&authorization_request)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(TakeAuthorizationRequest))
+        .await
+        .internal_server_error()?
+        .ok_or_else(|| {
+            ErrorResponse::bad_request(vec![Problem::new(
+                "/authorizationRequest",
+                "the authorization request does not exist or has expired",
+            )])
+        })?;
+    let request = TakeAuthorizationRequestRow::from_row(&request).unwrap();
+
+    let mut code = [0u8; 32];
+    rand::rng().fill_bytes(&mut code);
+
+    database
+        .execute(
+            CreateAuthorizationCode::QUERY,
+            CreateAuthorizationCode::params(
+                &sha256(&code),
+                &request.client_id,
+                &identity_id,
+                &request.redirect_uri,
+                &request.code_challenge,
+                request.scope.as_deref(),
+                request.nonce.as_deref(),
+                &token.claims.tid,
+            )
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(CreateAuthorizationCode))
+        .await
+        .fk_violation(ErrorResponse::unauthenticated)?
+        .internal_server_error()?;
+
+    AuditLog::new(&database, Some(&identity_id), &client_info)
+        .record(
+            AuditKind::AuthorizationCodeIssued,
+            Some(&request.client_id),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    let mut redirect_uri = Url::parse(&request.redirect_uri).internal_server_error()?;
+    redirect_uri
+        .query_pairs_mut()
+        .append_pair("code", &code.encode_base64());
+    if let Some(state) = request.state {
+        redirect_uri.query_pairs_mut().append_pair("state", &state);
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(Response {
+            redirect_uri: redirect_uri.into(),
+        }),
+    ))
+}
@@ -0,0 +1,170 @@
+use axum::{
+    extract::{Query, State},
+    response::Redirect,
+};
+use rand::RngCore;
+use reqwest::Url;
+use serde::Deserialize;
+use tracing::Instrument;
+use ts_api_helper::{EncodeBase64, ErrorResponse, InlineErrorResponse, Problem};
+use ts_sql_helper_lib::{FromRow, query};
+
+use crate::{
+    ApiState,
+    client_info::ClientInfo,
+    routes::audit_events::{AuditKind, AuditLog, AuditOutcome},
+    telemetry::query_span,
+};
+
+query! {
+    name: GetClient,
+    row: {redirect_uris: Vec<String>},
+    query: r#"
+        SELECT
+            redirect_uris
+        FROM
+            clients
+        WHERE
+            id = $1::VARCHAR;"#
+}
+
+query! {
+    name: CreateAuthorizationRequest,
+    optional_params: [5, 6, 7],
+    query: r#"
+        INSERT INTO
+            authorization_requests (
+                id,
+                client_id,
+                redirect_uri,
+                code_challenge,
+                state,
+                scope,
+                nonce
+            )
+        VALUES
+            (
+                $1::BYTEA,
+                $2::VARCHAR,
+                $3::VARCHAR,
+                $4::VARCHAR,
+                $5::VARCHAR,
+                $6::VARCHAR,
+                $7::VARCHAR
+            );"#
+}
+
+#[derive(Deserialize)]
+pub struct AuthorizeQuery {
+    response_type: Option<String>,
+    client_id: Option<String>,
+    redirect_uri: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    state: Option<String>,
+    scope: Option<String>,
+    nonce: Option<String>,
+}
+
+pub async fn authorize_handler(
+    State(ApiState {
+        pool,
+        login_page_url,
+        ..
+    }): State<ApiState>,
+    client_info: ClientInfo,
+    Query(query): Query<AuthorizeQuery>,
+) -> Result<Redirect, ErrorResponse> {
+    let client_id = query.client_id.ok_or_else(|| {
+        ErrorResponse::bad_request(vec![Problem::new("/client_id", "must be present")])
+    })?;
+    let redirect_uri = query.redirect_uri.ok_or_else(|| {
+        ErrorResponse::bad_request(vec![Problem::new("/redirect_uri", "must be present")])
+    })?;
+
+    let database = pool.get().await.internal_server_error()?;
+
+    // The redirect URI must be verified before any error may be sent to it
+    {
+        let client = database
+            .query_opt(
+                GetClient::QUERY,
+                GetClient::params(&client_id).as_array().as_slice(),
+            )
+            .instrument(query_span!(GetClient))
+            .await
+            .internal_server_error()?
+            .ok_or_else(|| {
+                ErrorResponse::bad_request(vec![Problem::new("/client_id", "unknown client")])
+            })?;
+        let GetClientRow { redirect_uris } = GetClientRow::from_row(&client).unwrap();
+
+        if !redirect_uris.contains(&redirect_uri) {
+            return Err(ErrorResponse::bad_request(vec![Problem::new(
+                "/redirect_uri",
+                "is not registered for this client",
+            )]));
+        }
+    }
+
+    let code_challenge = match (
+        query.response_type.as_deref(),
+        query.code_challenge,
+        query.code_challenge_method.as_deref(),
+    ) {
+        (Some("code"), Some(code_challenge), Some("S256")) => code_challenge,
+        (response_type, ..) => {
+            let error = if response_type == Some("code") {
+                "invalid_request"
+            } else {
+                "unsupported_response_type"
+            };
+
+            let mut redirect_url = Url::parse(&redirect_uri).internal_server_error()?;
+            redirect_url.query_pairs_mut().append_pair("error", error);
+            if let Some(state) = query.state {
+                redirect_url.query_pairs_mut().append_pair("state", &state);
+            }
+
+            return Ok(Redirect::to(redirect_url.as_str()));
+        }
+    };
+
+    let mut id = [0u8; 32];
+    rand::rng().fill_bytes(&mut id);
+
+    database
+        .execute(
+            CreateAuthorizationRequest::QUERY,
+            CreateAuthorizationRequest::params(
+                &id,
+                &client_id,
+                &redirect_uri,
+                &code_challenge,
+                query.state.as_deref(),
+                query.scope.as_deref(),
+                query.nonce.as_deref(),
+            )
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(CreateAuthorizationRequest))
+        .await
+        .internal_server_error()?;
+
+    // The identity is not known until the login page completes the request
+    AuditLog::new(&database, None, &client_info)
+        .record(
+            AuditKind::AuthorizationRequested,
+            Some(&client_id),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    let mut login_url = Url::parse(&login_page_url).internal_server_error()?;
+    login_url
+        .query_pairs_mut()
+        .append_pair("authorizationRequest", &id.encode_base64());
+
+    Ok(Redirect::to(login_url.as_str()))
+}
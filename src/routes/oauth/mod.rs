@@ -0,0 +1,58 @@
+use axum::{
+    Router,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use http::StatusCode;
+use openssl::sha::sha256;
+use serde::Serialize;
+use ts_api_helper::{ErrorResponse, Json};
+
+use crate::ApiState;
+
+use authorization_codes_handler::authorization_codes_handler;
+use authorize_handler::authorize_handler;
+use token_handler::token_handler;
+
+mod authorization_codes_handler;
+mod authorize_handler;
+mod token_handler;
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/authorize", get(authorize_handler))
+        .route("/authorization-codes", post(authorization_codes_handler))
+        .route("/token", post(token_handler))
+        .with_state(state)
+}
+
+/// Derives the S256 PKCE code challenge for a code verifier.
+pub fn s256_code_challenge(code_verifier: &str) -> String {
+    Base64UrlUnpadded::encode_string(&sha256(code_verifier.as_bytes()))
+}
+
+/// An error from the OAuth token endpoint, RFC 6749 errors are returned in the form clients expect.
+#[derive(Debug)]
+pub enum TokenError {
+    Rfc6749(&'static str),
+    Response(ErrorResponse),
+}
+impl From<ErrorResponse> for TokenError {
+    fn from(value: ErrorResponse) -> Self {
+        Self::Response(value)
+    }
+}
+impl IntoResponse for TokenError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: &'static str,
+        }
+
+        match self {
+            Self::Rfc6749(error) => (StatusCode::BAD_REQUEST, Json(Body { error })).into_response(),
+            Self::Response(response) => response.into_response(),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use axum::{Form, extract::State};
+use http::{
+    HeaderMap, HeaderValue, StatusCode,
+    header::{CACHE_CONTROL, PRAGMA},
+};
+use jiff::Timestamp;
+use openssl::{memcmp, sha::sha256};
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use ts_api_helper::{DecodeBase64, EncodeBase64, InlineErrorResponse, Json};
+use ts_sql_helper_lib::{FromRow, ParseFromRow, SqlTimestamp, query};
+
+use crate::{
+    ApiState,
+    client_info::ClientInfo,
+    id_token::{ClientToken, IdToken},
+    models::Identity,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        oauth::{TokenError, s256_code_challenge},
+        sessions::{NewSession, record_session},
+    },
+    telemetry::query_span,
+};
+
+query! {
+    name: TakeAuthorizationCode,
+    row: {
+        client_id: String,
+        identity_id: Vec<u8>,
+        redirect_uri: String,
+        code_challenge: String,
+        scope: Option<String>,
+        nonce: Option<String>,
+        auth_time: SqlTimestamp
+    },
+    query: r#"
+        DELETE FROM
+            authorization_codes
+        WHERE
+            code = $1::BYTEA
+            AND expires > timezone('utc', NOW())
+        RETURNING
+            client_id,
+            identity_id,
+            redirect_uri,
+            code_challenge,
+            scope,
+            nonce,
+            auth_time;"#
+}
+
+query! {
+    name: GetIdentity,
+    query: r#"
+    SELECT
+        id,
+        username,
+        display_name,
+        created,
+        expires
+    FROM
+        identities
+    WHERE
+        id = $1::BYTEA;"#
+}
+
+#[derive(Deserialize)]
+pub struct TokenForm {
+    grant_type: String,
+    code: String,
+    redirect_uri: String,
+    client_id: String,
+    code_verifier: String,
+}
+
+#[derive(Serialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+pub async fn token_handler(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    client_info: ClientInfo,
+    Form(form): Form<TokenForm>,
+) -> Result<(StatusCode, HeaderMap, Json<TokenResponse>), TokenError> {
+    if form.grant_type != "authorization_code" {
+        return Err(TokenError::Rfc6749("unsupported_grant_type"));
+    }
+
+    // RFC 7636 limits the verifier to 43-128 characters
+    if !(43..=128).contains(&form.code_verifier.len()) {
+        return Err(TokenError::Rfc6749("invalid_request"));
+    }
+
+    let code = form
+        .code
+        .decode_base64()
+        .map_err(|_| TokenError::Rfc6749("invalid_grant"))?;
+
+    let database = state.pool.get().await.internal_server_error()?;
+
+    let authorization = database
+        .query_opt(
+            TakeAuthorizationCode::QUERY,
+            TakeAuthorizationCode::params(&sha256(&code))
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(TakeAuthorizationCode))
+        .await
+        .internal_server_error()?
+        .ok_or(TokenError::Rfc6749("invalid_grant"))?;
+    let authorization = TakeAuthorizationCodeRow::from_row(&authorization).unwrap();
+    let audit_log = AuditLog::new(&database, Some(&authorization.identity_id), &client_info);
+
+    let code_challenge = s256_code_challenge(&form.code_verifier);
+    if authorization.client_id != form.client_id
+        || authorization.redirect_uri != form.redirect_uri
+        || code_challenge.len() != authorization.code_challenge.len()
+        || !memcmp::eq(
+            code_challenge.as_bytes(),
+            authorization.code_challenge.as_bytes(),
+        )
+    {
+        audit_log
+            .record(
+                AuditKind::AuthorizationCodeExchanged,
+                Some(&form.client_id),
+                AuditOutcome::Denied,
+            )
+            .await;
+        return Err(TokenError::Rfc6749("invalid_grant"));
+    }
+
+    // The token is restricted to the client, first-party routes reject it
+    let signing_key = state.keyring.signing_key().internal_server_error()?;
+    let token = state
+        .id_token_signer
+        .issue_client_token(
+            &signing_key,
+            ClientToken {
+                sub: authorization.identity_id.encode_base64(),
+                aud: authorization.client_id.clone(),
+                scope: authorization.scope.clone(),
+            },
+        )
+        .internal_server_error()?;
+    state.metrics.record_token_issued(&token.typ);
+    audit_log
+        .record(
+            AuditKind::AuthorizationCodeExchanged,
+            Some(&authorization.client_id),
+            AuditOutcome::Success,
+        )
+        .await;
+    audit_log
+        .record(
+            AuditKind::TokenIssued,
+            Some(&token.tid),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    record_session(
+        &*database,
+        NewSession {
+            token_id: &token.tid,
+            identity_id: &authorization.identity_id,
+            typ: &token.typ,
+            public_key_id: None,
+            expires: token.exp,
+            scope: authorization.scope.as_deref(),
+        },
+        &headers,
+    )
+    .await?;
+
+    // ID tokens are only issued for OpenID Connect requests
+    let scopes: Vec<&str> = authorization
+        .scope
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    let id_token = if scopes.contains(&"openid") {
+        let identity: Identity = database
+            .query_opt(
+                GetIdentity::QUERY,
+                GetIdentity::params(&authorization.identity_id)
+                    .as_array()
+                    .as_slice(),
+            )
+            .instrument(query_span!(GetIdentity))
+            .await
+            .internal_server_error()?
+            .ok_or(TokenError::Rfc6749("invalid_grant"))?
+            .parse()
+            .unwrap();
+
+        let (preferred_username, name) = if scopes.contains(&"profile") {
+            (Some(identity.username), Some(identity.display_name))
+        } else {
+            (None, None)
+        };
+
+        let id_token = state
+            .id_token_signer
+            .issue(
+                &signing_key,
+                IdToken {
+                    sub: authorization.identity_id.encode_base64(),
+                    aud: authorization.client_id,
+                    auth_time: authorization.auth_time.0,
+                    nonce: authorization.nonce,
+                    preferred_username,
+                    name,
+                },
+            )
+            .internal_server_error()?;
+        state.metrics.record_id_token_issued();
+
+        Some(id_token)
+    } else {
+        None
+    };
+
+    let expires_in = token.exp.duration_since(Timestamp::now()).as_secs();
+
+    let mut header_map = HeaderMap::new();
+    header_map.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    header_map.insert(PRAGMA, HeaderValue::from_static("no-cache"));
+
+    Ok((
+        StatusCode::OK,
+        header_map,
+        Json(TokenResponse {
+            access_token: token.token,
+            token_type: "Bearer",
+            expires_in,
+            scope: authorization.scope,
+            id_token,
+        }),
+    ))
+}
@@ -1,12 +1,21 @@
 use axum::extract::{Path, State};
 use http::StatusCode;
+use tracing::Instrument;
 use ts_api_helper::{
-    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse,
-    token::{extractor::Token, json_web_token::TokenType},
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, token::json_web_token::TokenType,
 };
 use ts_sql_helper_lib::query;
 
-use crate::{ApiState, routes::revoked_tokens::revoke_token};
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        revoked_tokens::revoke_token,
+    },
+    telemetry::query_span,
+};
 
 query! {
     name: GetPublicKeyCount,
@@ -31,22 +40,35 @@ query! {
 
 pub async fn delete_handler(
     _: ApiKey,
-    Token(token): Token,
-    State(ApiState { pool, .. }): State<ApiState>,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, metrics, .. }): State<ApiState>,
     Path(public_key_id): Path<String>,
+    client_info: ClientInfo,
 ) -> Result<StatusCode, ErrorResponse> {
     let expected_consent = TokenType::Consent {
         act: format!("DELETE /public-keys/{public_key_id}"),
     };
+
+    let database = pool.get().await.internal_server_error()?;
+
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+    let audit_log = AuditLog::new(&database, Some(&identity_id), &client_info);
+
     if token.claims.typ != expected_consent {
+        audit_log
+            .record(
+                AuditKind::PasskeyDeleted,
+                Some(&public_key_id),
+                AuditOutcome::Denied,
+            )
+            .await;
         return Err(ErrorResponse::unauthenticated());
     }
 
-    let database = pool.get().await.internal_server_error()?;
-    revoke_token(&database, &token.claims.tid, token.claims.exp).await;
+    revoke_token(&database, &token.claims.tid, token.claims.exp, &metrics).await;
+    audit_log.consent_consumed(&token.claims.typ).await;
 
-    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
-    let public_key_id = public_key_id.decode_base64().unprocessable_entity()?;
+    let public_key_id_bytes = public_key_id.decode_base64().unprocessable_entity()?;
 
     // Ensure identity always has one public key
     {
@@ -57,6 +79,7 @@ pub async fn delete_handler(
                     .as_array()
                     .as_slice(),
             )
+            .instrument(query_span!(GetPublicKeyCount))
             .await
             .internal_server_error()?
             .try_get("public_key_count")
@@ -65,6 +88,13 @@ pub async fn delete_handler(
             return Err(ErrorResponse::unauthenticated());
         }
         if public_key_count == 1 {
+            audit_log
+                .record(
+                    AuditKind::PasskeyDeleted,
+                    Some(&public_key_id),
+                    AuditOutcome::Denied,
+                )
+                .await;
             return Err(ErrorResponse {
                 status: StatusCode::NOT_ACCEPTABLE,
                 problems: vec![],
@@ -75,12 +105,21 @@ pub async fn delete_handler(
     database
         .execute(
             DeletePublicKey::QUERY,
-            DeletePublicKey::params(&public_key_id, &identity_id)
+            DeletePublicKey::params(&public_key_id_bytes, &identity_id)
                 .as_array()
                 .as_slice(),
         )
+        .instrument(query_span!(DeletePublicKey))
         .await
         .internal_server_error()?;
 
+    audit_log
+        .record(
+            AuditKind::PasskeyDeleted,
+            Some(&public_key_id),
+            AuditOutcome::Success,
+        )
+        .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,13 +1,14 @@
 use axum::extract::State;
 use http::StatusCode;
 use serde::Serialize;
+use tracing::Instrument;
 use ts_api_helper::{
-    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, token::extractor::Token,
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json,
     webauthn::persisted_public_key::PersistedPublicKey,
 };
 use ts_sql_helper_lib::{FromRow, query};
 
-use crate::ApiState;
+use crate::{ApiState, access_token::AccessToken, telemetry::query_span};
 
 query! {
     name: GetPublicKeys,
@@ -37,7 +38,7 @@ pub struct Response {
 
 pub async fn get_handler(
     _: ApiKey,
-    Token(token): Token,
+    AccessToken(token): AccessToken,
     State(ApiState { pool, .. }): State<ApiState>,
 ) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
     let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
@@ -48,6 +49,7 @@ pub async fn get_handler(
             GetPublicKeys::QUERY,
             GetPublicKeys::params(&identity_id).as_array().as_slice(),
         )
+        .instrument(query_span!(GetPublicKeys))
         .await
         .internal_server_error()?
         .into_iter()
@@ -1,6 +1,6 @@
 use axum::{
     Router,
-    routing::{delete, post},
+    routing::{delete, post, put},
 };
 
 use crate::ApiState;
@@ -8,14 +8,17 @@ use crate::ApiState;
 use delete_handler::delete_handler;
 use get_handler::get_handler;
 use post_handler::post_handler;
+use put_handler::put_handler;
 
 mod delete_handler;
 mod get_handler;
 mod post_handler;
+mod put_handler;
 
 pub fn router(state: ApiState) -> Router {
     Router::new()
         .route("/public-keys", post(post_handler).get(get_handler))
         .route("/public-keys/{publicKeyId}", delete(delete_handler))
+        .route("/public-keys/{publicKeyId}/displayName", put(put_handler))
         .with_state(state)
 }
@@ -1,9 +1,10 @@
 use axum::extract::State;
 use http::{HeaderMap, HeaderValue, StatusCode, header::AUTHORIZATION};
 use serde::Deserialize;
+use tracing::Instrument;
 use ts_api_helper::{
-    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
-    token::{extractor::Token, json_web_token::TokenType},
+    ApiKey, DecodeBase64, EncodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
+    token::json_web_token::TokenType,
     webauthn::{
         persisted_public_key::PersistedPublicKey,
         public_key_credential::{PublicKeyCredential, Response},
@@ -12,7 +13,18 @@ use ts_api_helper::{
 };
 use ts_sql_helper_lib::{ParseFromRow, SqlError, query};
 
-use crate::ApiState;
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    metrics::Ceremony,
+    models::ChallengePurpose,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        sessions::{NewSession, record_session},
+    },
+    telemetry::query_span,
+};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -68,8 +80,10 @@ query! {
 
 pub async fn post_handler(
     _: ApiKey,
-    Token(token): Token,
+    AccessToken(token): AccessToken,
     State(state): State<ApiState>,
+    headers: HeaderMap,
+    client_info: ClientInfo,
     Json(Body {
         credential,
         display_name,
@@ -89,17 +103,32 @@ pub async fn post_handler(
 
     let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
     let verification_result = credential
-        .verify(&state, Some(&identity_id))
-        .await
-        .internal_server_error()?;
+        .verify(
+            &state.verifier(ChallengePurpose::Registration),
+            Some(&identity_id),
+        )
+        .await;
+    state
+        .metrics
+        .record_verification(Ceremony::Registration, verification_result.as_ref());
+    let verification_result = verification_result.internal_server_error()?;
+
+    let database = state.pool.get().await.internal_server_error()?;
+    let audit_log = AuditLog::new(&database, Some(&identity_id), &client_info);
+    let public_key_id = credential.raw_id.encode_base64();
 
     let VerificationResult::Valid { identity_id } = verification_result else {
+        audit_log
+            .record(
+                AuditKind::PasskeyRegistered,
+                Some(&public_key_id),
+                AuditOutcome::Failed,
+            )
+            .await;
         return Err(ErrorResponse::unauthenticated());
     };
 
     let public_key: PersistedPublicKey = {
-        let database = state.pool.get().await.internal_server_error()?;
-
         let transports: Vec<_> = response
             .method_results
             .transports
@@ -131,19 +160,37 @@ pub async fn post_handler(
                 .as_array()
                 .as_slice(),
             )
+            .instrument(query_span!(CreatePublicKey))
             .await
             .fk_violation(ErrorResponse::unauthenticated)?
             .internal_server_error()?
             .parse()
             .unwrap()
     };
+    audit_log
+        .record(
+            AuditKind::PasskeyRegistered,
+            Some(&public_key_id),
+            AuditOutcome::Success,
+        )
+        .await;
 
     let mut header_map = HeaderMap::new();
     if token.claims.typ.eq(&TokenType::Provisioning) {
         let token = state
-            .signing_jwk
+            .keyring
+            .signing_jwk()
+            .internal_server_error()?
             .issue(token.claims.sub, TokenType::Common)
             .internal_server_error()?;
+        state.metrics.record_token_issued(&token.claims.typ);
+        audit_log
+            .record(
+                AuditKind::TokenIssued,
+                Some(&token.claims.tid),
+                AuditOutcome::Success,
+            )
+            .await;
 
         let value = HeaderValue::from_str(&format!("bearer {}", token.serialize()))
             .internal_server_error()?;
@@ -151,7 +198,6 @@ pub async fn post_handler(
         header_map.insert(AUTHORIZATION, value);
 
         // Flag identity is non-expiring
-        let database = state.pool.get().await.internal_server_error()?;
         database
             .execute(
                 MakeIdentityPermanant::QUERY,
@@ -159,8 +205,23 @@ pub async fn post_handler(
                     .as_array()
                     .as_slice(),
             )
+            .instrument(query_span!(MakeIdentityPermanant))
             .await
             .internal_server_error()?;
+
+        record_session(
+            &*database,
+            NewSession {
+                token_id: &token.claims.tid,
+                identity_id: &identity_id,
+                typ: &token.claims.typ,
+                public_key_id: Some(&credential.raw_id),
+                expires: token.claims.exp,
+                scope: None,
+            },
+            &headers,
+        )
+        .await?;
     }
 
     Ok((StatusCode::CREATED, header_map, Json(public_key)))
@@ -0,0 +1,89 @@
+use axum::extract::{Path, State};
+use http::StatusCode;
+use serde::Deserialize;
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
+    webauthn::persisted_public_key::PersistedPublicKey,
+};
+use ts_sql_helper_lib::{ParseFromRow, query};
+
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    routes::audit_events::{AuditKind, AuditLog, AuditOutcome},
+    telemetry::query_span,
+};
+
+query! {
+    name: UpdatePublicKeyDisplayName,
+    query: r#"
+        UPDATE
+            public_keys
+        SET
+            display_name = $1::VARCHAR
+        WHERE
+            raw_id = $2::BYTEA
+            AND identity_id = $3::BYTEA
+        RETURNING
+            raw_id,
+            identity_id,
+            display_name,
+            public_key,
+            public_key_algorithm,
+            transports,
+            signature_counter,
+            created,
+            last_used;"#
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    display_name: String,
+}
+
+pub async fn put_handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, .. }): State<ApiState>,
+    Path(public_key_id): Path<String>,
+    client_info: ClientInfo,
+    Json(Body { display_name }): Json<Body>,
+) -> Result<(StatusCode, Json<PersistedPublicKey>), ErrorResponse> {
+    if display_name.is_empty() {
+        return Err(ErrorResponse::bad_request(vec![Problem::new(
+            "/displayName",
+            "must not be empty",
+        )]));
+    }
+
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+    let public_key_id_bytes = public_key_id.decode_base64().unprocessable_entity()?;
+
+    let database = pool.get().await.internal_server_error()?;
+    let public_key = database
+        .query_opt(
+            UpdatePublicKeyDisplayName::QUERY,
+            UpdatePublicKeyDisplayName::params(&display_name, &public_key_id_bytes, &identity_id)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(UpdatePublicKeyDisplayName))
+        .await
+        .internal_server_error()?
+        .ok_or_else(ErrorResponse::forbidden)?
+        .parse()
+        .unwrap();
+
+    AuditLog::new(&database, Some(&identity_id), &client_info)
+        .record(
+            AuditKind::PasskeyRenamed,
+            Some(&public_key_id),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    Ok((StatusCode::OK, Json(public_key)))
+}
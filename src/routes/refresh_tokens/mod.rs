@@ -0,0 +1,175 @@
+use axum::{Router, routing::post};
+use jiff::Timestamp;
+use openssl::sha::sha256;
+use post_handler::post_handler;
+use rand::RngCore;
+use tokio_postgres::GenericClient;
+use tracing::Instrument;
+use ts_api_helper::{EncodeBase64, ErrorResponse, InlineErrorResponse};
+use ts_rust_helper::error::ErrorLogger;
+use ts_sql_helper_lib::{SqlTimestamp, query};
+
+use crate::{
+    ApiState,
+    metrics::{Metrics, Revocation},
+    telemetry::query_span,
+};
+
+mod post_handler;
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/refresh-tokens", post(post_handler))
+        .with_state(state)
+}
+
+query! {
+    name: CreateRefreshToken,
+    query: r#"
+        INSERT INTO
+            refresh_tokens (
+                token,
+                family,
+                identity_id,
+                access_token_id,
+                access_token_expires
+            )
+        VALUES
+            ($1::BYTEA, $2::BYTEA, $3::BYTEA, $4::VARCHAR, $5::TIMESTAMPTZ);"#
+}
+
+query! {
+    name: RevokeFamilyAccessTokens,
+    query: r#"
+        INSERT INTO
+            revocations (token, expires)
+        SELECT
+            access_token_id,
+            access_token_expires
+        FROM
+            refresh_tokens
+        WHERE
+            family = $1::BYTEA
+        ON CONFLICT DO NOTHING;"#
+}
+
+query! {
+    name: RevokeFamily,
+    query: r#"
+        INSERT INTO
+            revocations (token, expires)
+        SELECT
+            $2::VARCHAR,
+            MAX(expires)
+        FROM
+            refresh_tokens
+        WHERE
+            family = $1::BYTEA
+        ON CONFLICT DO NOTHING;"#
+}
+
+query! {
+    name: UseFamily,
+    query: r#"
+        UPDATE
+            refresh_tokens
+        SET
+            used = (timezone('utc', NOW()))
+        WHERE
+            family = $1::BYTEA
+            AND used IS NULL;"#
+}
+
+/// Hashes a refresh token for storage, the raw token is only ever held by the client.
+pub fn hash_refresh_token(token: &[u8]) -> [u8; 32] {
+    sha256(token)
+}
+
+/// Issues a refresh token for an access token, starting a new family if none is provided.
+pub async fn issue_refresh_token(
+    client: &impl GenericClient,
+    identity_id: &[u8],
+    family: Option<&[u8]>,
+    access_token_id: &str,
+    access_token_expires: Timestamp,
+) -> Result<String, ErrorResponse> {
+    let mut token = [0u8; 32];
+    rand::rng().fill_bytes(&mut token);
+
+    let family = match family {
+        Some(family) => family.to_vec(),
+        None => {
+            let mut family = [0u8; 32];
+            rand::rng().fill_bytes(&mut family);
+            family.to_vec()
+        }
+    };
+
+    client
+        .execute(
+            CreateRefreshToken::QUERY,
+            CreateRefreshToken::params(
+                &hash_refresh_token(&token),
+                &family,
+                identity_id,
+                access_token_id,
+                &SqlTimestamp(access_token_expires),
+            )
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(CreateRefreshToken))
+        .await
+        .internal_server_error()?;
+
+    Ok(token.encode_base64())
+}
+
+/// Revokes a refresh token family, every refresh token in the family and the access tokens issued
+/// alongside them.
+pub async fn revoke_refresh_token_family(
+    client: &impl GenericClient,
+    family: &[u8],
+    metrics: &Metrics,
+) -> bool {
+    let revoked_access_tokens = client
+        .execute(
+            RevokeFamilyAccessTokens::QUERY,
+            RevokeFamilyAccessTokens::params(family)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(RevokeFamilyAccessTokens))
+        .await
+        .log_error()
+        .is_ok();
+
+    let revoked_family = client
+        .execute(
+            RevokeFamily::QUERY,
+            RevokeFamily::params(family, &family.encode_base64())
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(RevokeFamily))
+        .await
+        .log_error()
+        .is_ok();
+
+    let used_family = client
+        .execute(
+            UseFamily::QUERY,
+            UseFamily::params(family).as_array().as_slice(),
+        )
+        .instrument(query_span!(UseFamily))
+        .await
+        .log_error()
+        .is_ok();
+
+    let revoked = revoked_access_tokens && revoked_family && used_family;
+    if revoked {
+        metrics.record_revocation(Revocation::RefreshTokenFamily);
+    }
+
+    revoked
+}
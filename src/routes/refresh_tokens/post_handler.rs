@@ -0,0 +1,215 @@
+use axum::extract::State;
+use http::{HeaderMap, HeaderValue, StatusCode, header::AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, EncodeBase64, ErrorResponse, InlineErrorResponse, Json,
+    token::json_web_token::TokenType,
+};
+use ts_sql_helper_lib::{FromRow, query};
+
+use crate::{
+    ApiState,
+    client_info::ClientInfo,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        refresh_tokens::{hash_refresh_token, issue_refresh_token, revoke_refresh_token_family},
+        sessions::{NewSession, record_session},
+    },
+    telemetry::query_span,
+};
+
+query! {
+    name: UseRefreshToken,
+    row: {family: Vec<u8>, identity_id: Vec<u8>},
+    query: r#"
+        UPDATE
+            refresh_tokens
+        SET
+            used = (timezone('utc', NOW()))
+        WHERE
+            token = $1::BYTEA
+            AND used IS NULL
+            AND expires > timezone('utc', NOW())
+        RETURNING
+            family,
+            identity_id;"#
+}
+
+query! {
+    name: GetUsedRefreshToken,
+    row: {family: Vec<u8>, identity_id: Vec<u8>},
+    query: r#"
+        SELECT
+            family,
+            identity_id
+        FROM
+            refresh_tokens
+        WHERE
+            token = $1::BYTEA
+            AND used IS NOT NULL;"#
+}
+
+query! {
+    name: GetRevokedFamily,
+    query: r#"
+        SELECT
+            token
+        FROM
+            revocations
+        WHERE
+            token = $1::VARCHAR;"#
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    refresh_token: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub refresh_token: String,
+}
+
+pub async fn post_handler(
+    _: ApiKey,
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    client_info: ClientInfo,
+    Json(Body { refresh_token }): Json<Body>,
+) -> Result<(StatusCode, HeaderMap, Json<Response>), ErrorResponse> {
+    let refresh_token = refresh_token.decode_base64().unprocessable_entity()?;
+    let refresh_token = hash_refresh_token(&refresh_token);
+
+    let mut database = state.pool.get().await.internal_server_error()?;
+
+    // The token is only used if its replacement is issued, otherwise a failed refresh would leave
+    // the client with a used token that trips reuse detection when retried
+    let transaction = database.transaction().await.internal_server_error()?;
+
+    let row = transaction
+        .query_opt(
+            UseRefreshToken::QUERY,
+            UseRefreshToken::params(&refresh_token)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(UseRefreshToken))
+        .await
+        .internal_server_error()?;
+
+    let Some(row) = row else {
+        transaction.rollback().await.internal_server_error()?;
+
+        // A refresh token being presented twice means it has leaked, so the family is revoked
+        let used_token = database
+            .query_opt(
+                GetUsedRefreshToken::QUERY,
+                GetUsedRefreshToken::params(&refresh_token)
+                    .as_array()
+                    .as_slice(),
+            )
+            .instrument(query_span!(GetUsedRefreshToken))
+            .await
+            .internal_server_error()?;
+
+        if let Some(used_token) = used_token {
+            let GetUsedRefreshTokenRow {
+                family,
+                identity_id,
+            } = GetUsedRefreshTokenRow::from_row(&used_token).unwrap();
+            tracing::warn!("refresh token reuse detected, revoking family");
+            revoke_refresh_token_family(&*database, &family, &state.metrics).await;
+            AuditLog::new(&database, Some(&identity_id), &client_info)
+                .record(
+                    AuditKind::RefreshTokenReused,
+                    Some(&family.encode_base64()),
+                    AuditOutcome::Denied,
+                )
+                .await;
+        }
+
+        return Err(ErrorResponse::unauthenticated());
+    };
+
+    let UseRefreshTokenRow {
+        family,
+        identity_id,
+    } = UseRefreshTokenRow::from_row(&row).unwrap();
+
+    let revoked_family = transaction
+        .query_opt(
+            GetRevokedFamily::QUERY,
+            GetRevokedFamily::params(&family.encode_base64())
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(GetRevokedFamily))
+        .await
+        .internal_server_error()?;
+    if revoked_family.is_some() {
+        transaction.commit().await.internal_server_error()?;
+        AuditLog::new(&database, Some(&identity_id), &client_info)
+            .record(
+                AuditKind::TokenRefreshed,
+                Some(&family.encode_base64()),
+                AuditOutcome::Denied,
+            )
+            .await;
+        return Err(ErrorResponse::unauthenticated());
+    }
+
+    let token = state
+        .keyring
+        .signing_jwk()
+        .internal_server_error()?
+        .issue(identity_id.encode_base64(), TokenType::Common)
+        .internal_server_error()?;
+
+    record_session(
+        &transaction,
+        NewSession {
+            token_id: &token.claims.tid,
+            identity_id: &identity_id,
+            typ: &token.claims.typ,
+            public_key_id: None,
+            expires: token.claims.exp,
+            scope: None,
+        },
+        &headers,
+    )
+    .await?;
+
+    let refresh_token = issue_refresh_token(
+        &transaction,
+        &identity_id,
+        Some(&family),
+        &token.claims.tid,
+        token.claims.exp,
+    )
+    .await?;
+
+    transaction.commit().await.internal_server_error()?;
+
+    state.metrics.record_token_issued(&token.claims.typ);
+    AuditLog::new(&database, Some(&identity_id), &client_info)
+        .record(
+            AuditKind::TokenRefreshed,
+            Some(&token.claims.tid),
+            AuditOutcome::Success,
+        )
+        .await;
+
+    let mut header_map = HeaderMap::new();
+    let value =
+        HeaderValue::from_str(&format!("bearer {}", token.serialize())).internal_server_error()?;
+    header_map.insert(AUTHORIZATION, value);
+
+    Ok((
+        StatusCode::CREATED,
+        header_map,
+        Json(Response { refresh_token }),
+    ))
+}
@@ -1,21 +1,8 @@
 use axum::extract::{Path, State};
 use http::StatusCode;
 use ts_api_helper::{ApiKey, ErrorResponse, InlineErrorResponse};
-use ts_sql_helper_lib::query;
 
-use crate::ApiState;
-
-query! {
-    name: GetRevokedToken,
-    query: r#"
-        SELECT
-            token,
-            expires
-        FROM
-            revocations
-        WHERE
-            token = $1::VARCHAR;"#
-}
+use crate::{ApiState, routes::revoked_tokens::is_revoked};
 
 pub async fn get_handler(
     _: ApiKey,
@@ -24,15 +11,11 @@ pub async fn get_handler(
 ) -> Result<StatusCode, ErrorResponse> {
     let database = pool.get().await.internal_server_error()?;
 
-    let row = database
-        .query_opt(
-            GetRevokedToken::QUERY,
-            GetRevokedToken::params(&token).as_array().as_slice(),
-        )
+    let revoked = is_revoked(&*database, &token)
         .await
         .internal_server_error()?;
 
-    if row.is_some() {
+    if revoked {
         Ok(StatusCode::OK)
     } else {
         Err(ErrorResponse {
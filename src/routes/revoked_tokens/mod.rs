@@ -5,11 +5,16 @@ use axum::{
 use get_handler::get_handler;
 use jiff::Timestamp;
 use post_handler::post_handler;
-use tokio_postgres::Client;
+use tokio_postgres::{Client, GenericClient};
+use tracing::Instrument;
 use ts_rust_helper::error::ErrorLogger;
 use ts_sql_helper_lib::{SqlTimestamp, query};
 
-use crate::ApiState;
+use crate::{
+    ApiState,
+    metrics::{Metrics, Revocation},
+    telemetry::query_span,
+};
 
 mod get_handler;
 mod post_handler;
@@ -27,18 +32,59 @@ query! {
         INSERT INTO
             revocations (token, expires)
         VALUES
-            ($1::VARCHAR, $2::TIMESTAMPTZ);"#
+            ($1::VARCHAR, $2::TIMESTAMPTZ)
+        ON CONFLICT DO NOTHING;"#
+}
+
+query! {
+    name: GetRevokedToken,
+    query: r#"
+        SELECT
+            token,
+            expires
+        FROM
+            revocations
+        WHERE
+            token = $1::VARCHAR;"#
 }
 
-pub async fn revoke_token(client: &Client, token_id: &str, expiry: Timestamp) -> bool {
-    client
+/// Whether a token has been revoked.
+pub async fn is_revoked(
+    client: &impl GenericClient,
+    token_id: &str,
+) -> Result<bool, tokio_postgres::Error> {
+    let row = client
+        .query_opt(
+            GetRevokedToken::QUERY,
+            GetRevokedToken::params(token_id).as_array().as_slice(),
+        )
+        .instrument(query_span!(GetRevokedToken))
+        .await?;
+
+    Ok(row.is_some())
+}
+
+pub async fn revoke_token(
+    client: &Client,
+    token_id: &str,
+    expiry: Timestamp,
+    metrics: &Metrics,
+) -> bool {
+    let revoked = client
         .execute(
             RevokeToken::QUERY,
             RevokeToken::params(token_id, &SqlTimestamp(expiry))
                 .as_array()
                 .as_slice(),
         )
+        .instrument(query_span!(RevokeToken))
         .await
         .log_error()
-        .is_ok()
+        .is_ok();
+
+    if revoked {
+        metrics.record_revocation(Revocation::Token);
+    }
+
+    revoked
 }
@@ -1,19 +1,44 @@
 use axum::extract::State;
 use http::StatusCode;
 
-use ts_api_helper::{ApiKey, ErrorResponse, InlineErrorResponse, token::extractor::Token};
+use ts_api_helper::{ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse};
 
-use crate::{ApiState, routes::revoked_tokens::revoke_token};
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        revoked_tokens::revoke_token,
+    },
+};
 
 pub async fn post_handler(
     _: ApiKey,
-    Token(token): Token,
+    AccessToken(token): AccessToken,
     State(state): State<ApiState>,
+    client_info: ClientInfo,
 ) -> Result<StatusCode, ErrorResponse> {
     let database = state.pool.get().await.internal_server_error()?;
-    if !revoke_token(&database, &token.claims.tid, token.claims.exp).await {
+    if !revoke_token(
+        &database,
+        &token.claims.tid,
+        token.claims.exp,
+        &state.metrics,
+    )
+    .await
+    {
         return Err(ErrorResponse::internal_server_error());
     };
 
+    let actor_id = token.claims.sub.decode_base64().ok();
+    AuditLog::new(&database, actor_id.as_deref(), &client_info)
+        .record(
+            AuditKind::TokenRevoked,
+            Some(&token.claims.tid),
+            AuditOutcome::Success,
+        )
+        .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
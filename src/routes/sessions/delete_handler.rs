@@ -0,0 +1,115 @@
+use axum::extract::State;
+use http::StatusCode;
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, ErrorResponse, InlineErrorResponse, token::json_web_token::TokenType,
+};
+use ts_sql_helper_lib::{FromRow, query};
+
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    metrics::Revocation,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        refresh_tokens::revoke_refresh_token_family,
+        revoked_tokens::revoke_token,
+    },
+    telemetry::query_span,
+};
+
+query! {
+    name: RevokeSessions,
+    query: r#"
+        INSERT INTO
+            revocations (token, expires)
+        SELECT
+            token,
+            expires
+        FROM
+            sessions
+        WHERE
+            identity_id = $1::BYTEA
+            AND expires > timezone('utc', NOW())
+        ON CONFLICT DO NOTHING;"#
+}
+
+query! {
+    name: GetRefreshTokenFamilies,
+    row: {family: Vec<u8>},
+    query: r#"
+        SELECT DISTINCT
+            family
+        FROM
+            refresh_tokens
+        WHERE
+            identity_id = $1::BYTEA
+            AND used IS NULL;"#
+}
+
+pub async fn delete_handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, metrics, .. }): State<ApiState>,
+    client_info: ClientInfo,
+) -> Result<StatusCode, ErrorResponse> {
+    let expected_consent = TokenType::Consent {
+        act: "DELETE /sessions".to_string(),
+    };
+
+    let mut database = pool.get().await.internal_server_error()?;
+    revoke_token(&database, &token.claims.tid, token.claims.exp, &metrics).await;
+
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+    let audit_log = AuditLog::new(&database, Some(&identity_id), &client_info);
+    audit_log.consent_consumed(&token.claims.typ).await;
+
+    if token.claims.typ != expected_consent {
+        audit_log
+            .record(AuditKind::SessionsRevoked, None, AuditOutcome::Denied)
+            .await;
+        return Err(ErrorResponse::forbidden());
+    }
+
+    // The sessions and refresh token families are revoked together, so a failure part way leaves
+    // every session usable rather than some
+    let transaction = database.transaction().await.internal_server_error()?;
+
+    let revoked_sessions = transaction
+        .execute(
+            RevokeSessions::QUERY,
+            RevokeSessions::params(&identity_id).as_array().as_slice(),
+        )
+        .instrument(query_span!(RevokeSessions))
+        .await
+        .internal_server_error()?;
+
+    let families = transaction
+        .query(
+            GetRefreshTokenFamilies::QUERY,
+            GetRefreshTokenFamilies::params(&identity_id)
+                .as_array()
+                .as_slice(),
+        )
+        .instrument(query_span!(GetRefreshTokenFamilies))
+        .await
+        .internal_server_error()?
+        .into_iter()
+        .map(|row| GetRefreshTokenFamiliesRow::from_row(&row).unwrap());
+
+    for GetRefreshTokenFamiliesRow { family } in families {
+        if !revoke_refresh_token_family(&transaction, &family, &metrics).await {
+            return Err(ErrorResponse::internal_server_error());
+        }
+    }
+
+    transaction.commit().await.internal_server_error()?;
+    metrics.record_revocations(Revocation::Token, revoked_sessions);
+
+    AuditLog::new(&database, Some(&identity_id), &client_info)
+        .record(AuditKind::SessionsRevoked, None, AuditOutcome::Success)
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
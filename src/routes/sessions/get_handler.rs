@@ -0,0 +1,97 @@
+use axum::extract::State;
+use http::StatusCode;
+use serde::Serialize;
+use tracing::Instrument;
+use ts_api_helper::{
+    ApiKey, DecodeBase64, EncodeBase64, ErrorResponse, InlineErrorResponse, Json,
+    token::json_web_token::TokenType,
+};
+use ts_sql_helper_lib::{FromRow, SqlTimestamp, query};
+
+use crate::{ApiState, access_token::AccessToken, telemetry::query_span};
+
+query! {
+    name: GetSessions,
+    row: {
+        token: String,
+        token_type: String,
+        public_key_id: Option<Vec<u8>>,
+        user_agent: Option<String>,
+        issued: SqlTimestamp,
+        expires: SqlTimestamp
+    },
+    query: r#"
+        SELECT
+            token,
+            token_type,
+            public_key_id,
+            user_agent,
+            issued,
+            expires
+        FROM
+            sessions
+        WHERE
+            identity_id = $1::BYTEA
+            AND expires > timezone('utc', NOW())
+            AND NOT EXISTS (
+                SELECT
+                    1
+                FROM
+                    revocations
+                WHERE
+                    revocations.token = sessions.token
+            )
+        ORDER BY
+            issued DESC;"#
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub token_id: String,
+    #[serde(flatten)]
+    pub typ: TokenType,
+    pub public_key_id: Option<String>,
+    pub user_agent: Option<String>,
+    pub issued: SqlTimestamp,
+    pub expires: SqlTimestamp,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub sessions: Vec<Session>,
+}
+
+pub async fn get_handler(
+    _: ApiKey,
+    AccessToken(token): AccessToken,
+    State(ApiState { pool, .. }): State<ApiState>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+
+    let database = pool.get().await.internal_server_error()?;
+    let sessions = database
+        .query(
+            GetSessions::QUERY,
+            GetSessions::params(&identity_id).as_array().as_slice(),
+        )
+        .instrument(query_span!(GetSessions))
+        .await
+        .internal_server_error()?
+        .into_iter()
+        .map(|row| GetSessionsRow::from_row(&row).unwrap())
+        .map(|session| Session {
+            token_id: session.token,
+            typ: serde_json::from_str(&session.token_type).unwrap(),
+            public_key_id: session
+                .public_key_id
+                .map(|public_key_id| public_key_id.encode_base64()),
+            user_agent: session.user_agent,
+            issued: session.issued,
+            expires: session.expires,
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(Response { sessions })))
+}
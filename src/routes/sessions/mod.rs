@@ -0,0 +1,82 @@
+use axum::{Router, routing::get};
+use delete_handler::delete_handler;
+use get_handler::get_handler;
+use http::{HeaderMap, header::USER_AGENT};
+use jiff::Timestamp;
+use tokio_postgres::GenericClient;
+use tracing::Instrument;
+use ts_api_helper::{ErrorResponse, InlineErrorResponse, token::json_web_token::TokenType};
+use ts_sql_helper_lib::{SqlTimestamp, query};
+
+use crate::{ApiState, telemetry::query_span};
+
+mod delete_handler;
+mod get_handler;
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/sessions", get(get_handler).delete(delete_handler))
+        .with_state(state)
+}
+
+query! {
+    name: CreateSession,
+    optional_params: [4, 5, 7],
+    query: r#"
+        INSERT INTO
+            sessions (token, identity_id, token_type, public_key_id, user_agent, expires, scope)
+        VALUES
+            (
+                $1::VARCHAR,
+                $2::BYTEA,
+                $3::VARCHAR,
+                $4::BYTEA,
+                $5::VARCHAR,
+                $6::TIMESTAMPTZ,
+                $7::VARCHAR
+            );"#
+}
+
+/// The details of an issued token to record as a session.
+pub struct NewSession<'a> {
+    pub token_id: &'a str,
+    pub identity_id: &'a [u8],
+    pub typ: &'a TokenType,
+    pub public_key_id: Option<&'a [u8]>,
+    pub expires: Timestamp,
+    /// The OAuth scope granted to the token, first-party tokens are unscoped.
+    pub scope: Option<&'a str>,
+}
+
+pub async fn record_session(
+    client: &impl GenericClient,
+    session: NewSession<'_>,
+    headers: &HeaderMap,
+) -> Result<(), ErrorResponse> {
+    let token_type = serde_json::to_string(session.typ).internal_server_error()?;
+
+    let user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|user_agent| user_agent.to_str().ok());
+
+    client
+        .execute(
+            CreateSession::QUERY,
+            CreateSession::params(
+                session.token_id,
+                session.identity_id,
+                &token_type,
+                session.public_key_id,
+                user_agent,
+                &SqlTimestamp(session.expires),
+                session.scope,
+            )
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(CreateSession))
+        .await
+        .internal_server_error()?;
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use axum::extract::State;
 use http::{HeaderMap, HeaderValue, StatusCode, header::AUTHORIZATION};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use ts_api_helper::{
     ApiKey, DecodeBase64, EncodeBase64, ErrorResponse, InlineErrorResponse, Json, Problem,
-    token::{extractor::Token, json_web_token::TokenType},
+    token::json_web_token::TokenType,
     webauthn::{
         public_key_credential::{PublicKeyCredential, Response},
         verification::VerificationResult,
@@ -11,7 +12,19 @@ use ts_api_helper::{
 };
 use ts_sql_helper_lib::query;
 
-use crate::ApiState;
+use crate::{
+    ApiState,
+    access_token::AccessToken,
+    client_info::ClientInfo,
+    metrics::Ceremony,
+    models::ChallengePurpose,
+    routes::{
+        audit_events::{AuditKind, AuditLog, AuditOutcome},
+        refresh_tokens::issue_refresh_token,
+        sessions::{NewSession, record_session},
+    },
+    telemetry::query_span,
+};
 
 #[derive(Deserialize)]
 pub struct Body {
@@ -20,6 +33,13 @@ pub struct Body {
     pub typ: TokenType,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
 query! {
     name: UpdatePasskeyOnLogin,
     query: r#"
@@ -34,10 +54,12 @@ query! {
 
 pub async fn post_handler(
     _: ApiKey,
-    token: Option<Token>,
+    token: Option<AccessToken>,
     State(state): State<ApiState>,
+    headers: HeaderMap,
+    client_info: ClientInfo,
     Json(Body { credential, typ }): Json<Body>,
-) -> Result<(StatusCode, HeaderMap), ErrorResponse> {
+) -> Result<(StatusCode, HeaderMap, Json<ResponseBody>), ErrorResponse> {
     let Response::AssertionResponse(assertion_response) = &credential.response else {
         return Err(ErrorResponse::unprocessable_entity());
     };
@@ -53,48 +75,108 @@ pub async fn post_handler(
     }
 
     let identity_id = match token.as_ref() {
-        Some(Token(token)) => Some(token.claims.sub.decode_base64().unprocessable_entity()?),
+        Some(AccessToken(token)) => Some(token.claims.sub.decode_base64().unprocessable_entity()?),
         None => None,
     };
 
+    let (ceremony, purpose) = match &typ {
+        TokenType::Consent { act } => (
+            Ceremony::Consent,
+            ChallengePurpose::Consent { act: act.clone() },
+        ),
+        _ => (Ceremony::Login, ChallengePurpose::Authentication),
+    };
     let verification_result = credential
-        .verify(&state, identity_id.as_deref())
-        .await
-        .internal_server_error()?;
+        .verify(&state.verifier(purpose), identity_id.as_deref())
+        .await;
+    state
+        .metrics
+        .record_verification(ceremony, verification_result.as_ref());
+    let verification_result = verification_result.internal_server_error()?;
+
+    let database = state.pool.get().await.internal_server_error()?;
 
     let VerificationResult::Valid { identity_id } = verification_result else {
+        AuditLog::new(&database, identity_id.as_deref(), &client_info)
+            .record(AuditKind::TokenIssued, None, AuditOutcome::Failed)
+            .await;
         return Err(ErrorResponse::unauthenticated());
     };
+    let audit_log = AuditLog::new(&database, Some(&identity_id), &client_info);
 
-    {
-        let database = state.pool.get().await.internal_server_error()?;
-        database
-            .execute(
-                UpdatePasskeyOnLogin::QUERY,
-                UpdatePasskeyOnLogin::params(
-                    &assertion_response
-                        .authenticator_data
-                        .signature_counter
-                        .into(),
-                    &credential.raw_id,
-                )
-                .as_array()
-                .as_slice(),
+    database
+        .execute(
+            UpdatePasskeyOnLogin::QUERY,
+            UpdatePasskeyOnLogin::params(
+                &assertion_response
+                    .authenticator_data
+                    .signature_counter
+                    .into(),
+                &credential.raw_id,
             )
-            .await
-            .internal_server_error()?;
-    }
+            .as_array()
+            .as_slice(),
+        )
+        .instrument(query_span!(UpdatePasskeyOnLogin))
+        .await
+        .internal_server_error()?;
+
+    let is_common = typ == TokenType::Common;
 
     let mut header_map = HeaderMap::new();
     let token = state
-        .signing_jwk
+        .keyring
+        .signing_jwk()
+        .internal_server_error()?
         .issue(identity_id.encode_base64(), typ)
         .internal_server_error()?;
+    state.metrics.record_token_issued(&token.claims.typ);
+    audit_log
+        .record(
+            AuditKind::TokenIssued,
+            Some(&token.claims.tid),
+            AuditOutcome::Success,
+        )
+        .await;
 
     let value =
         HeaderValue::from_str(&format!("bearer {}", token.serialize())).internal_server_error()?;
 
     header_map.insert(AUTHORIZATION, value);
 
-    Ok((StatusCode::CREATED, header_map))
+    record_session(
+        &*database,
+        NewSession {
+            token_id: &token.claims.tid,
+            identity_id: &identity_id,
+            typ: &token.claims.typ,
+            public_key_id: Some(&credential.raw_id),
+            expires: token.claims.exp,
+            scope: None,
+        },
+        &headers,
+    )
+    .await?;
+
+    // Only common tokens may be refreshed, consent must always be a fresh ceremony
+    let refresh_token = if is_common {
+        Some(
+            issue_refresh_token(
+                &*database,
+                &identity_id,
+                None,
+                &token.claims.tid,
+                token.claims.exp,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        header_map,
+        Json(ResponseBody { refresh_token }),
+    ))
 }
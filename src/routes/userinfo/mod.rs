@@ -0,0 +1,95 @@
+use axum::{Router, extract::State, routing::get};
+use http::StatusCode;
+use serde::Serialize;
+use tracing::Instrument;
+use ts_api_helper::{DecodeBase64, ErrorResponse, InlineErrorResponse, Json};
+use ts_sql_helper_lib::{ParseFromRow, SqlTimestamp, query};
+
+use crate::{ApiState, access_token::ClientAccessToken, models::Identity, telemetry::query_span};
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/userinfo", get(handler).post(handler))
+        .with_state(state)
+}
+
+query! {
+    name: GetIdentity,
+    query: r#"
+    SELECT
+        id,
+        username,
+        display_name,
+        created,
+        expires
+    FROM
+        identities
+    WHERE
+        id = $1::BYTEA;"#
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<SqlTimestamp>,
+}
+
+pub async fn handler(
+    ClientAccessToken(token): ClientAccessToken,
+    State(ApiState { pool, .. }): State<ApiState>,
+) -> Result<(StatusCode, Json<Response>), ErrorResponse> {
+    let identity_id = token.claims.sub.decode_base64().unprocessable_entity()?;
+
+    let database = pool.get().await.internal_server_error()?;
+
+    // Tokens issued to OAuth clients are limited to their granted scope, first-party tokens are not
+    let scope = token
+        .is_client_token()
+        .then(|| token.claims.scope.clone().unwrap_or_default());
+    let (openid, profile) = match scope.as_deref() {
+        Some(scope) => {
+            let scopes: Vec<&str> = scope.split_whitespace().collect();
+            (scopes.contains(&"openid"), scopes.contains(&"profile"))
+        }
+        None => (true, true),
+    };
+
+    if !openid {
+        return Err(ErrorResponse::forbidden());
+    }
+
+    let identity: Identity = database
+        .query_opt(
+            GetIdentity::QUERY,
+            GetIdentity::params(&identity_id).as_array().as_slice(),
+        )
+        .instrument(query_span!(GetIdentity))
+        .await
+        .internal_server_error()?
+        .ok_or_else(ErrorResponse::unauthenticated)?
+        .parse()
+        .unwrap();
+
+    let response = if profile {
+        Response {
+            sub: token.claims.sub,
+            preferred_username: Some(identity.username),
+            name: Some(identity.display_name),
+            created: Some(identity.created),
+        }
+    } else {
+        Response {
+            sub: token.claims.sub,
+            preferred_username: None,
+            name: None,
+            created: None,
+        }
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
@@ -1,4 +1,5 @@
 use axum::{Router, extract::State, routing::get};
+use http::{HeaderName, header::CACHE_CONTROL};
 use serde::Serialize;
 use ts_api_helper::{
     Json,
@@ -6,7 +7,7 @@ use ts_api_helper::{
     webauthn::public_key_credential_creation_options::{PublicKeyParameters, RelyingParty},
 };
 
-use crate::ApiState;
+use crate::{ApiState, keyring::KEY_SET_MAX_AGE};
 
 pub fn router(state: ApiState) -> Router {
     Router::new()
@@ -16,11 +17,20 @@ pub fn router(state: ApiState) -> Router {
             "/.well-known/public-key-parameters.json",
             get(get_public_key_parameters),
         )
+        .route(
+            "/.well-known/openid-configuration",
+            get(get_openid_configuration),
+        )
         .with_state(state)
 }
 
-async fn get_jwks(State(ApiState { jwks_file, .. }): State<ApiState>) -> Json<JsonWebKeySet> {
-    Json(jwks_file)
+/// The key set may be cached for [`KEY_SET_MAX_AGE`], keys are published at least that long before
+/// they are promoted.
+async fn get_jwks(
+    State(ApiState { keyring, .. }): State<ApiState>,
+) -> ([(HeaderName, String); 1], Json<JsonWebKeySet>) {
+    let cache_control = format!("public, max-age={}", KEY_SET_MAX_AGE.as_secs());
+    ([(CACHE_CONTROL, cache_control)], Json(keyring.key_set()))
 }
 
 #[derive(Debug, Serialize)]
@@ -40,3 +50,54 @@ async fn get_relying_party(
 ) -> Json<RelyingParty> {
     Json(relying_party)
 }
+
+#[derive(Debug, Serialize)]
+struct OpenIdConfigurationResponse {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+    jwks_uri: String,
+    response_types_supported: Vec<&'static str>,
+    subject_types_supported: Vec<&'static str>,
+    id_token_signing_alg_values_supported: Vec<&'static str>,
+    scopes_supported: Vec<&'static str>,
+    token_endpoint_auth_methods_supported: Vec<&'static str>,
+    grant_types_supported: Vec<&'static str>,
+    code_challenge_methods_supported: Vec<&'static str>,
+    claims_supported: Vec<&'static str>,
+}
+
+async fn get_openid_configuration(
+    State(ApiState {
+        id_token_signer, ..
+    }): State<ApiState>,
+) -> Json<OpenIdConfigurationResponse> {
+    let issuer = id_token_signer.issuer();
+
+    Json(OpenIdConfigurationResponse {
+        issuer: issuer.to_string(),
+        authorization_endpoint: format!("{issuer}/authorize"),
+        token_endpoint: format!("{issuer}/token"),
+        userinfo_endpoint: format!("{issuer}/userinfo"),
+        jwks_uri: format!("{issuer}/.well-known/jwks.json"),
+        response_types_supported: vec!["code"],
+        subject_types_supported: vec!["public"],
+        id_token_signing_alg_values_supported: vec!["ES256"],
+        scopes_supported: vec!["openid", "profile"],
+        token_endpoint_auth_methods_supported: vec!["none"],
+        grant_types_supported: vec!["authorization_code"],
+        code_challenge_methods_supported: vec!["S256"],
+        claims_supported: vec![
+            "iss",
+            "sub",
+            "aud",
+            "exp",
+            "iat",
+            "auth_time",
+            "nonce",
+            "preferred_username",
+            "name",
+        ],
+    })
+}
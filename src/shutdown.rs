@@ -0,0 +1,53 @@
+use tokio::{
+    signal::unix::{SignalKind, signal},
+    sync::watch,
+};
+use ts_rust_helper::error::ErrorLogger;
+
+/// Resolves once the process has been asked to stop by `SIGTERM` or `SIGINT`.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    requested: watch::Receiver<bool>,
+}
+
+impl Shutdown {
+    /// Starts listening for the shutdown signals.
+    pub fn on_signal() -> Self {
+        let (sender, requested) = watch::channel(false);
+
+        tokio::spawn(async move {
+            tokio::select! {
+                () = wait_for(SignalKind::terminate()) => tracing::info!("received SIGTERM, shutting down"),
+                () = wait_for(SignalKind::interrupt()) => tracing::info!("received SIGINT, shutting down"),
+            }
+
+            let _ = sender.send(true);
+        });
+
+        Self { requested }
+    }
+
+    /// Waits until shutdown is requested.
+    pub async fn requested(&self) {
+        let mut requested = self.requested.clone();
+        if requested.wait_for(|requested| *requested).await.is_err() {
+            // The signal could not be listened for, so shutdown is never requested
+            core::future::pending::<()>().await;
+        }
+    }
+
+    /// Waits until shutdown is requested, owning a handle so the future can be spawned.
+    pub fn requested_owned(&self) -> impl Future<Output = ()> + Send + 'static {
+        let shutdown = self.clone();
+        async move { shutdown.requested().await }
+    }
+}
+
+async fn wait_for(kind: SignalKind) {
+    match signal(kind).log_error() {
+        Ok(mut signal) => {
+            signal.recv().await;
+        }
+        Err(_) => core::future::pending().await,
+    }
+}
@@ -1,30 +1,113 @@
+use core::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use reqwest::Client;
+use tokio_postgres::GenericClient;
+use tracing::Instrument;
 use ts_api_helper::{
     ApiKeyValidationConfig, ConnectionPool, HasApiKeyValidationConfig, HasHttpClient,
     token::{
-        JsonWebKeySetCache, SigningJsonWebKey,
+        JsonWebKeySetCache,
         extractor::{HasKeySetCache, HasRevocationEndpoint},
-        json_web_key::JsonWebKeySet,
     },
     webauthn::{
         self, challenge::Challenge, persisted_public_key::PersistedPublicKey,
         public_key_credential_creation_options::RelyingParty,
     },
 };
-use ts_sql_helper_lib::{FromRow, query};
+use ts_sql_helper_lib::{FromRow, perform_migrations_async, query};
+
+use crate::{
+    client_info::TrustedProxies,
+    config::Config,
+    id_token::IdTokenSigner,
+    keyring::{Keyring, KeyringError},
+    metrics::{Metrics, MetricsError},
+    models::ChallengePurpose,
+    rate_limit::RateLimiter,
+    telemetry::query_span,
+    username_protection::{UsernameProtection, UsernameProtectionError},
+};
+
+type BoxError = Box<dyn core::error::Error + Send + Sync + 'static>;
 
 #[derive(Debug, Clone)]
 pub struct ApiState {
     pub pool: ConnectionPool,
-    pub jwks_file: JsonWebKeySet,
-    pub signing_jwk: Arc<SigningJsonWebKey>,
+    pub keyring: Keyring,
     pub jwks_cache: JsonWebKeySetCache,
     pub api_key_config: ApiKeyValidationConfig,
     pub http_client: Client,
     pub revocation_endpoint: String,
     pub relying_party: RelyingParty,
+    /// The origins challenges are issued to and verified for.
+    pub webauthn_origins: Vec<String>,
+    pub login_page_url: String,
+    pub id_token_signer: Arc<IdTokenSigner>,
+    pub metrics: Arc<Metrics>,
+    /// Set once the database migrations have been performed.
+    pub migrations_complete: Arc<AtomicBool>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub trusted_proxies: TrustedProxies,
+    pub username_protection: Arc<UsernameProtection>,
+}
+
+impl ApiState {
+    pub fn new(config: &Config, pool: ConnectionPool) -> Result<Self, StateError> {
+        let keyring = Keyring::load(&config.signing_keys).map_err(StateError::keyring)?;
+        let jwks_cache = config.token_validating_config.jwks_cache();
+        let api_key_config = config.api_key_validation_config.clone();
+        let http_client = config
+            .http_client_config
+            .http_client()
+            .map_err(|source| StateError::http_client(Box::new(source)))?;
+        let revocation_endpoint = config.token_validating_config.revocation_endpoint.clone();
+        let relying_party = config.relying_party.clone();
+        let webauthn_origins = config.webauthn_origins.clone();
+        let login_page_url = config.login_page_url.clone();
+        let id_token_signer = Arc::new(config.open_id_config.id_token_signer());
+        let metrics = Arc::new(Metrics::new(pool.clone()).map_err(StateError::metrics)?);
+        let migrations_complete = Arc::new(AtomicBool::new(false));
+        let rate_limiter = Arc::new(RateLimiter::new(
+            &config.rate_limit_config,
+            config.api_key_validation_config.header.clone(),
+        ));
+        let trusted_proxies =
+            TrustedProxies(Arc::from(config.listen_config.trusted_proxies.as_slice()));
+        let username_protection = Arc::new(
+            UsernameProtection::new(&config.username_protection_config)
+                .map_err(StateError::username_protection)?,
+        );
+
+        Ok(Self {
+            pool,
+            keyring,
+            jwks_cache,
+            api_key_config,
+            http_client,
+            revocation_endpoint,
+            relying_party,
+            webauthn_origins,
+            login_page_url,
+            id_token_signer,
+            metrics,
+            migrations_complete,
+            rate_limiter,
+            trusted_proxies,
+            username_protection,
+        })
+    }
+
+    /// Migrates the database to the latest version, after which the service reports ready.
+    pub async fn migrate(&self) -> Result<(), StateError> {
+        let connection = self.pool.get().await.map_err(StateError::pool_connection)?;
+        perform_migrations_async(connection.client(), None)
+            .await
+            .map_err(|source| StateError::migrate(Box::new(source)))?;
+        self.migrations_complete.store(true, Ordering::Release);
+
+        Ok(())
+    }
 }
 
 impl HasKeySetCache for ApiState {
@@ -50,6 +133,7 @@ impl HasRevocationEndpoint for ApiState {
 
 query! {
     name: TakeChallenge,
+    row: {origin: String, purpose: String, act: Option<String>},
     query: r#"
         DELETE FROM
             challenges
@@ -59,6 +143,8 @@ query! {
             challenge,
             identity_id,
             origin,
+            purpose,
+            act,
             issued,
             expires;"#
 }
@@ -82,11 +168,32 @@ query! {
             raw_id = $1::BYTEA;"#
 }
 
-impl webauthn::verification::Verifier for ApiState {
+/// Verifies WebAuthN responses for one ceremony, so a challenge issued for another ceremony can't
+/// complete it.
+#[derive(Debug)]
+pub struct CeremonyVerifier<'a> {
+    state: &'a ApiState,
+    purpose: ChallengePurpose,
+}
+
+impl ApiState {
+    /// The verifier for credentials responding to a challenge issued for the purpose.
+    pub fn verifier(&self, purpose: ChallengePurpose) -> CeremonyVerifier<'_> {
+        CeremonyVerifier {
+            state: self,
+            purpose,
+        }
+    }
+}
+
+impl webauthn::verification::Verifier for CeremonyVerifier<'_> {
     type Error = VerifierError;
 
+    /// Takes the challenge, which is consumed even if it was issued for another purpose or to an
+    /// origin that is no longer allowed.
     async fn get_challenge(&self, challenge: &[u8]) -> Result<Option<Challenge>, Self::Error> {
         let connection = self
+            .state
             .pool
             .get()
             .await
@@ -97,15 +204,37 @@ impl webauthn::verification::Verifier for ApiState {
                 TakeChallenge::QUERY,
                 TakeChallenge::params(challenge).as_array().as_slice(),
             )
+            .instrument(query_span!(TakeChallenge))
             .await
             .map_err(VerifierError::query_challenge)?;
 
-        match row {
-            Some(row) => Ok(Some(
-                Challenge::from_row(&row).map_err(VerifierError::challenge_from_row)?,
-            )),
-            None => Ok(None),
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let TakeChallengeRow {
+            origin,
+            purpose,
+            act,
+        } = TakeChallengeRow::from_row(&row).map_err(VerifierError::challenge_from_row)?;
+
+        let purpose = ChallengePurpose::from_columns(&purpose, act);
+        if purpose.as_ref() != Some(&self.purpose) {
+            tracing::warn!(
+                expected = self.purpose.as_str(),
+                "rejected a challenge issued for another purpose"
+            );
+            return Ok(None);
         }
+
+        if !self.state.webauthn_origins.contains(&origin) {
+            tracing::warn!(origin, "rejected a challenge issued to a disallowed origin");
+            return Ok(None);
+        }
+
+        Ok(Some(
+            Challenge::from_row(&row).map_err(VerifierError::challenge_from_row)?,
+        ))
     }
 
     async fn get_public_key(
@@ -113,6 +242,7 @@ impl webauthn::verification::Verifier for ApiState {
         raw_id: &[u8],
     ) -> Result<Option<PersistedPublicKey>, Self::Error> {
         let connection = self
+            .state
             .pool
             .get()
             .await
@@ -123,6 +253,7 @@ impl webauthn::verification::Verifier for ApiState {
                 GetPublicKey::QUERY,
                 GetPublicKey::params(raw_id).as_array().as_slice(),
             )
+            .instrument(query_span!(GetPublicKey))
             .await
             .map_err(VerifierError::query_public_key)?;
 
@@ -135,7 +266,7 @@ impl webauthn::verification::Verifier for ApiState {
     }
 
     fn relying_party_id(&self) -> &str {
-        &self.relying_party.id
+        &self.state.relying_party.id
     }
 }
 /// Error variants for WebAuthN response verification.
@@ -212,3 +343,86 @@ impl VerifierError {
         Self::ChallengeFromRow { source }
     }
 }
+
+/// Error variants for creating the API state.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum StateError {
+    #[non_exhaustive]
+    Keyring { source: KeyringError },
+
+    #[non_exhaustive]
+    HttpClient { source: BoxError },
+
+    #[non_exhaustive]
+    Metrics { source: MetricsError },
+
+    #[non_exhaustive]
+    UsernameProtection { source: UsernameProtectionError },
+
+    #[non_exhaustive]
+    PoolConnection {
+        source: bb8::RunError<tokio_postgres::Error>,
+    },
+
+    #[non_exhaustive]
+    Migrate { source: BoxError },
+}
+impl core::fmt::Display for StateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Keyring { .. } => write!(f, "could not load the keyring"),
+            Self::HttpClient { .. } => write!(f, "could not create the HTTP client"),
+            Self::Metrics { .. } => write!(f, "could not create the metrics"),
+            Self::UsernameProtection { .. } => {
+                write!(f, "could not set up the username protection")
+            }
+            Self::PoolConnection { .. } => write!(f, "could not get a database connection"),
+            Self::Migrate { .. } => write!(f, "could not migrate the database"),
+        }
+    }
+}
+impl core::error::Error for StateError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Keyring { source, .. } => Some(source),
+            Self::HttpClient { source, .. } => Some(source.as_ref()),
+            Self::Metrics { source, .. } => Some(source),
+            Self::UsernameProtection { source, .. } => Some(source),
+            Self::PoolConnection { source, .. } => Some(source),
+            Self::Migrate { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+impl StateError {
+    #[allow(missing_docs)]
+    pub fn keyring(source: KeyringError) -> Self {
+        Self::Keyring { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn http_client(source: BoxError) -> Self {
+        Self::HttpClient { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn metrics(source: MetricsError) -> Self {
+        Self::Metrics { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn username_protection(source: UsernameProtectionError) -> Self {
+        Self::UsernameProtection { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn pool_connection(source: bb8::RunError<tokio_postgres::Error>) -> Self {
+        Self::PoolConnection { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn migrate(source: BoxError) -> Self {
+        Self::Migrate { source }
+    }
+}
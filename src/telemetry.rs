@@ -0,0 +1,211 @@
+use core::time::Duration;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    response::Response,
+};
+use http::HeaderName;
+use opentelemetry::{global, trace::TracerProvider};
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{Resource, propagation::TraceContextPropagator, trace::SdkTracerProvider};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use ts_rust_helper::error::ErrorLogger;
+
+/// The header carrying the ID of a request, generated if the client did not send one.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LogFormat {
+    /// Human readable lines.
+    #[default]
+    Text,
+
+    /// One JSON object per line, including the fields of the enclosing spans.
+    Json,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryConfig {
+    /// The format logs are written to stdout in.
+    pub log_format: LogFormat,
+
+    /// The OTLP HTTP endpoint to export traces to, such as `http://localhost:4318/v1/traces`.
+    /// Traces are not exported when this is not set.
+    pub otlp_endpoint: Option<String>,
+
+    /// The service name attached to exported traces.
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            log_format: LogFormat::Text,
+            otlp_endpoint: None,
+            service_name: "identity-service".to_string(),
+        }
+    }
+}
+
+/// Exports traces until dropped, at which point any pending spans are flushed.
+#[derive(Debug)]
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            provider.shutdown().log_error();
+        }
+    }
+}
+
+/// Installs the global tracing subscriber, exporting traces over OTLP when configured.
+pub fn init(level: Level, config: Option<&TelemetryConfig>) -> Result<Telemetry, TelemetryError> {
+    let provider = match config {
+        Some(TelemetryConfig {
+            otlp_endpoint: Some(endpoint),
+            service_name,
+            ..
+        }) => {
+            let exporter = SpanExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .build()
+                .map_err(TelemetryError::exporter)?;
+
+            Some(
+                SdkTracerProvider::builder()
+                    .with_batch_exporter(exporter)
+                    .with_resource(
+                        Resource::builder()
+                            .with_service_name(service_name.clone())
+                            .build(),
+                    )
+                    .build(),
+            )
+        }
+        _ => None,
+    };
+
+    // Incoming `traceparent` headers are continued by the request spans
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let otel_layer = provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+    });
+
+    let log_format = config.map_or(LogFormat::Text, |config| config.log_format);
+    let (text_layer, json_layer) = match log_format {
+        LogFormat::Text => (Some(tracing_subscriber::fmt::layer()), None),
+        LogFormat::Json => (None, Some(tracing_subscriber::fmt::layer().json())),
+    };
+
+    tracing_subscriber::registry()
+        .with(text_layer)
+        .with(json_layer)
+        .with(otel_layer)
+        .with(LevelFilter::from_level(level))
+        .init();
+
+    Ok(Telemetry { provider })
+}
+
+/// The span for handling a request, continuing the trace from the `traceparent` header if present.
+///
+/// The request ID is recorded on the span so every log and error within the request carries it.
+pub fn request_span(request: &Request) -> Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(request.uri().path(), MatchedPath::as_str);
+
+    let span = tracing::info_span!(
+        "request",
+        otel.name = %format!("{} {route}", request.method()),
+        otel.kind = "server",
+        http.request.method = %request.method(),
+        http.route = route,
+        request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|request_id| request_id.to_str().ok()),
+    );
+
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    span.set_parent(parent);
+
+    span
+}
+
+/// Logs the response within the request span, server errors are logged as errors.
+pub fn log_response(response: &Response, latency: Duration, _: &Span) {
+    let status = response.status();
+    let latency = latency.as_millis();
+
+    if status.is_server_error() {
+        tracing::error!(%status, latency, "request failed");
+    } else {
+        tracing::debug!(%status, latency, "request finished");
+    }
+}
+
+/// The span for a query created with `query!`, instrumenting the query's future.
+///
+/// ```ignore
+/// database
+///     .query_opt(GetIdentity::QUERY, GetIdentity::params(&id).as_array().as_slice())
+///     .instrument(query_span!(GetIdentity))
+///     .await
+/// ```
+macro_rules! query_span {
+    ($query:ident) => {
+        tracing::info_span!(
+            "query",
+            otel.name = stringify!($query),
+            otel.kind = "client",
+            db.system.name = "postgresql",
+            db.query.text = $query::QUERY,
+        )
+    };
+}
+pub(crate) use query_span;
+
+/// Error variants for setting up telemetry.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum TelemetryError {
+    #[non_exhaustive]
+    Exporter { source: ExporterBuildError },
+}
+impl core::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::Exporter { .. } => write!(f, "could not create the OTLP exporter"),
+        }
+    }
+}
+impl core::error::Error for TelemetryError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::Exporter { source, .. } => Some(source),
+        }
+    }
+}
+impl TelemetryError {
+    #[allow(missing_docs)]
+    pub fn exporter(source: ExporterBuildError) -> Self {
+        Self::Exporter { source }
+    }
+}
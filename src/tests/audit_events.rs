@@ -0,0 +1,251 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::tests::harness::{TestServer, bearer, claims, credential_id};
+
+#[tokio::test]
+async fn records_history_of_identity() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("audited").await;
+    let response = server.login(&mut identity).await;
+    let login = bearer(response.headers());
+
+    let response = server
+        .request(Method::GET, "/audit-events")
+        .header(AUTHORIZATION, &login)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: Value = response.json().await.unwrap();
+    assert!(body.get("nextCursor").is_none());
+
+    // Newest first
+    let events: Vec<_> = body["auditEvents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| {
+            assert_eq!("success", event["outcome"]);
+            (event["kind"].as_str().unwrap(), event["target"].clone())
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("token_issued", claims(&login)["tid"].clone()),
+            ("token_issued", claims(&identity.bearer)["tid"].clone()),
+            (
+                "passkey_registered",
+                Value::from(credential_id(&identity.authenticator))
+            ),
+            ("challenge_issued", Value::from("registration")),
+            ("token_issued", events[4].1.clone()),
+            ("identity_created", Value::Null),
+        ],
+        events
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn records_options_challenges_and_denied_passkey_deletion() {
+    let server = TestServer::start().await;
+    let identity = server.register("challenged").await;
+    let public_key_id = credential_id(&identity.authenticator);
+
+    for path in [
+        "/credential-creation-options",
+        "/credential-request-options?act=DELETE%20/sessions",
+    ] {
+        let response = server
+            .request(Method::GET, path)
+            .header(AUTHORIZATION, &identity.bearer)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    let response = server
+        .request(Method::DELETE, &format!("/public-keys/{public_key_id}"))
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = server
+        .request(Method::GET, "/audit-events")
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    let events: Vec<_> = body["auditEvents"].as_array().unwrap()[..3]
+        .iter()
+        .map(|event| {
+            (
+                event["kind"].as_str().unwrap(),
+                event["target"].clone(),
+                event["outcome"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("passkey_deleted", Value::from(public_key_id), "denied"),
+            ("challenge_issued", Value::from("consent"), "success"),
+            ("challenge_issued", Value::from("registration"), "success"),
+        ],
+        events
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn pages_through_history() {
+    let server = TestServer::start().await;
+    let identity = server.register("paged").await;
+
+    let mut cursor: Option<i64> = None;
+    let mut kinds = vec![];
+    loop {
+        let mut path = "/audit-events?limit=2".to_string();
+        if let Some(cursor) = cursor {
+            path.push_str(&format!("&cursor={cursor}"));
+        }
+
+        let response = server
+            .request(Method::GET, &path)
+            .header(AUTHORIZATION, &identity.bearer)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body: Value = response.json().await.unwrap();
+        let events = body["auditEvents"].as_array().unwrap();
+        assert!(events.len() <= 2);
+        kinds.extend(
+            events
+                .iter()
+                .map(|event| event["kind"].as_str().unwrap().to_string()),
+        );
+
+        match body["nextCursor"].as_i64() {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+
+    assert_eq!(
+        vec![
+            "token_issued",
+            "passkey_registered",
+            "challenge_issued",
+            "token_issued",
+            "identity_created"
+        ],
+        kinds
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn records_denied_actions() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("denied").await;
+
+    // A common token is not consent to delete the identity
+    let response = server
+        .request(Method::DELETE, &format!("/identities/{}", identity.id))
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let response = server.login(&mut identity).await;
+    let login = bearer(response.headers());
+
+    let response = server
+        .request(Method::GET, "/audit-events?limit=2")
+        .header(AUTHORIZATION, &login)
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    let denied = &body["auditEvents"][1];
+    assert_eq!("identity_deleted", denied["kind"]);
+    assert_eq!("denied", denied["outcome"]);
+    assert_eq!(identity.id.as_str(), denied["target"]);
+    assert!(denied["ipAddress"].is_string());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn records_introspection() {
+    let server = TestServer::start().await;
+    let identity = server.register("introspected").await;
+    let token = identity.bearer.split_once(' ').unwrap().1;
+
+    for token in [token, "not-a-token"] {
+        let response = server
+            .request_without_origin(Method::POST, "/introspect")
+            .form(&[("token", token)])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    let events: Vec<(Option<String>, String)> = server
+        .database
+        .client
+        .query(
+            "SELECT target, outcome FROM audit_events WHERE kind = 'token_introspected' ORDER BY id;",
+            &[],
+        )
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                claims(&identity.bearer)["tid"].as_str().map(str::to_string),
+                "success".to_string()
+            ),
+            (None, "failed".to_string()),
+        ],
+        events
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_invalid_limit() {
+    let server = TestServer::start().await;
+    let identity = server.register("limited").await;
+
+    for limit in [0, 101] {
+        let response = server
+            .request(Method::GET, &format!("/audit-events?limit={limit}"))
+            .header(AUTHORIZATION, &identity.bearer)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    server.stop().await;
+}
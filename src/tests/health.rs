@@ -0,0 +1,87 @@
+use http::StatusCode;
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::{telemetry::REQUEST_ID_HEADER, tests::harness::TestServer};
+
+#[tokio::test]
+async fn reports_alive_without_api_key() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/healthz")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(json!({ "status": "pass" }), body);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn reports_ready_with_each_check() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/readyz")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(
+        json!({
+            "status": "pass",
+            "checks": {
+                "database": "pass",
+                "migrations": "pass",
+                "signingKey": "pass",
+            },
+        }),
+        body
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn reports_not_ready_before_migrations() {
+    let server = TestServer::start_without_migrations().await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/readyz")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!("fail", body["status"]);
+    assert_eq!("fail", body["checks"]["migrations"]);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn generates_and_propagates_request_ids() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/healthz")
+        .send()
+        .await
+        .unwrap();
+    let generated = response.headers().get(REQUEST_ID_HEADER).unwrap();
+    assert!(uuid::Uuid::parse_str(generated.to_str().unwrap()).is_ok());
+
+    let response = server
+        .request_without_api_key(Method::GET, "/healthz")
+        .header(REQUEST_ID_HEADER, "upstream-request")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!("upstream-request", response.headers()[REQUEST_ID_HEADER]);
+
+    server.stop().await;
+}
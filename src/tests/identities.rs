@@ -0,0 +1,213 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::tests::harness::{TestServer, claims};
+
+#[tokio::test]
+async fn creates_identity_with_provisioning_token() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request(Method::POST, "/identities")
+        .json(&json!({ "username": "provisioned", "displayName": "Provisioned" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let bearer = response.headers()[AUTHORIZATION]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let identity: Value = response.json().await.unwrap();
+    assert_eq!("provisioned", identity["username"]);
+    assert_eq!(identity["id"], claims(&bearer)["sub"]);
+    assert_eq!("provisioning", claims(&bearer)["typ"]);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_invalid_and_duplicate_usernames() {
+    let server = TestServer::start().await;
+    server.register("taken").await;
+
+    for username in ["abc", "taken"] {
+        let response = server
+            .request(Method::POST, "/identities")
+            .json(&json!({ "username": username, "displayName": "Display Name" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status(), "{username}");
+    }
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn gets_only_own_identity() {
+    let server = TestServer::start().await;
+    let identity = server.register("getter").await;
+    let other = server.register("other").await;
+
+    let response = server
+        .request(Method::GET, &format!("/identities/{}", identity.id))
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!("getter", body["username"]);
+    assert_eq!(Value::Null, body["expires"]);
+
+    let response = server
+        .request(Method::GET, &format!("/identities/{}", other.id))
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let response = server
+        .request(Method::GET, &format!("/identities/{}", identity.id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn patches_display_name_and_username_with_consent() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("patcher").await;
+    let path = format!("/identities/{}", identity.id);
+
+    let response = server
+        .request(Method::PATCH, &path)
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({ "displayName": "New Name" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!("New Name", body["displayName"]);
+
+    let response = server
+        .request(Method::PATCH, &path)
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({ "username": "renamed" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let consent = server
+        .consent(&mut identity, &format!("PATCH {path}"))
+        .await;
+    let response = server
+        .request(Method::PATCH, &path)
+        .header(AUTHORIZATION, &consent)
+        .json(&json!({ "username": "renamed" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!("renamed", body["username"]);
+
+    // Consent tokens are single use
+    let response = server
+        .request(Method::PATCH, &path)
+        .header(AUTHORIZATION, &consent)
+        .json(&json!({ "username": "renamed-again" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_consent_for_display_name_without_consuming_it() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("consenting").await;
+    let other = server.register("other").await;
+    let path = format!("/identities/{}", identity.id);
+
+    let consent = server
+        .consent(&mut identity, &format!("PATCH {path}"))
+        .await;
+
+    let response = server
+        .request(Method::PATCH, &path)
+        .header(AUTHORIZATION, &consent)
+        .json(&json!({ "displayName": "New Name" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let response = server
+        .request(Method::PATCH, &format!("/identities/{}", other.id))
+        .header(AUTHORIZATION, &consent)
+        .json(&json!({ "username": "taken-over" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    // The rejected requests did not consume the consent
+    let response = server
+        .request(Method::PATCH, &path)
+        .header(AUTHORIZATION, &consent)
+        .json(&json!({ "username": "renamed" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn deletes_identity_with_consent() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("deleted").await;
+    let path = format!("/identities/{}", identity.id);
+
+    let response = server
+        .request(Method::DELETE, &path)
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let consent = server
+        .consent(&mut identity, &format!("DELETE {path}"))
+        .await;
+    let response = server
+        .request(Method::DELETE, &path)
+        .header(AUTHORIZATION, &consent)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let response = server
+        .request(Method::GET, &path)
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    server.stop().await;
+}
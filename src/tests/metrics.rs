@@ -0,0 +1,110 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+
+use crate::tests::harness::TestServer;
+
+async fn scrape(server: &TestServer) -> String {
+    let response = server
+        .request_without_origin(Method::GET, "/metrics")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.text().await.unwrap()
+}
+
+#[tokio::test]
+async fn counts_ceremonies_and_tokens() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("measured").await;
+
+    let response = server.login(&mut identity).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let metrics = scrape(&server).await;
+    for line in [
+        r#"identity_service_webauthn_verifications_total{ceremony="registration",result="valid"} 1"#,
+        r#"identity_service_webauthn_verifications_total{ceremony="login",result="valid"} 1"#,
+        r#"identity_service_tokens_issued_total{typ="provisioning"} 1"#,
+        r#"identity_service_tokens_issued_total{typ="common"} 2"#,
+        r#"identity_service_http_requests_total{method="POST",route="/tokens",status="201"} 1"#,
+    ] {
+        assert!(
+            metrics.lines().any(|metric| metric == line),
+            "missing `{line}`"
+        );
+    }
+
+    server.stop().await;
+}
+
+/// The value of a metric without labels other than those in `name`.
+fn value(metrics: &str, name: &str) -> u64 {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+        .map_or(0, |value| value.parse().unwrap())
+}
+
+#[tokio::test]
+async fn requires_api_key() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/metrics")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn counts_revoked_sessions() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("revoked").await;
+    server.login(&mut identity).await;
+    let consent = server.consent(&mut identity, "DELETE /sessions").await;
+
+    let revoked_tokens = r#"identity_service_revocations_total{kind="token"}"#;
+    let before = value(&scrape(&server).await, revoked_tokens);
+
+    let response = server
+        .request(Method::DELETE, "/sessions")
+        .header(AUTHORIZATION, &consent)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    // The consent is revoked on receipt, then at least the registration and login sessions
+    let after = value(&scrape(&server).await, revoked_tokens);
+    assert!(
+        after - before >= 3,
+        "counted {} revocations",
+        after - before
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn reports_pool_statistics() {
+    let server = TestServer::start().await;
+
+    let metrics = scrape(&server).await;
+    assert!(
+        metrics
+            .lines()
+            .any(|line| line.starts_with("identity_service_pool_connections "))
+    );
+    assert!(
+        metrics
+            .lines()
+            .any(|line| line.starts_with("identity_service_pool_gets_total{result=\"direct\"}"))
+    );
+
+    server.stop().await;
+}
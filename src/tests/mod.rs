@@ -0,0 +1,19 @@
+//! End to end tests of the API against a local PostgreSQL server.
+
+mod harness;
+
+mod audit_events;
+mod health;
+mod identities;
+mod metrics;
+mod oauth;
+mod public_keys;
+mod rate_limit;
+mod refresh_tokens;
+mod revoked_tokens;
+mod sessions;
+mod tokens;
+mod username_protection;
+mod well_known;
+
+pub use harness::TestDatabase;
@@ -0,0 +1,339 @@
+use base64ct::{Base64UrlUnpadded, Encoding};
+use http::{
+    StatusCode,
+    header::{AUTHORIZATION, LOCATION},
+};
+use reqwest::{Method, Url};
+use serde_json::{Value, json};
+use ts_api_helper::token::json_web_key::JsonWebKeySet;
+
+use crate::{
+    jwt::verify_token,
+    routes::oauth::s256_code_challenge,
+    tests::harness::{TestIdentity, TestServer, claims},
+};
+
+const REDIRECT_URI: &str = "https://client.example.com/callback";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+async fn register_client(server: &TestServer) {
+    server
+        .database
+        .client
+        .execute(
+            "INSERT INTO clients (id, display_name, redirect_uris) VALUES ('client', 'Client', ARRAY[$1::VARCHAR]);",
+            &[&REDIRECT_URI],
+        )
+        .await
+        .unwrap();
+}
+
+fn query_value(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Runs the authorization code flow, returning the token endpoint response.
+async fn authorize(server: &TestServer, identity: &TestIdentity, scope: &str) -> Value {
+    let response = server
+        .request_without_api_key(Method::GET, "/authorize")
+        .query(&[
+            ("response_type", "code"),
+            ("client_id", "client"),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_challenge", &s256_code_challenge(CODE_VERIFIER)),
+            ("code_challenge_method", "S256"),
+            ("state", "client-state"),
+            ("scope", scope),
+            ("nonce", "client-nonce"),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_redirection());
+    let login_url = Url::parse(response.headers()[LOCATION].to_str().unwrap()).unwrap();
+    let authorization_request = query_value(&login_url, "authorizationRequest").unwrap();
+
+    let response = server
+        .request(Method::POST, "/authorization-codes")
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({ "authorizationRequest": authorization_request }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body: Value = response.json().await.unwrap();
+    let redirect_uri = Url::parse(body["redirectUri"].as_str().unwrap()).unwrap();
+    assert!(redirect_uri.as_str().starts_with(REDIRECT_URI));
+    assert_eq!(
+        Some("client-state".to_string()),
+        query_value(&redirect_uri, "state")
+    );
+    let code = query_value(&redirect_uri, "code").unwrap();
+
+    let response = server
+        .request_without_api_key(Method::POST, "/token")
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            ("client_id", "client"),
+            ("code_verifier", CODE_VERIFIER),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    // The code is single use
+    let replay = server
+        .request_without_api_key(Method::POST, "/token")
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            ("client_id", "client"),
+            ("code_verifier", CODE_VERIFIER),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, replay.status());
+    let error: Value = replay.json().await.unwrap();
+    assert_eq!("invalid_grant", error["error"]);
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn rejects_unregistered_redirect_uri() {
+    let server = TestServer::start().await;
+    register_client(&server).await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/authorize")
+        .query(&[
+            ("response_type", "code"),
+            ("client_id", "client"),
+            ("redirect_uri", "https://attacker.example.com/callback"),
+            ("code_challenge", &s256_code_challenge(CODE_VERIFIER)),
+            ("code_challenge_method", "S256"),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    // Errors are sent to the client once the redirect URI is verified
+    let response = server
+        .request_without_api_key(Method::GET, "/authorize")
+        .query(&[
+            ("response_type", "token"),
+            ("client_id", "client"),
+            ("redirect_uri", REDIRECT_URI),
+            ("state", "client-state"),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_redirection());
+    let redirect_uri = Url::parse(response.headers()[LOCATION].to_str().unwrap()).unwrap();
+    assert_eq!(
+        Some("unsupported_response_type".to_string()),
+        query_value(&redirect_uri, "error")
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn issues_tokens_for_authorization_code() {
+    let server = TestServer::start().await;
+    register_client(&server).await;
+    let identity = server.register("delegator").await;
+
+    let tokens = authorize(&server, &identity, "openid profile").await;
+    assert_eq!("Bearer", tokens["token_type"]);
+    assert_eq!("openid profile", tokens["scope"]);
+
+    let access_token = tokens["access_token"].as_str().unwrap();
+    assert_eq!(identity.id, claims(access_token)["sub"]);
+
+    let id_token = claims(tokens["id_token"].as_str().unwrap());
+    assert_eq!(identity.id, id_token["sub"]);
+    assert_eq!("client", id_token["aud"]);
+    assert_eq!("client-nonce", id_token["nonce"]);
+    assert_eq!(identity.username, id_token["preferred_username"]);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn signs_tokens_with_the_published_key() {
+    let server = TestServer::start().await;
+    register_client(&server).await;
+    let identity = server.register("verified").await;
+
+    let tokens = authorize(&server, &identity, "openid").await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/.well-known/jwks.json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let key_set: JsonWebKeySet = response.json().await.unwrap();
+
+    for token in [&tokens["id_token"], &tokens["access_token"]] {
+        let token = token.as_str().unwrap();
+
+        // ES256 signatures are the raw 64 byte `r || s`, not DER
+        let (_, signature) = token.rsplit_once('.').unwrap();
+        assert_eq!(64, Base64UrlUnpadded::decode_vec(signature).unwrap().len());
+
+        let verified: Value = verify_token(&key_set, token).unwrap();
+        assert_eq!(identity.id, verified["sub"]);
+    }
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn scopes_userinfo_to_granted_scope() {
+    let server = TestServer::start().await;
+    register_client(&server).await;
+    let identity = server.register("userinfo").await;
+
+    let tokens = authorize(&server, &identity, "openid profile").await;
+    let bearer = format!("bearer {}", tokens["access_token"].as_str().unwrap());
+    let response = server
+        .request_without_api_key(Method::GET, "/userinfo")
+        .header(AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let userinfo: Value = response.json().await.unwrap();
+    assert_eq!(identity.id, userinfo["sub"]);
+    assert_eq!(identity.username, userinfo["preferred_username"]);
+
+    let tokens = authorize(&server, &identity, "openid").await;
+    assert!(tokens["id_token"].is_string());
+    let bearer = format!("bearer {}", tokens["access_token"].as_str().unwrap());
+    let response = server
+        .request_without_api_key(Method::POST, "/userinfo")
+        .header(AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let userinfo: Value = response.json().await.unwrap();
+    assert_eq!(Value::Null, userinfo["preferred_username"]);
+
+    let tokens = authorize(&server, &identity, "email").await;
+    assert_eq!(Value::Null, tokens["id_token"]);
+    let bearer = format!("bearer {}", tokens["access_token"].as_str().unwrap());
+    let response = server
+        .request_without_api_key(Method::GET, "/userinfo")
+        .header(AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn restricts_client_tokens_to_the_client() {
+    let server = TestServer::start().await;
+    register_client(&server).await;
+    let identity = server.register("restricted").await;
+
+    let tokens = authorize(&server, &identity, "openid profile").await;
+    let access_token = tokens["access_token"].as_str().unwrap();
+    assert_eq!("client", claims(access_token)["aud"]);
+    assert_eq!("openid profile", claims(access_token)["scope"]);
+
+    let bearer = format!("bearer {access_token}");
+    let response = server
+        .request(Method::PATCH, &format!("/identities/{}", identity.id))
+        .header(AUTHORIZATION, &bearer)
+        .json(&json!({ "displayName": "Renamed By Client" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let response = server
+        .request(Method::GET, "/sessions")
+        .header(AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let response = server
+        .request_without_origin(Method::POST, "/introspect")
+        .form(&[("token", access_token)])
+        .send()
+        .await
+        .unwrap();
+    let introspection: Value = response.json().await.unwrap();
+    assert_eq!(true, introspection["active"]);
+    assert_eq!("client", introspection["client_id"]);
+    assert_eq!("openid profile", introspection["scope"]);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn introspects_tokens() {
+    let server = TestServer::start().await;
+    let identity = server.register("introspected").await;
+    let token = identity.bearer.split_once(' ').unwrap().1;
+
+    let response = server
+        .request_without_origin(Method::POST, "/introspect")
+        .form(&[("token", token)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let introspection: Value = response.json().await.unwrap();
+    assert_eq!(true, introspection["active"]);
+    assert_eq!(identity.id, introspection["sub"]);
+    assert_eq!(claims(token)["tid"], introspection["jti"]);
+    assert_eq!(claims(token)["exp"], introspection["exp"]);
+
+    let response = server
+        .request(Method::POST, "/revoked-tokens")
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    for token in [token, "not-a-token"] {
+        let response = server
+            .request_without_origin(Method::POST, "/introspect")
+            .form(&[("token", token)])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let introspection: Value = response.json().await.unwrap();
+        assert_eq!(json!({ "active": false }), introspection);
+    }
+
+    let response = server
+        .request_without_api_key(Method::POST, "/introspect")
+        .form(&[("token", token)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
@@ -0,0 +1,217 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::{
+    authenticator::{Algorithm, SoftwareAuthenticator},
+    tests::harness::{TestServer, credential_id},
+};
+
+#[tokio::test]
+async fn registers_additional_eddsa_passkey() {
+    let server = TestServer::start().await;
+    let identity = server.register("multikey").await;
+
+    let mut authenticator = SoftwareAuthenticator::new(Algorithm::EdDsa).unwrap();
+    let challenge = server
+        .create_challenge(
+            json!({ "purpose": "registration" }),
+            Some((&identity.id, &identity.bearer)),
+        )
+        .await;
+    let response = server
+        .request(Method::POST, "/public-keys")
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({
+            "displayName": "Second Passkey",
+            "credential": server.create_credential(&mut authenticator, &challenge),
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+    // Only provisioning tokens are exchanged for a common token
+    assert!(response.headers().get(AUTHORIZATION).is_none());
+
+    let response = server
+        .request(Method::GET, "/public-keys")
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(2, body["publicKeys"].as_array().unwrap().len());
+
+    // The new passkey can be used to log in
+    let response = server
+        .request_token(&mut authenticator, json!({ "typ": "common" }), None)
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn tracks_signature_counter() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("counted").await;
+
+    for _ in 0..3 {
+        let response = server.login(&mut identity).await;
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    let response = server
+        .request(Method::GET, "/public-keys")
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(
+        i64::from(identity.authenticator.counter()),
+        body["publicKeys"][0]["signatureCounter"].as_i64().unwrap()
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_empty_display_name() {
+    let server = TestServer::start().await;
+    let identity = server.register("unnamed").await;
+
+    let mut authenticator = SoftwareAuthenticator::new(Algorithm::Es256).unwrap();
+    let challenge = server
+        .create_challenge(
+            json!({ "purpose": "registration" }),
+            Some((&identity.id, &identity.bearer)),
+        )
+        .await;
+    let response = server
+        .request(Method::POST, "/public-keys")
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({
+            "displayName": "",
+            "credential": server.create_credential(&mut authenticator, &challenge),
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn renames_only_own_passkey() {
+    let server = TestServer::start().await;
+    let identity = server.register("renamer").await;
+    let other = server.register("bystander").await;
+
+    let response = server
+        .request(
+            Method::PUT,
+            &format!(
+                "/public-keys/{}/displayName",
+                credential_id(&identity.authenticator)
+            ),
+        )
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({ "displayName": "Renamed Passkey" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!("Renamed Passkey", body["displayName"]);
+
+    let response = server
+        .request(
+            Method::PUT,
+            &format!(
+                "/public-keys/{}/displayName",
+                credential_id(&other.authenticator)
+            ),
+        )
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({ "displayName": "Stolen Passkey" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn deletes_passkey_with_consent_but_keeps_the_last() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("pruner").await;
+    let first_key = credential_id(&identity.authenticator);
+    let path = format!("/public-keys/{first_key}");
+
+    let consent = server
+        .consent(&mut identity, &format!("DELETE {path}"))
+        .await;
+    let response = server
+        .request(Method::DELETE, &path)
+        .header(AUTHORIZATION, &consent)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_ACCEPTABLE, response.status());
+
+    // Add a second passkey so the first may be deleted
+    let mut authenticator = SoftwareAuthenticator::new(Algorithm::Es256).unwrap();
+    let challenge = server
+        .create_challenge(
+            json!({ "purpose": "registration" }),
+            Some((&identity.id, &identity.bearer)),
+        )
+        .await;
+    let response = server
+        .request(Method::POST, "/public-keys")
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({
+            "displayName": "Replacement Passkey",
+            "credential": server.create_credential(&mut authenticator, &challenge),
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let response = server
+        .request(Method::DELETE, &path)
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let consent = server
+        .consent(&mut identity, &format!("DELETE {path}"))
+        .await;
+    let response = server
+        .request(Method::DELETE, &path)
+        .header(AUTHORIZATION, &consent)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let response = server
+        .request(Method::GET, "/public-keys")
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    let public_keys = body["publicKeys"].as_array().unwrap();
+    assert_eq!(1, public_keys.len());
+    assert_ne!(first_key, public_keys[0]["rawId"]);
+
+    server.stop().await;
+}
@@ -0,0 +1,189 @@
+use core::net::{IpAddr, Ipv4Addr};
+
+use http::{StatusCode, header::RETRY_AFTER};
+use reqwest::Method;
+use serde_json::json;
+
+use crate::{rate_limit::BucketConfig, tests::harness::TestServer};
+
+const UNLIMITED: BucketConfig = BucketConfig {
+    burst: 1000,
+    per_minute: 1000,
+};
+
+#[tokio::test]
+async fn limits_each_ip_address() {
+    let server = TestServer::start_with_config(|config| {
+        config.rate_limit_config.enabled = true;
+        config.rate_limit_config.per_ip_address = BucketConfig {
+            burst: 2,
+            per_minute: 1,
+        };
+        config.rate_limit_config.per_api_key = UNLIMITED;
+        config.rate_limit_config.per_username = UNLIMITED;
+    })
+    .await;
+
+    for _ in 0..2 {
+        server
+            .create_challenge(json!({ "purpose": "authentication" }), None)
+            .await;
+    }
+
+    let response = server
+        .request(Method::POST, "/challenges")
+        .json(&json!({ "purpose": "authentication" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    let retry_after: u64 = response.headers()[RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+
+    // Other routes are not limited
+    let response = server
+        .request_without_api_key(Method::GET, "/.well-known/jwks.json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn limits_login_options_and_authorization_requests() {
+    let server = TestServer::start_with_config(|config| {
+        config.rate_limit_config.enabled = true;
+        config.rate_limit_config.per_ip_address = BucketConfig {
+            burst: 2,
+            per_minute: 1,
+        };
+        config.rate_limit_config.per_api_key = UNLIMITED;
+        config.rate_limit_config.per_username = UNLIMITED;
+    })
+    .await;
+
+    let request_options = || {
+        server
+            .request(Method::GET, "/credential-request-options")
+            .send()
+    };
+    let authorize = || {
+        server
+            .request_without_api_key(Method::GET, "/authorize")
+            .query(&[("response_type", "code"), ("client_id", "unknown")])
+            .send()
+    };
+
+    assert_eq!(StatusCode::OK, request_options().await.unwrap().status());
+    assert_ne!(
+        StatusCode::TOO_MANY_REQUESTS,
+        authorize().await.unwrap().status()
+    );
+
+    // Both routes take from the client's bucket
+    assert_eq!(
+        StatusCode::TOO_MANY_REQUESTS,
+        request_options().await.unwrap().status()
+    );
+    assert_eq!(
+        StatusCode::TOO_MANY_REQUESTS,
+        authorize().await.unwrap().status()
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn limits_forwarded_addresses_from_trusted_proxies() {
+    let server = TestServer::start_with_config(|config| {
+        config.listen_config.trusted_proxies = vec![IpAddr::V4(Ipv4Addr::LOCALHOST)];
+        config.rate_limit_config.enabled = true;
+        config.rate_limit_config.per_ip_address = BucketConfig {
+            burst: 1,
+            per_minute: 1,
+        };
+        config.rate_limit_config.per_api_key = UNLIMITED;
+        config.rate_limit_config.per_username = UNLIMITED;
+    })
+    .await;
+
+    let challenge = |forwarded_for: &str| {
+        server
+            .request(Method::POST, "/challenges")
+            .header("x-forwarded-for", forwarded_for)
+            .json(&json!({ "purpose": "authentication" }))
+            .send()
+    };
+    assert_eq!(
+        StatusCode::CREATED,
+        challenge("203.0.113.1").await.unwrap().status()
+    );
+    assert_eq!(
+        StatusCode::TOO_MANY_REQUESTS,
+        challenge("203.0.113.1").await.unwrap().status()
+    );
+
+    // Clients behind the proxy have their own bucket, and an address written by the client before
+    // the proxy's entry is ignored
+    assert_eq!(
+        StatusCode::CREATED,
+        challenge("203.0.113.1, 203.0.113.2")
+            .await
+            .unwrap()
+            .status()
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn limits_each_username() {
+    let server = TestServer::start_with_config(|config| {
+        config.rate_limit_config.enabled = true;
+        config.rate_limit_config.per_ip_address = UNLIMITED;
+        config.rate_limit_config.per_api_key = UNLIMITED;
+        config.rate_limit_config.per_username = BucketConfig {
+            burst: 1,
+            per_minute: 1,
+        };
+    })
+    .await;
+
+    let lookup = |username: &str| {
+        server
+            .request(Method::GET, "/existing-credentials")
+            .query(&[("username", username)])
+            .send()
+    };
+    assert_eq!(StatusCode::OK, lookup("targeted").await.unwrap().status());
+    assert_eq!(
+        StatusCode::TOO_MANY_REQUESTS,
+        lookup("targeted").await.unwrap().status()
+    );
+    assert_eq!(StatusCode::OK, lookup("bystander").await.unwrap().status());
+
+    // The username is also read from the body when registering
+    let response = server
+        .request(Method::POST, "/identities")
+        .json(&json!({ "username": "targeted", "displayName": "Targeted" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+
+    let response = server
+        .request(Method::POST, "/identities")
+        .json(&json!({ "username": "newcomer", "displayName": "Newcomer" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    server.stop().await;
+}
@@ -0,0 +1,105 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::tests::harness::{TestServer, bearer, claims};
+
+#[tokio::test]
+async fn rotates_refresh_token() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("refresher").await;
+    let response = server.login(&mut identity).await;
+    let body: Value = response.json().await.unwrap();
+    let refresh_token = body["refreshToken"].as_str().unwrap().to_string();
+
+    let response = server
+        .request(Method::POST, "/refresh-tokens")
+        .json(&json!({ "refreshToken": refresh_token }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let bearer = bearer(response.headers());
+    assert_eq!(identity.id, claims(&bearer)["sub"]);
+    assert_eq!("common", claims(&bearer)["typ"]);
+
+    let body: Value = response.json().await.unwrap();
+    let rotated = body["refreshToken"].as_str().unwrap();
+    assert_ne!(refresh_token, rotated);
+
+    let response = server
+        .request(Method::GET, &format!("/identities/{}", identity.id))
+        .header(AUTHORIZATION, &bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn revokes_family_on_reuse() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("leaked").await;
+    let response = server.login(&mut identity).await;
+    let login = bearer(response.headers());
+    let body: Value = response.json().await.unwrap();
+    let refresh_token = body["refreshToken"].as_str().unwrap().to_string();
+
+    let response = server
+        .request(Method::POST, "/refresh-tokens")
+        .json(&json!({ "refreshToken": refresh_token }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let refreshed = bearer(response.headers());
+    let body: Value = response.json().await.unwrap();
+    let rotated = body["refreshToken"].as_str().unwrap().to_string();
+
+    // Presenting the used token again revokes every token in the family
+    let response = server
+        .request(Method::POST, "/refresh-tokens")
+        .json(&json!({ "refreshToken": refresh_token }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = server
+        .request(Method::POST, "/refresh-tokens")
+        .json(&json!({ "refreshToken": rotated }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    for bearer in [&login, &refreshed] {
+        let response = server
+            .request(Method::GET, &format!("/identities/{}", identity.id))
+            .header(AUTHORIZATION, bearer)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_unknown_refresh_token() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request(Method::POST, "/refresh-tokens")
+        .json(&json!({ "refreshToken": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
@@ -0,0 +1,39 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+
+use crate::tests::harness::{TestServer, claims};
+
+#[tokio::test]
+async fn revokes_own_token() {
+    let server = TestServer::start().await;
+    let identity = server.register("revoker").await;
+    let token_id = claims(&identity.bearer)["tid"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let path = format!("/revoked-tokens/{token_id}");
+
+    let response = server.request(Method::GET, &path).send().await.unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let response = server
+        .request(Method::POST, "/revoked-tokens")
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let response = server.request(Method::GET, &path).send().await.unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let response = server
+        .request(Method::GET, &format!("/identities/{}", identity.id))
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
@@ -0,0 +1,82 @@
+use http::{StatusCode, header::AUTHORIZATION};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::tests::harness::{TestServer, bearer, claims, credential_id};
+
+#[tokio::test]
+async fn lists_sessions_of_identity() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("sessions").await;
+    let response = server.login(&mut identity).await;
+    let login = bearer(response.headers());
+
+    let response = server
+        .request(Method::GET, "/sessions")
+        .header(AUTHORIZATION, &login)
+        .header("User-Agent", "integration-test")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: Value = response.json().await.unwrap();
+    let sessions = body["sessions"].as_array().unwrap();
+    let token_ids: Vec<_> = sessions.iter().map(|session| &session["tokenId"]).collect();
+    assert!(token_ids.contains(&&claims(&identity.bearer)["tid"]));
+    assert!(token_ids.contains(&&claims(&login)["tid"]));
+    assert!(
+        sessions
+            .iter()
+            .all(|session| session["publicKeyId"] == credential_id(&identity.authenticator))
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn revokes_all_sessions_with_consent() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("signout").await;
+    let response = server.login(&mut identity).await;
+    let login = bearer(response.headers());
+    let body: Value = response.json().await.unwrap();
+    let refresh_token = body["refreshToken"].as_str().unwrap().to_string();
+
+    let response = server
+        .request(Method::DELETE, "/sessions")
+        .header(AUTHORIZATION, &login)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let consent = server.consent(&mut identity, "DELETE /sessions").await;
+    let response = server
+        .request(Method::DELETE, "/sessions")
+        .header(AUTHORIZATION, &consent)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    for bearer in [&identity.bearer, &login] {
+        let response = server
+            .request(Method::GET, "/sessions")
+            .header(AUTHORIZATION, bearer)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+
+    let response = server
+        .request(Method::POST, "/refresh-tokens")
+        .json(&json!({ "refreshToken": refresh_token }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
@@ -0,0 +1,391 @@
+use http::{
+    StatusCode,
+    header::{AUTHORIZATION, ORIGIN},
+};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::{
+    authenticator::{Algorithm, Ceremony, SoftwareAuthenticator},
+    tests::harness::{TestServer, bearer, claims, credential_id},
+};
+
+#[tokio::test]
+async fn requires_origin_for_challenges() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request_without_api_key(Method::POST, "/challenges")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = server
+        .request_without_origin(Method::POST, "/challenges")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn scopes_challenges_to_token_subject() {
+    let server = TestServer::start().await;
+    let identity = server.register("scoped").await;
+    let other = server.register("unscoped").await;
+
+    let response = server
+        .request(Method::POST, "/challenges")
+        .header(AUTHORIZATION, &identity.bearer)
+        .json(&json!({ "identityId": other.id, "purpose": "authentication" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let response = server
+        .request(Method::POST, "/challenges")
+        .json(&json!({ "identityId": identity.id, "purpose": "authentication" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn lists_existing_credentials() {
+    let server = TestServer::start().await;
+    let identity = server.register("listed").await;
+
+    for query in [
+        format!("username={}", identity.username),
+        format!("identityId={}", identity.id),
+    ] {
+        let response = server
+            .request(Method::GET, &format!("/existing-credentials?{query}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body: Value = response.json().await.unwrap();
+        let credentials = body["credentials"].as_array().unwrap();
+        assert_eq!(1, credentials.len());
+        assert_eq!(credential_id(&identity.authenticator), credentials[0]["id"]);
+    }
+
+    let response = server
+        .request(Method::GET, "/existing-credentials")
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert!(body["credentials"].as_array().unwrap().is_empty());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn creates_credential_options() {
+    let server = TestServer::start().await;
+    let identity = server.register("optioned").await;
+
+    let response = server
+        .request(
+            Method::GET,
+            "/credential-creation-options?preferResidentKey=true",
+        )
+        .header(AUTHORIZATION, &identity.bearer)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let options: Value = response.json().await.unwrap();
+    assert_eq!(server.rp_id, options["rp"]["id"]);
+    assert_eq!(identity.id, options["user"]["id"]);
+    assert_eq!(1, options["excludeCredentials"].as_array().unwrap().len());
+    assert_eq!(
+        "preferred",
+        options["authenticatorSelection"]["residentKey"]
+    );
+
+    let response = server
+        .request(
+            Method::GET,
+            &format!("/credential-request-options?username={}", identity.username),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let options: Value = response.json().await.unwrap();
+    assert_eq!(server.rp_id, options["rpId"]);
+    assert_eq!(1, options["allowCredentials"].as_array().unwrap().len());
+
+    // The challenge from the options can be used for the ceremony
+    let mut authenticator = identity.authenticator;
+    let response = server
+        .request(Method::POST, "/tokens")
+        .json(&json!({
+            "credential": server.get_credential(
+                &mut authenticator,
+                options["challenge"].as_str().unwrap(),
+            ),
+            "typ": "common",
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn issues_common_token_with_passkey() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("login").await;
+
+    let response = server.login(&mut identity).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let bearer = bearer(response.headers());
+    assert_eq!(identity.id, claims(&bearer)["sub"]);
+    assert_eq!("common", claims(&bearer)["typ"]);
+
+    let body: Value = response.json().await.unwrap();
+    assert!(body["refreshToken"].is_string());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn issues_consent_token_without_refresh_token() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("consenting").await;
+
+    let response = server
+        .request_token(
+            &mut identity.authenticator,
+            json!({ "typ": "consent", "act": "DELETE /sessions" }),
+            Some((&identity.id, &identity.bearer)),
+        )
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let bearer = bearer(response.headers());
+    assert_eq!("consent", claims(&bearer)["typ"]);
+    assert_eq!("DELETE /sessions", claims(&bearer)["act"]);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(Value::Null, body["refreshToken"]);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_provisioning_tokens_and_unknown_passkeys() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("rejected").await;
+
+    let response = server
+        .request_token(
+            &mut identity.authenticator,
+            json!({ "typ": "provisioning" }),
+            None,
+        )
+        .await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let mut unknown = SoftwareAuthenticator::new(Algorithm::Es256).unwrap();
+    let response = server
+        .request_token(&mut unknown, json!({ "typ": "common" }), None)
+        .await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_reused_challenge() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("replayed").await;
+
+    let challenge = server
+        .create_challenge(json!({ "purpose": "authentication" }), None)
+        .await;
+    let credential = server.get_credential(&mut identity.authenticator, &challenge);
+    let body = json!({ "credential": credential, "typ": "common" });
+
+    let response = server
+        .request(Method::POST, "/tokens")
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let response = server
+        .request(Method::POST, "/tokens")
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_challenge_for_another_ceremony() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("crossed").await;
+    let bearer = identity.bearer.clone();
+
+    // A registration challenge can't be used to log in
+    let challenge = server
+        .create_challenge(
+            json!({ "purpose": "registration" }),
+            Some((&identity.id, &bearer)),
+        )
+        .await;
+    let response = server
+        .request(Method::POST, "/tokens")
+        .json(&json!({
+            "credential": server.get_credential(&mut identity.authenticator, &challenge),
+            "typ": "common",
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    // Consent to one action can't be used to consent to another
+    let challenge = server
+        .create_challenge(
+            json!({ "purpose": "consent", "act": "DELETE /sessions" }),
+            Some((&identity.id, &bearer)),
+        )
+        .await;
+    let response = server
+        .request(Method::POST, "/tokens")
+        .header(AUTHORIZATION, &bearer)
+        .json(&json!({
+            "credential": server.get_credential(&mut identity.authenticator, &challenge),
+            "typ": "consent",
+            "act": format!("DELETE /identities/{}", identity.id),
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    // Registration and consent challenges are for a known identity
+    let response = server
+        .request(Method::POST, "/challenges")
+        .json(&json!({ "purpose": "registration" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    // Consent is to a specific action
+    let response = server
+        .request(Method::POST, "/challenges")
+        .json(&json!({ "purpose": "consent" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn issues_authentication_challenges_without_a_purpose() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("defaulted").await;
+
+    let response = server
+        .request(Method::POST, "/challenges")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body: Value = response.json().await.unwrap();
+    let challenge = body["challenge"].as_str().unwrap();
+
+    let response = server
+        .request(Method::POST, "/tokens")
+        .json(&json!({
+            "credential": server.get_credential(&mut identity.authenticator, challenge),
+            "typ": "common",
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn rejects_challenges_for_disallowed_origins() {
+    let server = TestServer::start().await;
+    let mut identity = server.register("elsewhere").await;
+
+    let response = server
+        .request_without_origin(Method::POST, "/challenges")
+        .header(ORIGIN, "https://evil.example")
+        .json(&json!({ "purpose": "authentication" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    let response = server
+        .request_without_origin(
+            Method::GET,
+            &format!("/credential-request-options?username={}", identity.username),
+        )
+        .header(ORIGIN, "https://evil.example")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    // A challenge issued to an origin that is no longer allowed can't be used
+    let challenge = [7u8; 32];
+    server
+        .database
+        .client
+        .execute(
+            "INSERT INTO challenges (challenge, origin, purpose) VALUES ($1, $2, 'authentication');",
+            &[&challenge.as_slice(), &"https://evil.example"],
+        )
+        .await
+        .unwrap();
+    let credential = identity
+        .authenticator
+        .get(Ceremony {
+            challenge: &challenge,
+            rp_id: &server.rp_id,
+            origin: "https://evil.example",
+        })
+        .unwrap();
+    let response = server
+        .request(Method::POST, "/tokens")
+        .json(&json!({ "credential": credential, "typ": "common" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    server.stop().await;
+}
@@ -0,0 +1,70 @@
+use std::{fs, time::Instant};
+
+use http::StatusCode;
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::tests::harness::{TestServer, credential_id};
+
+async fn lookup(server: &TestServer, username: &str) -> Vec<Value> {
+    let response = server
+        .request(Method::GET, "/existing-credentials")
+        .query(&[("username", username)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body: Value = response.json().await.unwrap();
+    body["credentials"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn returns_decoys_for_unknown_usernames() {
+    let secret = std::env::temp_dir().join(format!("decoy-secret-{}", uuid::Uuid::new_v4()));
+    fs::write(&secret, "decoy secret").unwrap();
+
+    let server = TestServer::start_with_config(|config| {
+        config.username_protection_config.decoy_secret = Some(secret.clone());
+        config.username_protection_config.minimum_lookup_millis = 200;
+    })
+    .await;
+    let identity = server.register("protected").await;
+
+    let start = Instant::now();
+    let known = lookup(&server, "protected").await;
+    assert!(start.elapsed().as_millis() >= 200);
+    assert_eq!(1, known.len());
+    assert_eq!(credential_id(&identity.authenticator), known[0]["id"]);
+
+    let start = Instant::now();
+    let unknown = lookup(&server, "unknown").await;
+    assert!(start.elapsed().as_millis() >= 200);
+    assert_eq!(1, unknown.len());
+    assert_ne!(known[0]["id"], unknown[0]["id"]);
+
+    // The same decoy is returned for every lookup, and the shapes match
+    assert_eq!(unknown, lookup(&server, "unknown").await);
+    assert_ne!(unknown, lookup(&server, "another").await);
+    let keys = |credential: &Value| {
+        let mut keys: Vec<_> = credential.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    };
+    assert_eq!(keys(&known[0]), keys(&unknown[0]));
+
+    server.stop().await;
+    fs::remove_file(secret).unwrap();
+}
+
+#[tokio::test]
+async fn returns_nothing_for_unknown_usernames_without_decoys() {
+    let server = TestServer::start_with_config(|config| {
+        config.username_protection_config.decoy_secret = None;
+    })
+    .await;
+
+    assert!(lookup(&server, "unknown").await.is_empty());
+
+    server.stop().await;
+}
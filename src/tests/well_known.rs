@@ -0,0 +1,68 @@
+use http::{StatusCode, header::CACHE_CONTROL};
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::tests::harness::TestServer;
+
+#[tokio::test]
+async fn publishes_key_set_and_relying_party() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request(Method::GET, "/.well-known/jwks.json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        "public, max-age=3600",
+        response.headers()[CACHE_CONTROL].to_str().unwrap()
+    );
+    let key_set: Value = response.json().await.unwrap();
+    assert!(!key_set["keys"].as_array().unwrap().is_empty());
+
+    let response = server
+        .request(Method::GET, "/.well-known/relying-party.json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let relying_party: Value = response.json().await.unwrap();
+    assert_eq!(server.rp_id, relying_party["id"]);
+
+    let response = server
+        .request(Method::GET, "/.well-known/public-key-parameters.json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let parameters: Value = response.json().await.unwrap();
+    assert!(
+        parameters["publicKeyParameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|parameter| parameter["alg"] == -7)
+    );
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn publishes_openid_configuration() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .request_without_api_key(Method::GET, "/.well-known/openid-configuration")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let configuration: Value = response.json().await.unwrap();
+    let issuer = server.url.as_str().trim_end_matches('/');
+    assert_eq!(issuer, configuration["issuer"]);
+    assert_eq!(format!("{issuer}/token"), configuration["token_endpoint"]);
+
+    server.stop().await;
+}
@@ -0,0 +1,156 @@
+use core::time::Duration;
+use std::{fs, io, path::PathBuf};
+
+use openssl::{
+    error::ErrorStack,
+    hash::MessageDigest,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_api_helper::webauthn::{
+    public_key_credential::{Transports, Type},
+    public_key_credential_request_options::AllowCredentials,
+};
+
+/// The transports a decoy credential may have, as commonly reported by authenticators.
+const DECOY_TRANSPORTS: [&str; 3] = [r#"["internal"]"#, r#"["hybrid","internal"]"#, r#"["usb"]"#];
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsernameProtectionConfig {
+    /// The path to the secret that decoy credentials for unknown usernames are derived from.
+    /// Unknown usernames have no credentials when this is not set.
+    pub decoy_secret: Option<PathBuf>,
+
+    /// The least time a lookup by username takes, in milliseconds, so known and unknown usernames
+    /// take the same time. Only applies when decoys are enabled.
+    pub minimum_lookup_millis: u64,
+}
+
+impl Default for UsernameProtectionConfig {
+    fn default() -> Self {
+        Self {
+            decoy_secret: None,
+            minimum_lookup_millis: 100,
+        }
+    }
+}
+
+/// Hides which usernames have an identity when looking up their credentials.
+#[derive(Debug)]
+pub struct UsernameProtection {
+    decoy_key: Option<PKey<Private>>,
+    minimum_lookup: Duration,
+}
+
+impl UsernameProtection {
+    pub fn new(config: &UsernameProtectionConfig) -> Result<Self, UsernameProtectionError> {
+        let decoy_key = match &config.decoy_secret {
+            Some(path) => {
+                let secret = fs::read(path).map_err(UsernameProtectionError::read_secret)?;
+                if secret.is_empty() {
+                    return Err(UsernameProtectionError::EmptySecret);
+                }
+                Some(PKey::hmac(&secret).map_err(UsernameProtectionError::hmac)?)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            decoy_key,
+            minimum_lookup: Duration::from_millis(config.minimum_lookup_millis),
+        })
+    }
+
+    /// The least time a lookup by username takes, if decoys are enabled.
+    pub fn minimum_lookup(&self) -> Option<Duration> {
+        self.decoy_key.as_ref().map(|_| self.minimum_lookup)
+    }
+
+    /// The credentials returned for a username without any, the same for every lookup of the
+    /// username. None are returned if decoys are not enabled.
+    pub fn decoy_credentials(
+        &self,
+        username: &str,
+    ) -> Result<Vec<AllowCredentials>, UsernameProtectionError> {
+        let Some(decoy_key) = &self.decoy_key else {
+            return Ok(vec![]);
+        };
+
+        let mut signer = Signer::new(MessageDigest::sha256(), decoy_key)
+            .map_err(UsernameProtectionError::hmac)?;
+        let id = signer
+            .sign_oneshot_to_vec(username.as_bytes())
+            .map_err(UsernameProtectionError::hmac)?;
+
+        let transports = DECOY_TRANSPORTS[usize::from(id[0]) % DECOY_TRANSPORTS.len()];
+        // The decoy transports are valid, so they always parse
+        let transports: Vec<Transports> = serde_json::from_str(transports).unwrap();
+
+        Ok(vec![AllowCredentials {
+            id,
+            transports,
+            r#type: Type::PublicKey,
+        }])
+    }
+}
+
+/// Error variants for loading the decoy secret and deriving decoy credentials.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum UsernameProtectionError {
+    #[non_exhaustive]
+    ReadSecret { source: io::Error },
+
+    #[non_exhaustive]
+    EmptySecret,
+
+    #[non_exhaustive]
+    Hmac { source: ErrorStack },
+}
+impl core::fmt::Display for UsernameProtectionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            Self::ReadSecret { .. } => write!(f, "could not read the decoy secret"),
+            Self::EmptySecret => write!(f, "the decoy secret is empty"),
+            Self::Hmac { .. } => write!(f, "could not derive the decoy credentials"),
+        }
+    }
+}
+impl core::error::Error for UsernameProtectionError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self {
+            Self::ReadSecret { source, .. } => Some(source),
+            Self::EmptySecret => None,
+            Self::Hmac { source, .. } => Some(source),
+        }
+    }
+}
+impl UsernameProtectionError {
+    #[allow(missing_docs)]
+    pub fn read_secret(source: io::Error) -> Self {
+        Self::ReadSecret { source }
+    }
+
+    #[allow(missing_docs)]
+    pub fn hmac(source: ErrorStack) -> Self {
+        Self::Hmac { source }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ts_api_helper::webauthn::public_key_credential::Transports;
+
+    use super::DECOY_TRANSPORTS;
+
+    #[test]
+    fn decoy_transports_parse() {
+        for transports in DECOY_TRANSPORTS {
+            serde_json::from_str::<Vec<Transports>>(transports).unwrap();
+        }
+    }
+}